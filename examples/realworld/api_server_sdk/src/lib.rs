@@ -12,10 +12,10 @@ pub struct ApplicationState {
 }
 #[derive(Debug, thiserror::Error)]
 pub enum ApplicationStateError {
-    #[error(transparent)]
-    EncodingKey(jsonwebtoken::errors::Error),
-    #[error(transparent)]
-    GetPool(sqlx_core::Error),
+    #[error("Failed to build a singleton: `conduit_core::configuration::AuthConfig::encoding_key` returned an error")]
+    EncodingKey(#[source] jsonwebtoken::errors::Error),
+    #[error("Failed to build a singleton: `conduit_core::configuration::DatabaseConfig::get_pool` returned an error")]
+    GetPool(#[source] sqlx_core::Error),
 }
 pub async fn build_application_state(
     v0: &conduit_core::configuration::AuthConfig,
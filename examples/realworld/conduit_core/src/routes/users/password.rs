@@ -1,9 +1,9 @@
 //! This module contains the tools required to validate user-provided passwords 
 //! and store them securely in the database as PHC-encoded password hashes.
-use crate::telemetry::spawn_blocking_with_tracing;
 use anyhow::Context;
 use argon2::password_hash::SaltString;
 use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use pavex::telemetry::spawn_blocking_with_tracing;
 use secrecy::{ExposeSecret, Secret};
 use sqlx::PgPool;
 
@@ -0,0 +1,213 @@
+//! Benchmarks for the hot paths of Pavex's extractors: `QueryParams`, `PathParams` and `JsonBody`.
+//!
+//! Run them with:
+//!
+//! ```bash
+//! cargo bench -p pavex --bench extraction
+//! ```
+//!
+//! All inputs are fixed and defined in this file, so results are comparable across runs
+//! (and across machines, modulo hardware differences).
+//! Each group benchmarks the same payload twice, deserializing string-like values into
+//! `String` and into `Cow<'_, str>`, to quantify the allocations that `Cow` saves when the
+//! input doesn't need to be percent-decoded (or JSON-unescaped).
+use std::borrow::Cow;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use serde::Deserialize;
+
+use bytes::Bytes;
+use pavex::http::{HeaderMap, HeaderValue, Uri};
+use pavex::request::body::{BodySizeLimit, BufferedBody, JsonBody, RawIncomingBody};
+use pavex::request::path::{PathParams, RawPathParams};
+use pavex::request::query::QueryParams;
+use pavex::request::RequestHead;
+
+/// A query string with a mix of plain, percent-encoded and sequence values.
+const QUERY: &str = "/search?\
+    q=rust%20web%20framework&\
+    author=LukeMathWalker&\
+    page=3&\
+    per_page=50&\
+    sort=updated_at&\
+    tag[]=web&tag[]=http&tag[]=async&tag[]=api&tag[]=server&\
+    id[]=1&id[]=2&id[]=3&id[]=4&id[]=5&id[]=6&id[]=7&id[]=8";
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct OwnedQuery {
+    q: String,
+    author: String,
+    page: u32,
+    per_page: u32,
+    sort: String,
+    #[serde(rename = "tag[]")]
+    tags: Vec<String>,
+    #[serde(rename = "id[]")]
+    ids: Vec<u64>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct BorrowedQuery<'a> {
+    #[serde(borrow)]
+    q: Cow<'a, str>,
+    #[serde(borrow)]
+    author: Cow<'a, str>,
+    page: u32,
+    per_page: u32,
+    #[serde(borrow)]
+    sort: Cow<'a, str>,
+    #[serde(rename = "tag[]", borrow)]
+    tags: Vec<Cow<'a, str>>,
+    #[serde(rename = "id[]")]
+    ids: Vec<u64>,
+}
+
+/// A route template with several parameters and a matching path, including a
+/// percent-encoded segment.
+const ROUTE: &str = "/orgs/:org/repos/:repo/issues/:issue_id/comments/:comment_id";
+const PATH: &str = "/orgs/pavex-rs/repos/pavex%20core/issues/1234/comments/98765";
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct OwnedPath {
+    org: String,
+    repo: String,
+    issue_id: u64,
+    comment_id: u64,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct BorrowedPath<'a> {
+    #[serde(borrow)]
+    org: Cow<'a, str>,
+    #[serde(borrow)]
+    repo: Cow<'a, str>,
+    issue_id: u64,
+    comment_id: u64,
+}
+
+/// A JSON payload of ~100 records, with escaped strings and nested sequences.
+fn json_payload() -> String {
+    let records: Vec<String> = (0..100)
+        .map(|i| {
+            format!(
+                r#"{{"id":{i},"name":"user-{i}","bio":"Line one\nLine \"two\"","tags":["a","b","c"],"active":{}}}"#,
+                i % 2 == 0
+            )
+        })
+        .collect();
+    format!(r#"{{"records":[{}]}}"#, records.join(","))
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct OwnedRecord {
+    id: u64,
+    name: String,
+    bio: String,
+    tags: Vec<String>,
+    active: bool,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct OwnedPayload {
+    records: Vec<OwnedRecord>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct BorrowedRecord<'a> {
+    id: u64,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(borrow)]
+    bio: Cow<'a, str>,
+    #[serde(borrow)]
+    tags: Vec<Cow<'a, str>>,
+    active: bool,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct BorrowedPayload<'a> {
+    #[serde(borrow)]
+    records: Vec<BorrowedRecord<'a>>,
+}
+
+fn request_head(uri: &str, headers: HeaderMap) -> RequestHead {
+    let mut request = http::Request::new(());
+    *request.uri_mut() = uri.parse::<Uri>().unwrap();
+    *request.headers_mut() = headers;
+    request.into_parts().0.into()
+}
+
+fn buffered_body(head: &RequestHead, bytes: Vec<u8>) -> BufferedBody {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let body = RawIncomingBody::from(Bytes::from(bytes));
+    runtime
+        .block_on(BufferedBody::extract(head, body, BodySizeLimit::Disabled))
+        .unwrap()
+}
+
+fn query_params(c: &mut Criterion) {
+    let head = request_head(QUERY, HeaderMap::new());
+    let mut group = c.benchmark_group("QueryParams");
+    group.bench_function("String fields", |b| {
+        b.iter(|| QueryParams::<OwnedQuery>::extract(black_box(&head)).unwrap())
+    });
+    group.bench_function("Cow fields", |b| {
+        b.iter(|| QueryParams::<BorrowedQuery>::extract(black_box(&head)).unwrap())
+    });
+    group.finish();
+}
+
+fn path_params(c: &mut Criterion) {
+    let mut router = matchit::Router::new();
+    router.insert(ROUTE, ()).unwrap();
+    let matched = router.at(PATH).unwrap();
+
+    let mut group = c.benchmark_group("PathParams");
+    group.bench_function("String fields", |b| {
+        b.iter_batched(
+            || RawPathParams::from(matched.params.clone()),
+            |params| PathParams::<OwnedPath>::extract(black_box(params)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("Cow fields", |b| {
+        b.iter_batched(
+            || RawPathParams::from(matched.params.clone()),
+            |params| PathParams::<BorrowedPath>::extract(black_box(params)).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn json_body(c: &mut Criterion) {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        pavex::http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    let head = request_head("/records", headers);
+    let body = buffered_body(&head, json_payload().into_bytes());
+
+    let mut group = c.benchmark_group("JsonBody");
+    group.bench_function("String fields", |b| {
+        b.iter(|| JsonBody::<OwnedPayload>::extract(black_box(&head), black_box(&body)).unwrap())
+    });
+    group.bench_function("Cow fields", |b| {
+        b.iter(|| JsonBody::<BorrowedPayload>::extract(black_box(&head), black_box(&body)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, query_params, path_params, json_body);
+criterion_main!(benches);
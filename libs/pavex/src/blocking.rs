@@ -0,0 +1,215 @@
+//! Run blocking work (e.g. CPU-heavy computations or synchronous IO) without stalling
+//! the async runtime.
+//!
+//! Check out [`BlockingPool`] for more details.
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinError;
+
+use crate::blueprint::constructor::{CloningStrategy, Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::telemetry::spawn_blocking_with_tracing;
+
+#[derive(Debug, Clone)]
+/// A pool to offload blocking work from the async worker threads.
+///
+/// Request handlers (and middlewares) run on the async worker threads of the `tokio` runtime.
+/// If they perform CPU-bound work (e.g. hashing a password) or blocking IO (e.g. reading a
+/// file with `std::fs`), they prevent the worker from making progress on other requests.
+/// Use [`BlockingPool::run`] to execute that work on a dedicated thread instead.
+///
+/// The current `tracing` span is propagated to the closure: the events it emits are
+/// attached to the span of the request that spawned it.
+///
+/// # Installation
+///
+/// Register the [default constructor](BlockingPool::new) for [`BlockingPool`] alongside the
+/// [default constructor](BlockingPoolConfig::register) for [`BlockingPoolConfig`]:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blocking::{BlockingPool, BlockingPoolConfig};
+/// use pavex::blueprint::{router::POST, Blueprint};
+/// use pavex::response::Response;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     BlockingPoolConfig::register(&mut bp);
+///     BlockingPool::register(&mut bp);
+///     bp.route(POST, "/digest", f!(crate::digest));
+///     bp
+/// }
+///
+/// pub async fn digest(pool: &BlockingPool) -> Response {
+///     let digest = pool.run(|| expensive_computation()).await;
+///     match digest {
+///         Ok(digest) => Response::ok().set_typed_body(digest),
+///         // The closure panicked.
+///         Err(_) => Response::internal_server_error(),
+///     }
+/// }
+///
+/// fn expensive_computation() -> String {
+///     // [...]
+///     # todo!()
+/// }
+/// ```
+///
+/// # Sizing
+///
+/// [`BlockingPool`] relies on the blocking thread pool managed by the `tokio` runtime.
+/// [`BlockingPoolConfig::max_concurrency`] caps the number of closures that can run
+/// concurrently: additional closures wait for a free slot before being spawned.
+/// The cap is bounded, in turn, by the maximum number of blocking threads allowed by the
+/// runtime—check out `tokio`'s `Builder::max_blocking_threads`.
+pub struct BlockingPool {
+    permits: Arc<Semaphore>,
+}
+
+impl BlockingPool {
+    /// Build a new [`BlockingPool`] according to the provided configuration.
+    pub fn new(config: &BlockingPoolConfig) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(config.max_concurrency.get())),
+        }
+    }
+
+    /// Run `f` on a blocking thread and return a future that resolves to its output.
+    ///
+    /// The current `tracing` span is captured when this method is called.
+    /// If the pool is at capacity, `f` is spawned as soon as a running closure completes.
+    ///
+    /// The returned future resolves to an error if `f` panics.
+    ///
+    /// The returned future must be polled for `f` to be spawned: if it's dropped while
+    /// waiting for a free slot, `f` never runs.
+    /// Once `f` has been spawned, it runs to completion even if the returned future is dropped.
+    pub fn run<F, R>(&self, f: F) -> impl Future<Output = Result<R, JoinError>> + Send + 'static
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let span = tracing::Span::current();
+        let permits = self.permits.clone();
+        async move {
+            let permit = permits
+                .acquire_owned()
+                .await
+                .expect("The semaphore of a blocking pool is never closed");
+            span.in_scope(|| {
+                spawn_blocking_with_tracing(move || {
+                    let output = f();
+                    drop(permit);
+                    output
+                })
+            })
+            .await
+        }
+    }
+
+    /// Register the [default constructor](BlockingPool::new)
+    /// for [`BlockingPool`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(f!(pavex::blocking::BlockingPool::new), Lifecycle::Singleton)
+            .cloning(CloningStrategy::CloneIfNecessary)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Determine how many closures a [`BlockingPool`] can run concurrently.
+///
+/// Check out [`BlockingPool`]'s documentation for more details.
+pub struct BlockingPoolConfig {
+    max_concurrency: NonZeroUsize,
+}
+
+impl BlockingPoolConfig {
+    /// Set the maximum number of closures that can run concurrently on the pool.
+    ///
+    /// It defaults to the amount of parallelism available on the machine—a good fit for
+    /// CPU-bound work. Raise it if your closures spend most of their time waiting on IO.
+    pub fn max_concurrency(mut self, max_concurrency: NonZeroUsize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Register the [default constructor](BlockingPoolConfig::default)
+    /// for [`BlockingPoolConfig`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::blocking::BlockingPoolConfig as std::default::Default>::default),
+            Lifecycle::Singleton,
+        )
+    }
+}
+
+impl Default for BlockingPoolConfig {
+    fn default() -> Self {
+        let max_concurrency = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+        Self { max_concurrency }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use super::{BlockingPool, BlockingPoolConfig};
+    use crate::telemetry::recorder::Recorder;
+
+    #[tokio::test]
+    async fn closures_run_off_the_async_worker_within_the_current_span() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+        let pool = BlockingPool::new(&BlockingPoolConfig::default());
+
+        let span = tracing::info_span!("request");
+        let span_id = span.id().unwrap().into_u64();
+        let future = span.in_scope(|| pool.run(|| std::thread::current().id()));
+        let blocking_thread = future.await.unwrap();
+
+        assert_ne!(blocking_thread, std::thread::current().id());
+        let entered = recorder.entered();
+        assert!(
+            entered.contains(&(span_id, blocking_thread)),
+            "The span was not entered on the blocking thread: {entered:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn panics_are_surfaced_as_errors() {
+        let pool = BlockingPool::new(&BlockingPoolConfig::default());
+        let outcome = pool.run(|| -> u32 { panic!("Oh no") }).await;
+        assert!(outcome.unwrap_err().is_panic());
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_capped() {
+        let config = BlockingPoolConfig::default().max_concurrency(NonZeroUsize::MIN);
+        let pool = BlockingPool::new(&config);
+        let running = Arc::new(AtomicU64::new(0));
+        let max_running = Arc::new(AtomicU64::new(0));
+
+        let tasks = (0..4).map(|_| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            pool.run(move || {
+                let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(n, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+        for outcome in futures_util::future::join_all(tasks).await {
+            outcome.unwrap();
+        }
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::blueprint::internals::RegisteredFallback;
-use crate::blueprint::router::Fallback;
+use crate::blueprint::router::{Fallback, MethodNotAllowed};
+use crate::f;
 
 use super::constructor::{Constructor, Lifecycle};
 use super::internals::{
-    NestedBlueprint, RegisteredCallable, RegisteredConstructor, RegisteredRoute,
-    RegisteredWrappingMiddleware,
+    NestedBlueprint, RegisteredBasePath, RegisteredCallable, RegisteredConstructor,
+    RegisteredRoute, RegisteredWrappingMiddleware,
 };
 use super::middleware::WrappingMiddleware;
 use super::reflection::{Location, RawCallable, RawCallableIdentifiers};
@@ -26,7 +27,11 @@ use super::router::{MethodGuard, Route};
 /// - route handlers, registered via [`Blueprint::route`]
 /// - constructors, registered via [`Blueprint::constructor`]
 /// - wrapping middlewares, registered via [`Blueprint::wrap`]
+/// - authentication requirements, registered via [`Blueprint::require_auth`]
 /// - fallback handlers, registered via [`Blueprint::fallback`]
+/// - `405 Method Not Allowed` handlers, registered via [`Blueprint::method_not_allowed`]
+/// - a response hook, registered via [`Blueprint::response_hook`]
+/// - type-wide error handlers, registered via [`Blueprint::error_handler`]
 ///
 /// You can also choose to decompose your overall application into smaller sub-components,
 /// taking advantage of [`Blueprint::nest`] and [`Blueprint::nest_at`].
@@ -44,8 +49,35 @@ pub struct Blueprint {
     pub routes: Vec<RegisteredRoute>,
     /// The fallback request handler, if any.
     pub fallback_request_handler: Option<RegisteredFallback>,
+    /// The request handler for requests that match a route path but not its method guard,
+    /// if any.
+    ///
+    /// Check out [`Blueprint::method_not_allowed`] for more details.
+    #[serde(default)]
+    pub method_not_allowed_handler: Option<RegisteredFallback>,
+    /// The hook applied to every outgoing response, if any.
+    ///
+    /// Check out [`Blueprint::response_hook`] for more details.
+    #[serde(default)]
+    pub response_hook: Option<RegisteredCallable>,
+    /// All type-wide error handlers, in the order they were registered.
+    ///
+    /// Check out [`Blueprint::error_handler`] for more details.
+    #[serde(default)]
+    pub error_handlers: Vec<RegisteredCallable>,
     /// All blueprints nested under this one, in the order they were nested.
     pub nested_blueprints: Vec<NestedBlueprint>,
+    /// If `true`, the generated code will log a message every time a request-scoped
+    /// constructor fails.
+    ///
+    /// Check out [`Blueprint::log_constructor_failures`] for more details.
+    #[serde(default)]
+    pub log_constructor_failures: bool,
+    /// The path prefix shared by all the routes in the application, if any.
+    ///
+    /// Check out [`Blueprint::base_path`] for more details.
+    #[serde(default)]
+    pub base_path: Option<RegisteredBasePath>,
 }
 
 impl Default for Blueprint {
@@ -56,8 +88,13 @@ impl Default for Blueprint {
             constructors: Default::default(),
             routes: Default::default(),
             fallback_request_handler: None,
+            method_not_allowed_handler: None,
+            response_hook: None,
+            error_handlers: Default::default(),
             nested_blueprints: Default::default(),
             middlewares: Default::default(),
+            log_constructor_failures: false,
+            base_path: None,
         }
     }
 }
@@ -96,6 +133,13 @@ impl Blueprint {
     /// # }
     /// ```
     ///
+    /// # `Send`
+    ///
+    /// Request handlers don't need to return `Send` futures.
+    /// Each connection is served, from start to finish, by the same worker thread: you
+    /// can hold non-`Send` values (e.g. an `Rc` or a `RefCell` guard) across `.await` points
+    /// in your handlers, middlewares and constructors.
+    ///
     /// [`router`]: crate::blueprint::router
     /// [`PathParams`]: struct@crate::request::path::PathParams
     pub fn route(&mut self, method_guard: MethodGuard, path: &str, callable: RawCallable) -> Route {
@@ -107,6 +151,8 @@ impl Blueprint {
                 location: std::panic::Location::caller().into(),
             },
             error_handler: None,
+            priority: 0,
+            cache_control: None,
         };
         let route_id = self.routes.len();
         self.routes.push(registered_route);
@@ -116,6 +162,59 @@ impl Blueprint {
         }
     }
 
+    #[track_caller]
+    /// Register a request handler, but only if `condition` is `true`.
+    ///
+    /// It behaves exactly like [`Blueprint::route`] when `condition` is `true`.
+    /// It's a no-op otherwise: the route is not registered and `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    /// use pavex::response::Response;
+    ///
+    /// fn debug_handler() -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// // The `/debug` route is only available in debug builds.
+    /// bp.route_if(cfg!(debug_assertions), GET, "/debug", f!(crate::debug_handler));
+    /// # }
+    /// ```
+    ///
+    /// Use [`Option::map`] if you need to further configure the route
+    /// (e.g. to register an error handler):
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    /// # struct DebugError;
+    /// # fn debug_handler() -> Result<pavex::response::Response, DebugError> { todo!() }
+    /// # fn debug_error(_e: &DebugError) -> pavex::response::Response { todo!() }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route_if(cfg!(debug_assertions), GET, "/debug", f!(crate::debug_handler))
+    ///     .map(|route| route.error_handler(f!(crate::debug_error)));
+    /// # }
+    /// ```
+    pub fn route_if(
+        &mut self,
+        condition: bool,
+        method_guard: MethodGuard,
+        path: &str,
+        callable: RawCallable,
+    ) -> Option<Route> {
+        if condition {
+            Some(self.route(method_guard, path, callable))
+        } else {
+            None
+        }
+    }
+
     #[track_caller]
     /// Register a constructor.
     ///
@@ -145,6 +244,47 @@ impl Blueprint {
     /// bp.constructor(f!(crate::logger), Lifecycle::Transient);
     /// # }
     /// ```
+    ///
+    /// # Projections
+    ///
+    /// A constructor can return a reference that borrows from its inputs.
+    /// It's a convenient way to expose a part of a larger type (e.g. a section of your
+    /// application settings) without cloning it or registering a separate singleton for it:
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, constructor::Lifecycle};
+    /// # pub struct DatabaseSettings;
+    /// # pub struct RedisSettings;
+    ///
+    /// pub struct Settings {
+    ///     pub database: DatabaseSettings,
+    ///     pub redis: RedisSettings,
+    /// }
+    ///
+    /// pub fn database(settings: &Settings) -> &DatabaseSettings {
+    ///     &settings.database
+    /// }
+    ///
+    /// pub fn redis(settings: &Settings) -> &RedisSettings {
+    ///     &settings.redis
+    /// }
+    ///
+    /// # fn main() {
+    /// # fn settings() -> Settings { todo!() }
+    /// let mut bp = Blueprint::new();
+    /// bp.constructor(f!(crate::settings), Lifecycle::Singleton);
+    /// bp.constructor(f!(crate::database), Lifecycle::RequestScoped);
+    /// bp.constructor(f!(crate::redis), Lifecycle::RequestScoped);
+    /// # }
+    /// ```
+    ///
+    /// Components can then ask for `&DatabaseSettings` or `&RedisSettings` directly.
+    /// Pavex makes sure that the borrowed inputs outlive the output: singletons live in the
+    /// application state, so a projection from a singleton is valid for the whole request.
+    ///
+    /// A constructor that borrows from its inputs can't be a singleton: its output can't
+    /// be stored in the application state. Register it as request-scoped instead.
     pub fn constructor(&mut self, callable: RawCallable, lifecycle: Lifecycle) -> Constructor {
         let registered_constructor = RegisteredConstructor {
             constructor: RegisteredCallable {
@@ -154,6 +294,7 @@ impl Blueprint {
             lifecycle,
             cloning_strategy: None,
             error_handler: None,
+            optional: false,
         };
         let constructor_id = self.constructors.len();
         self.constructors.push(registered_constructor);
@@ -322,6 +463,7 @@ impl Blueprint {
                 location: std::panic::Location::caller().into(),
             },
             error_handler: None,
+            authenticator: None,
         };
         let middleware_id = self.middlewares.len();
         self.middlewares.push(registered);
@@ -331,6 +473,110 @@ impl Blueprint {
         }
     }
 
+    #[track_caller]
+    /// Require authentication for all the routes registered against this blueprint,
+    /// including those coming from nested blueprints.
+    ///
+    /// `authenticator` is registered as a [request-scoped](Lifecycle::RequestScoped)
+    /// constructor for the type that identifies the caller (e.g. a `CurrentUser`).
+    /// Pavex invokes it for every request to the protected routes, before the request
+    /// handler, even if neither the handler nor any other component asks for its output.
+    /// Request handlers can then inject the caller's identity like any other type.
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    /// use pavex::request::RequestHead;
+    /// use pavex::response::Response;
+    ///
+    /// pub struct CurrentUser {
+    ///     pub name: String,
+    /// }
+    ///
+    /// pub fn authenticate(request_head: &RequestHead) -> Result<CurrentUser, Response> {
+    ///     let name = request_head
+    ///         .headers
+    ///         .get("x-user")
+    ///         .and_then(|v| v.to_str().ok())
+    ///         .ok_or_else(Response::unauthorized)?;
+    ///     Ok(CurrentUser { name: name.to_owned() })
+    /// }
+    ///
+    /// pub fn greet(user: &CurrentUser) -> String {
+    ///     format!("Hello, {}!", user.name)
+    /// }
+    ///
+    /// fn admin() -> Blueprint {
+    ///     let mut bp = Blueprint::new();
+    ///     bp.require_auth(f!(crate::authenticate));
+    ///     bp.route(GET, "/greet", f!(crate::greet));
+    ///     bp
+    /// }
+    ///
+    /// fn blueprint() -> Blueprint {
+    ///     let mut bp = Blueprint::new();
+    ///     // Only the routes under `/admin` require authentication.
+    ///     bp.nest_at("/admin", admin());
+    ///     // [...]
+    ///     bp
+    /// }
+    /// ```
+    ///
+    /// # Unauthenticated requests
+    ///
+    /// Authenticators are expected to be fallible: requests that can't be authenticated are
+    /// short-circuited, i.e. the request handler is never invoked.
+    ///
+    /// - If the authenticator returns `Result<T, Response>`, the `Err` response is returned
+    ///   to the caller as it is—typically a `401 Unauthorized`.
+    ///   Check out [`Constructor::error_handler`] for more details on short-circuiting.
+    /// - If it returns any other error type, register an error handler on the returned
+    ///   [`Constructor`] to convert it into a response.
+    ///
+    /// # Execution order
+    ///
+    /// The authenticator is enforced by a wrapping middleware. It is invoked in the order
+    /// it was registered with respect to the other middlewares on this blueprint:
+    /// middlewares registered before [`Blueprint::require_auth`] see the responses returned
+    /// for unauthenticated requests.
+    ///
+    /// # Constructors
+    ///
+    /// The authenticator is registered as the request-scoped constructor for the type it returns.
+    /// You can't register another constructor for that type against this blueprint, or against
+    /// the blueprints nested under it: Pavex will reject it, since it could be used in place of
+    /// the authenticator.
+    ///
+    /// [`Constructor::error_handler`]: super::constructor::Constructor::error_handler
+    pub fn require_auth(&mut self, authenticator: RawCallable) -> Constructor {
+        let authenticator = RawCallableIdentifiers::from_raw_callable(authenticator);
+        let location: Location = std::panic::Location::caller().into();
+        self.middlewares.push(RegisteredWrappingMiddleware {
+            middleware: RegisteredCallable {
+                callable: RawCallableIdentifiers::from_raw_callable(f!(
+                    pavex::middleware::authenticated
+                )),
+                location: location.clone(),
+            },
+            error_handler: None,
+            authenticator: Some(authenticator.clone()),
+        });
+        let constructor_id = self.constructors.len();
+        self.constructors.push(RegisteredConstructor {
+            constructor: RegisteredCallable {
+                callable: authenticator,
+                location,
+            },
+            lifecycle: Lifecycle::RequestScoped,
+            cloning_strategy: None,
+            error_handler: None,
+            optional: false,
+        });
+        Constructor {
+            constructor_id,
+            blueprint: self,
+        }
+    }
+
     #[track_caller]
     /// Nest a [`Blueprint`] under the current [`Blueprint`] (the parent), adding a common prefix to all the new routes.  
     ///
@@ -506,6 +752,84 @@ impl Blueprint {
         })
     }
 
+    /// Log a message every time a request-scoped constructor fails, before its error
+    /// handler is invoked.
+    ///
+    /// The log message includes the path of the failing constructor as well as the
+    /// chain of components that (transitively) depend on it, all the way up to the request
+    /// handler (or middleware) that triggered its invocation.
+    /// It can be a big time-saver when a failure occurs deep into your dependency graph.
+    ///
+    /// Logs are emitted via [`tracing`](https://docs.rs/tracing), at the `ERROR` level.
+    /// Check out [`log_constructor_failure`](crate::telemetry::log_constructor_failure) for
+    /// more details on the log format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::blueprint::Blueprint;
+    ///
+    /// let mut bp = Blueprint::new();
+    /// bp.log_constructor_failures();
+    /// ```
+    ///
+    /// # Scope
+    ///
+    /// Failure logs are disabled by default.
+    /// This setting can only be enabled for the application as a whole: it is ignored if invoked
+    /// on a blueprint that gets nested under another one.
+    pub fn log_constructor_failures(&mut self) {
+        self.log_constructor_failures = true;
+    }
+
+    #[track_caller]
+    /// Serve the whole application under a common path prefix—e.g. `/service-a` if your
+    /// application sits behind a reverse proxy that forwards all `/service-a` requests to it.
+    ///
+    /// The base path is prepended to the path of every route, including those coming
+    /// from nested blueprints.
+    /// It stacks with the prefixes passed to [`Blueprint::nest_at`]: the base path comes first,
+    /// followed by the nesting prefixes, followed by the route path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    /// # pub fn health_check() {}
+    /// # pub fn get_user() {}
+    ///
+    /// fn blueprint() -> Blueprint {
+    ///     let mut bp = Blueprint::new();
+    ///     bp.base_path("/service-a");
+    ///     // Matches `GET /service-a/health`.
+    ///     bp.route(GET, "/health", f!(crate::health_check));
+    ///     // Matches `GET /service-a/api/users/:id`.
+    ///     bp.nest_at("/api", api_bp());
+    ///     bp
+    /// }
+    ///
+    /// fn api_bp() -> Blueprint {
+    ///     let mut bp = Blueprint::new();
+    ///     bp.route(GET, "/users/:id", f!(crate::get_user));
+    ///     bp
+    /// }
+    /// ```
+    ///
+    /// # Constraints
+    ///
+    /// The base path must begin with a forward slash, `/`, and it can't end with one—the same
+    /// rules that apply to [`Blueprint::nest_at`] prefixes.
+    ///
+    /// The base path applies to the application as a whole: it is ignored if set
+    /// on a blueprint that gets nested under another one.
+    /// If it is set multiple times on the same blueprint, the last value wins.
+    pub fn base_path(&mut self, path: &str) {
+        self.base_path = Some(RegisteredBasePath {
+            path: path.into(),
+            location: std::panic::Location::caller().into(),
+        });
+    }
+
     #[track_caller]
     /// Register a fallback handler to be invoked when an incoming request does **not** match
     /// any of the routes you registered with [`Blueprint::route`].  
@@ -643,6 +967,262 @@ impl Blueprint {
         self.fallback_request_handler = Some(registered);
         Fallback { blueprint: self }
     }
+
+    #[track_caller]
+    /// Register a handler to be invoked when the path of an incoming request matches
+    /// one of your routes, but its method doesn't—the `405 Method Not Allowed` scenario.
+    ///
+    /// The handler centralizes how `405` responses are rendered for all the routes
+    /// registered against this `Blueprint` (and the blueprints nested under it).
+    /// It has no impact on requests that don't match any route path: those are still
+    /// processed by the [fallback handler](Blueprint::fallback).
+    ///
+    /// If a `405 Method Not Allowed` handler has already been registered against this
+    /// `Blueprint`, it will be overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::{GET, POST}}};
+    /// use pavex::response::Response;
+    /// use pavex::router::AllowedMethods;
+    ///
+    /// fn list_users() -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    /// fn create_user() -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// fn method_not_allowed(allowed_methods: &AllowedMethods) -> Response {
+    ///     let allowed = allowed_methods
+    ///         .allow_header_value()
+    ///         .and_then(|v| v.to_str().ok().map(ToOwned::to_owned))
+    ///         .unwrap_or_default();
+    ///     Response::method_not_allowed()
+    ///         .set_typed_body(format!("Use one of the following methods: {allowed}"))
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route(GET, "/users", f!(crate::list_users));
+    /// bp.route(POST, "/users", f!(crate::create_user));
+    /// // Invoked for `PUT /users`, `DELETE /users`, etc.
+    /// bp.method_not_allowed(f!(crate::method_not_allowed));
+    /// # }
+    /// ```
+    ///
+    /// # Signature
+    ///
+    /// A `405 Method Not Allowed` handler is a function (or a method) that returns a [`Response`],
+    /// either directly (if infallible) or wrapped in a [`Result`] (if fallible).  
+    /// It can take advantage of dependency injection, like any other component.
+    /// In particular, it can take [`AllowedMethods`] as input to determine which methods
+    /// are accepted by the matched route.
+    ///
+    /// # The `Allow` header
+    ///
+    /// A `405 Method Not Allowed` response must include an `Allow` header listing the methods
+    /// supported by the target resource.  
+    /// Pavex takes care of it for you: if the response returned by your handler doesn't include
+    /// an `Allow` header, Pavex will set it to the list of methods allowed for the matched route.
+    /// If your handler sets an `Allow` header, Pavex will leave it untouched—your handler
+    /// always has the final say.
+    ///
+    /// # Nesting
+    ///
+    /// The `405 Method Not Allowed` handler registered against a blueprint applies to all
+    /// the routes registered against that blueprint, as well as to the routes registered
+    /// against the blueprints nested under it—unless they register a handler of their own.  
+    /// When it comes to method mismatches, a `405 Method Not Allowed` handler takes precedence
+    /// over the [fallbacks](Blueprint::fallback) in scope for the same routes.
+    /// If no `405 Method Not Allowed` handler is in scope for a route, its fallback is used
+    /// instead.
+    ///
+    /// [`Response`]: crate::response::Response
+    /// [`AllowedMethods`]: crate::router::AllowedMethods
+    pub fn method_not_allowed(&mut self, callable: RawCallable) -> MethodNotAllowed {
+        let registered = RegisteredFallback {
+            request_handler: RegisteredCallable {
+                callable: RawCallableIdentifiers::from_raw_callable(callable),
+                location: std::panic::Location::caller().into(),
+            },
+            error_handler: None,
+        };
+        self.method_not_allowed_handler = Some(registered);
+        MethodNotAllowed { blueprint: self }
+    }
+
+    #[track_caller]
+    /// Register a hook to post-process **every** outgoing response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::Blueprint};
+    /// use pavex::http::{HeaderName, HeaderValue};
+    /// use pavex::request::RequestHead;
+    /// use pavex::response::Response;
+    ///
+    /// pub fn security_headers(response: Response, request_head: &RequestHead) -> Response {
+    ///     let response = response
+    ///         .insert_header(
+    ///             HeaderName::from_static("x-content-type-options"),
+    ///             HeaderValue::from_static("nosniff"),
+    ///         )
+    ///         // Browsers ignore this header on responses sent over plain HTTP.
+    ///         .insert_header(
+    ///             HeaderName::from_static("strict-transport-security"),
+    ///             HeaderValue::from_static("max-age=63072000"),
+    ///         );
+    ///     if request_head.target.path().starts_with("/api/") {
+    ///         response.insert_header(
+    ///             HeaderName::from_static("cache-control"),
+    ///             HeaderValue::from_static("no-store"),
+    ///         )
+    ///     } else {
+    ///         response
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.response_hook(f!(crate::security_headers));
+    /// # }
+    /// ```
+    ///
+    /// # Signature
+    ///
+    /// A response hook is a function (or a method) that takes a [`Response`] as input and
+    /// returns a [`Response`].  
+    /// It can optionally take a `&`[`RequestHead`] as second input parameter, if it needs to
+    /// look at the incoming request. It can be either sync or async.  
+    /// If it does, none of your components can take [`RequestHead`] by value:
+    /// Pavex rejects the blueprint otherwise, since the request head must still be
+    /// around when the hook is invoked.
+    ///
+    /// Response hooks are **not** subject to dependency injection: those are the only
+    /// input parameters you can ask for.
+    /// If you need access to other components, use a [wrapping middleware](Blueprint::wrap)
+    /// instead.
+    ///
+    /// # Execution order
+    ///
+    /// The response hook is the last piece of logic to be executed before a response is sent
+    /// back to the caller.  
+    /// It is invoked **after** all middlewares, request handlers, error handlers and fallbacks.
+    /// It is therefore applied to every single response returned by your application, no matter
+    /// where it comes from: a successful response from a request handler, an error response
+    /// produced by an error handler, a `404 Not Found` from a fallback, etc.
+    ///
+    /// If a request is re-dispatched using a [`Dispatcher`], the response hook is applied
+    /// once to the response returned to the caller, not to the responses of the
+    /// internal requests.
+    ///
+    /// # Registration
+    ///
+    /// A response hook can only be registered against the top-level blueprint—i.e. the one
+    /// you pass to Pavex to generate your server SDK.
+    /// Pavex will return an error if you try to register a response hook against a nested
+    /// blueprint.
+    /// Registering a second response hook against the same blueprint will override the first one.
+    ///
+    /// [`Response`]: crate::response::Response
+    /// [`RequestHead`]: crate::request::RequestHead
+    /// [`Dispatcher`]: crate::router::Dispatcher
+    pub fn response_hook(&mut self, callable: RawCallable) {
+        self.response_hook = Some(RegisteredCallable {
+            callable: RawCallableIdentifiers::from_raw_callable(callable),
+            location: std::panic::Location::caller().into(),
+        });
+    }
+
+    #[track_caller]
+    /// Register a type-wide error handler.
+    ///
+    /// It is used for **all** the fallible components (constructors, request handlers,
+    /// middlewares, fallbacks) visible to this blueprint whose error type matches the
+    /// error type the handler takes as input, unless they have been given their own
+    /// error handler via `.error_handler`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    /// use pavex::response::Response;
+    ///
+    /// # pub struct Report;
+    /// # pub struct PublicReport;
+    /// #[derive(Debug)]
+    /// pub struct DatabaseError;
+    ///
+    /// pub fn report() -> Result<Report, DatabaseError> {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// pub fn public_report() -> Result<PublicReport, DatabaseError> {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// pub fn database_error(e: &DatabaseError) -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// pub fn redacted_database_error(e: &DatabaseError) -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// // Every fallible component that returns a `DatabaseError`...
+    /// bp.error_handler(f!(crate::database_error));
+    /// bp.route(GET, "/report", f!(crate::report));
+    ///
+    /// let mut public = Blueprint::new();
+    /// // ...unless it was registered against this nested blueprint (or one
+    /// // of its descendants), which overrides the handler for its own routes.
+    /// public.error_handler(f!(crate::redacted_database_error));
+    /// public.route(GET, "/report", f!(crate::public_report));
+    /// bp.nest_at("/public", public);
+    /// # }
+    /// ```
+    ///
+    /// # Resolution
+    ///
+    /// For each fallible component without an error handler of its own, Pavex looks for a
+    /// type-wide error handler that takes a reference to its error type as input:
+    ///
+    /// - It starts from the blueprint the component was registered against.
+    /// - If there is no match, it moves to the parent blueprint, all the way up to the
+    ///   root.
+    ///
+    /// The first match wins: registering a type-wide error handler against a nested blueprint
+    /// overrides, for the components in that blueprint, the ones registered against its
+    /// parents.
+    /// The error type must match exactly. A handler for `E` won't be used for `Box<E>` or for
+    /// an error type that wraps `E`.
+    /// Pavex will return an error if more than one type-wide error handler registered against
+    /// the same blueprint matches a component.
+    ///
+    /// Type-wide error handlers are never invoked for singleton constructors: those are
+    /// executed when the application state is built, not when a request is processed.
+    ///
+    /// # Introspection
+    ///
+    /// Pass `--error-handler-report <path>` to `pavex generate` to get a summary of the
+    /// error handler that is invoked for each fallible component, for every route.
+    pub fn error_handler(&mut self, callable: RawCallable) {
+        self.error_handlers.push(RegisteredCallable {
+            callable: RawCallableIdentifiers::from_raw_callable(callable),
+            location: std::panic::Location::caller().into(),
+        });
+    }
 }
 
 /// Methods to serialize and deserialize a [`Blueprint`].  
@@ -667,3 +1247,26 @@ impl Blueprint {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::blueprint::router::GET;
+    use crate::f;
+
+    use super::Blueprint;
+
+    #[test]
+    fn route_if_is_a_no_op_if_the_condition_is_false() {
+        let mut bp = Blueprint::new();
+        assert!(bp
+            .route_if(false, GET, "/debug", f!(crate::debug_handler))
+            .is_none());
+        assert!(bp.routes.is_empty());
+
+        assert!(bp
+            .route_if(true, GET, "/debug", f!(crate::debug_handler))
+            .is_some());
+        assert_eq!(bp.routes.len(), 1);
+        assert_eq!(bp.routes[0].path, "/debug");
+    }
+}
@@ -6,7 +6,22 @@ pub enum Lifecycle {
     /// The constructor for a `Singleton` type is invoked at most once.
     ///
     /// As a consequence, there is at most one instance of `Singleton` types,
-    /// stored inside the server's global state.  
+    /// stored inside the server's global state.
+    ///
+    /// # Sharing singletons via `Arc`
+    ///
+    /// If a component asks for `Arc<T>` (or `&Arc<T>`), there is no registered constructor
+    /// for `Arc<T>` and `T` is a singleton, Pavex will wrap the singleton instance of `T`
+    /// in an `Arc` for you—you don't need to register a constructor for `Arc<T>`.
+    ///
+    /// The bridging only goes one way: Pavex will never unwrap an `Arc<T>` singleton
+    /// to provide a `T`.
+    /// It doesn't apply to request-scoped or transient types either.
+    ///
+    /// `Arc::new` takes ownership of `T`. If other components ask for `T` (or `&T`) as well,
+    /// both `T` and `Arc<T>` must be stored in the application state: Pavex must be allowed
+    /// to clone `T` (see [`CloningStrategy`](super::CloningStrategy)), otherwise it
+    /// reports an error.
     Singleton,
     /// The constructor for a `RequestScoped` type is invoked at most once for every incoming request.
     ///
@@ -61,6 +61,47 @@ impl<'a> Constructor<'a> {
     /// If an error handler has already been registered for the same error type, it will be
     /// overwritten.
     ///
+    /// ## Short-circuiting with a response
+    ///
+    /// Sometimes a failure is a control-flow decision rather than an error—e.g. redirecting
+    /// unauthenticated users to a login page.
+    /// Request-scoped and transient constructors can return `Result<T, Response>`: you don't
+    /// need to register an error handler for them.
+    /// If the constructor returns `Err(response)`, the request processing pipeline is
+    /// short-circuited and `response` is returned to the caller as it is.
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, constructor::Lifecycle};
+    /// use pavex::http::{header::LOCATION, HeaderValue};
+    /// use pavex::request::RequestHead;
+    /// use pavex::response::Response;
+    /// # struct Session;
+    /// # fn session_from_cookie(_head: &RequestHead) -> Option<Session> { todo!() }
+    ///
+    /// pub fn session(request_head: &RequestHead) -> Result<Session, Response> {
+    ///     session_from_cookie(request_head).ok_or_else(|| {
+    ///         Response::see_other().insert_header(LOCATION, HeaderValue::from_static("/login"))
+    ///     })
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.constructor(f!(crate::session), Lifecycle::RequestScoped);
+    /// # }
+    /// ```
+    ///
+    /// Everything else works as it would for an error response produced by an error handler:
+    /// the response goes through the wrapping middlewares that have already been
+    /// invoked and constructor failures are [logged](Blueprint::log_constructor_failures),
+    /// if enabled.
+    /// If you do register an error handler for a constructor that returns `Result<T, Response>`,
+    /// the error handler takes precedence.
+    ///
+    /// Singleton constructors can't short-circuit: they are invoked when the application
+    /// state is built, there is no request to respond to.
+    /// Pavex rejects singleton constructors that fail with a `Response`.
+    ///
     /// ## Common Errors
     ///
     /// Pavex will fail to generate the runtime code for your application if you register
@@ -87,6 +128,65 @@ impl<'a> Constructor<'a> {
         self.blueprint.constructors[self.constructor_id].cloning_strategy = Some(strategy);
         self
     }
+
+    /// Mark the output type of this singleton constructor as optional.
+    ///
+    /// By default, if a singleton constructor fails, the application state can't be built
+    /// and the error is returned to the caller of `build_application_state`—i.e. your
+    /// application doesn't start.
+    /// That's the right behaviour for **required** dependencies (e.g. a database connection pool).
+    ///
+    /// Some dependencies, though, are nice-to-have (e.g. a cache): your application can still
+    /// serve requests, with degraded functionality, if they are unavailable.
+    /// If you mark a fallible singleton constructor as optional, Pavex stores an
+    /// `Option<T>` in the application state:
+    ///
+    /// - `Some(T)` if the constructor succeeded;
+    /// - `None` if the constructor failed. The application starts anyway: the error is
+    ///   discarded, after logging a warning (see [`log_optional_singleton_failure`]).
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, constructor::Lifecycle};
+    /// use pavex::response::Response;
+    /// # struct Cache;
+    /// # struct CacheError;
+    ///
+    /// fn cache() -> Result<Cache, CacheError> {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// // Components that can cope with a missing cache ask for `&Option<Cache>`.
+    /// fn handler(cache: &Option<Cache>) -> Response {
+    ///     match cache.as_ref() {
+    ///         Some(cache) => { /* [...] */ }
+    ///         None => { /* Fallback logic */ }
+    ///     }
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.constructor(f!(crate::cache), Lifecycle::Singleton)
+    ///     .optional();
+    /// # }
+    /// ```
+    ///
+    /// No constructor is registered for `T` itself: Pavex will reject, at compile-time,
+    /// any component that asks for `T` (or `&T`) without going through `Option<T>`.
+    ///
+    /// ## Common Errors
+    ///
+    /// Pavex will fail to generate the runtime code for your application if you mark
+    /// as optional a constructor that either isn't a singleton or doesn't return a `Result`.
+    ///
+    /// [`log_optional_singleton_failure`]: crate::telemetry::log_optional_singleton_failure
+    pub fn optional(self) -> Self {
+        self.blueprint.constructors[self.constructor_id].optional = true;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
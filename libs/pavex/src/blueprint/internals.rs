@@ -18,6 +18,26 @@ pub struct RegisteredRoute {
     pub request_handler: RegisteredCallable,
     /// The callable in charge of processing errors returned by the request handler, if any.
     pub error_handler: Option<RegisteredCallable>,
+    /// The matching priority of the route.
+    ///
+    /// Check out [`Route::priority`](super::router::Route::priority) for more details.
+    #[serde(default)]
+    pub priority: i32,
+    /// The value of the `Cache-Control` header to set on the responses returned by this route,
+    /// if any.
+    ///
+    /// Check out [`Route::cache_control`](super::router::Route::cache_control) for more details.
+    #[serde(default)]
+    pub cache_control: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+/// The base path set for a [`Blueprint`] via [`Blueprint::base_path`].
+pub struct RegisteredBasePath {
+    /// The base path.
+    pub path: String,
+    /// The location where the base path was set.
+    pub location: Location,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -41,6 +61,10 @@ pub struct RegisteredConstructor {
     pub cloning_strategy: Option<CloningStrategy>,
     /// The callable in charge of processing errors returned by this constructor, if any.
     pub error_handler: Option<RegisteredCallable>,
+    /// If `true`, a failure of this (singleton) constructor doesn't prevent the application
+    /// state from being built.
+    #[serde(default)]
+    pub optional: bool,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -50,6 +74,13 @@ pub struct RegisteredWrappingMiddleware {
     pub middleware: RegisteredCallable,
     /// The callable in charge of processing errors returned by this middleware, if any.
     pub error_handler: Option<RegisteredCallable>,
+    /// The authenticator that this middleware enforces, if it was registered
+    /// via [`Blueprint::require_auth`].
+    ///
+    /// The authenticator is also registered as a request-scoped constructor against
+    /// the same [`Blueprint`].
+    #[serde(default)]
+    pub authenticator: Option<RawCallableIdentifiers>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
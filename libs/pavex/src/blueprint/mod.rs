@@ -11,3 +11,4 @@ pub mod internals;
 pub mod middleware;
 pub mod reflection;
 pub mod router;
+pub mod validation;
@@ -1,5 +1,6 @@
 use std::collections::BTreeSet;
 
+use http::method::InvalidMethod;
 use http::Method;
 
 use crate::blueprint::router::method_guard::inner::method_to_bitset;
@@ -13,6 +14,8 @@ use crate::router::{AllowedMethods, MethodAllowList};
 /// [`MethodGuard::from_iter`].  
 /// If you want to match all well-known HTTP methods, use [`ANY`].  
 /// If you want to match **any** HTTP method, including custom ones, use [`ANY_WITH_EXTENSIONS`].  
+/// If you want to match a custom HTTP method (e.g. WebDAV's `PROPFIND`), use
+/// [`MethodGuard::from_bytes`].
 ///
 /// [`Blueprint::route`]: crate::blueprint::Blueprint::route
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -56,6 +59,33 @@ impl MethodGuard {
         }
     }
 
+    /// Build a new [`MethodGuard`] that matches incoming requests using the given HTTP method,
+    /// even if it's not one of the well-known ones.
+    ///
+    /// ```rust
+    /// use pavex::blueprint::router::{MethodGuard, GET};
+    ///
+    /// // WebDAV's `PROPFIND` method.
+    /// let guard = MethodGuard::from_bytes(b"PROPFIND").unwrap();
+    /// // It can be combined with other guards, as usual.
+    /// let guard = guard.or(GET);
+    /// ```
+    ///
+    /// # Validation
+    ///
+    /// The method must be a valid HTTP token (see [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-9.1)):
+    /// a non-empty sequence of visible ASCII characters, excluding delimiters such as
+    /// whitespace, `(`, `)`, `,`, `/`, `:`, `;`, `<`, `=`, `>`, `?`, `@`, `[`, `\`, `]`, `{`, `}`
+    /// and `"`.
+    /// An error is returned otherwise.
+    ///
+    /// Methods are case-sensitive: `b"propfind"` and `b"PROPFIND"` are two different methods.
+    /// If you pass the name of a well-known method (e.g. `b"GET"`), you'll get the same guard
+    /// you'd get using the corresponding constant (e.g. [`GET`]).
+    pub fn from_bytes(method: &[u8]) -> Result<Self, InvalidMethod> {
+        Method::from_bytes(method).map(Self::from)
+    }
+
     /// Combine this [`MethodGuard`] with another one, returning a new [`MethodGuard`].
     ///
     /// The returned [`MethodGuard`] will match requests that match either of the two
@@ -0,0 +1,69 @@
+use crate::blueprint::internals::RegisteredCallable;
+use crate::blueprint::{
+    reflection::{RawCallable, RawCallableIdentifiers},
+    Blueprint,
+};
+
+/// The type returned by [`Blueprint::method_not_allowed`].
+///
+/// It allows you to further configure the behaviour of the registered handler.
+pub struct MethodNotAllowed<'a> {
+    pub(crate) blueprint: &'a mut Blueprint,
+}
+
+impl<'a> MethodNotAllowed<'a> {
+    #[track_caller]
+    /// Register an error handler.
+    ///
+    /// Error handlers convert the error type returned by your request handler into an HTTP response.
+    ///
+    /// Error handlers CANNOT consume the error type, they must take a reference to the
+    /// error as input.  
+    /// Error handlers can have additional input parameters alongside the error, as long as there
+    /// are constructors registered for those parameter types.
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::Blueprint;
+    /// use pavex::response::Response;
+    /// use pavex::router::AllowedMethods;
+    /// # struct LogLevel;
+    /// # struct TemplateError;
+    ///
+    /// fn method_not_allowed(allowed_methods: &AllowedMethods) -> Result<Response, TemplateError> {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// fn error_to_response(error: &TemplateError, log_level: LogLevel) -> Response {
+    ///     // [...]
+    ///     # todo!()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.method_not_allowed(f!(crate::method_not_allowed))
+    ///     .error_handler(f!(crate::error_to_response));
+    /// # }
+    /// ```
+    ///
+    /// If an error handler has already been registered for the same error type, it will be
+    /// overwritten.
+    ///
+    /// ## Common Errors
+    ///
+    /// Pavex will fail to generate the runtime code for your application if you register
+    /// an error handler for an infallible request handler (i.e. a request handler that doesn't
+    /// return a `Result`).
+    pub fn error_handler(self, error_handler: RawCallable) -> Self {
+        let callable_identifiers = RawCallableIdentifiers::from_raw_callable(error_handler);
+        let callable = RegisteredCallable {
+            callable: callable_identifiers,
+            location: std::panic::Location::caller().into(),
+        };
+        if let Some(handler) = &mut self.blueprint.method_not_allowed_handler {
+            handler.error_handler = Some(callable);
+        }
+        self
+    }
+}
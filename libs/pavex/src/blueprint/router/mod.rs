@@ -9,8 +9,10 @@ pub use method_guard::{
     MethodGuard, ANY, ANY_WITH_EXTENSIONS, CONNECT, DELETE, GET, HEAD, OPTIONS, PATCH, POST, PUT,
     TRACE,
 };
+pub use method_not_allowed::MethodNotAllowed;
 pub use route::Route;
 
 mod fallback;
 mod method_guard;
+mod method_not_allowed;
 mod route;
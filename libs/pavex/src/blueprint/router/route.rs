@@ -3,6 +3,7 @@ use crate::blueprint::{
     reflection::{RawCallable, RawCallableIdentifiers},
     Blueprint,
 };
+use crate::response::CacheControl;
 
 /// The type returned by [`Blueprint::route`].
 ///
@@ -66,4 +67,100 @@ impl<'a> Route<'a> {
         self.blueprint.routes[self.route_id].error_handler = Some(callable);
         self
     }
+
+    /// Override the matching priority of this route.
+    ///
+    /// By default, all routes have a priority of `0`.
+    ///
+    /// # Default precedence
+    ///
+    /// An incoming request may match the paths of multiple routes—e.g. `/users/me` matches
+    /// both `/users/me` and `/users/:id`.
+    /// When priorities are left untouched, the most specific route wins:
+    /// static segments take precedence over path parameters, which in turn take precedence
+    /// over catch-all parameters. `/users/me` would therefore be routed to the
+    /// `/users/me` handler.
+    ///
+    /// # Overriding the default
+    ///
+    /// When priorities are set, routes are matched in order of **descending** priority:
+    ///
+    /// - the incoming request is first matched against the routes with the highest priority,
+    ///   using the default precedence rules among them;
+    /// - if none of them match the request path, Pavex moves on to the routes with the next
+    ///   highest priority, and so on.
+    ///
+    /// ```rust
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, router::GET};
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route(GET, "/users/me", f!(crate::get_current_user));
+    /// // `GET /users/me` will be handled by `get_user`, even though `/users/me`
+    /// // is a more specific match.
+    /// bp.route(GET, "/users/:id", f!(crate::get_user)).priority(1);
+    /// # }
+    /// ```
+    ///
+    /// Routes with different priorities are never considered to be in conflict, even if their
+    /// paths would conflict if they were registered with the same priority
+    /// (e.g. `/users/:id` and `/users/:user_id`).
+    ///
+    /// # Limitations
+    ///
+    /// - Priorities are taken into account when matching the **path** of the incoming request.
+    ///   If the highest-priority route that matches the path doesn't accept the
+    ///   method of the incoming request, the request is handled by the fallback for
+    ///   that route—Pavex won't try routes with a lower priority.
+    /// - All the routes registered for the same path must have the same priority.
+    /// - The catch-all routes that Pavex registers on your behalf to dispatch requests
+    ///   to the fallbacks of nested blueprints (see [`Blueprint::nest_at`]) have the
+    ///   default priority, `0`.
+    pub fn priority(self, priority: i32) -> Self {
+        self.blueprint.routes[self.route_id].priority = priority;
+        self
+    }
+
+    /// Set caching directives for the responses returned by this route.
+    ///
+    /// Pavex will set the `Cache-Control` header on every response returned by this route,
+    /// including error responses.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use pavex::f;
+    /// use pavex::blueprint::{Blueprint, router::GET};
+    /// use pavex::response::CacheControl;
+    ///
+    /// # fn main() {
+    /// let mut bp = Blueprint::new();
+    /// bp.route(GET, "/logo.svg", f!(crate::logo))
+    ///     .cache_control(CacheControl::public().max_age(Duration::from_secs(3600)));
+    /// bp.route(GET, "/account", f!(crate::account))
+    ///     .cache_control(CacheControl::no_store());
+    /// # }
+    /// ```
+    ///
+    /// # Precedence
+    ///
+    /// The header is set **after** the request handler and all the middlewares wrapping it
+    /// have been executed.
+    /// If the response already includes a `Cache-Control` header, Pavex leaves it untouched:
+    /// your request handler (or one of your middlewares) always has the final say.
+    ///
+    /// If you call this method multiple times, the last set of directives wins.
+    ///
+    /// # Limitations
+    ///
+    /// The directives only apply to the responses returned by this route.
+    /// They don't apply to the responses returned by the fallback handler, e.g. when the
+    /// incoming request matches the path of the route but not its method.
+    ///
+    /// Pavex doesn't set the `Expires` header—check out
+    /// [`CacheControl`'s documentation](CacheControl#expires) for the rationale.
+    pub fn cache_control(self, cache_control: CacheControl) -> Self {
+        self.blueprint.routes[self.route_id].cache_control = Some(cache_control.to_string());
+        self
+    }
 }
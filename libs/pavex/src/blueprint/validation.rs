@@ -0,0 +1,384 @@
+//! Structural checks that can be performed on a [`Blueprint`] without
+//! invoking Pavex's CLI.
+//!
+//! Check out [`Blueprint::validate`] for more details.
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+
+use indexmap::IndexMap;
+use matchit::InsertError;
+
+use super::internals::RegisteredRoute;
+use super::reflection::Location;
+use super::Blueprint;
+use crate::router::AllowedMethods;
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+/// An issue detected by [`Blueprint::validate`].
+pub struct BlueprintDiagnostic {
+    /// A description of the issue, with suggestions on how to fix it (if possible).
+    pub message: String,
+    /// Where the problematic component was registered against the [`Blueprint`].
+    pub location: Location,
+}
+
+impl std::fmt::Display for BlueprintDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Location { line, column, file } = &self.location;
+        write!(f, "{}\n  --> {file}:{line}:{column}", self.message)
+    }
+}
+
+impl std::error::Error for BlueprintDiagnostic {}
+
+impl Blueprint {
+    /// Check the structure of the [`Blueprint`] for errors, without generating any code.
+    ///
+    /// It's meant to give you fast feedback, e.g. in a unit test or a build script: you
+    /// don't have to wait for Pavex's CLI to find out that a route path is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::{f, blueprint::{Blueprint, router::GET}};
+    ///
+    /// let mut bp = Blueprint::new();
+    /// // The route path is missing its leading `/`.
+    /// bp.route(GET, "home", f!(crate::home));
+    ///
+    /// let diagnostics = bp.validate().unwrap_err();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// ```
+    ///
+    /// # Checks
+    ///
+    /// The following checks are performed:
+    ///
+    /// - route paths must either be empty or begin with a forward slash, `/`;
+    /// - the path prefixes passed to [`Blueprint::nest_at`] and [`Blueprint::base_path`] must
+    ///   not be empty, must begin with a forward slash, `/`, and must not end with a
+    ///   trailing slash;
+    /// - route paths must be well-formed (e.g. path parameters must be named,
+    ///   catch-all parameters must be at the end of the path);
+    /// - different route paths must not conflict with each other;
+    /// - there can't be more than one request handler for the same combination of path and
+    ///   HTTP method;
+    /// - all the routes registered for the same path must have the same
+    ///   [priority](crate::blueprint::router::Route::priority).
+    ///
+    /// # Limitations
+    ///
+    /// `validate` only looks at the data stored in the [`Blueprint`] itself.
+    /// All checks that require knowledge of the types involved (e.g. are all the dependencies
+    /// of a request handler constructible? Is there an error handler for every fallible
+    /// constructor?) can only be performed by Pavex's CLI, when generating the code for your
+    /// application.
+    /// A blueprint that passes validation is **not** guaranteed to compile successfully.
+    pub fn validate(&self) -> Result<(), Vec<BlueprintDiagnostic>> {
+        let mut diagnostics = Vec::new();
+        let mut routes = Vec::new();
+        let base_path = match &self.base_path {
+            Some(base_path) => match invalid_prefix_message(&base_path.path, "base_path") {
+                Some(message) => {
+                    diagnostics.push(BlueprintDiagnostic {
+                        message,
+                        location: base_path.location.clone(),
+                    });
+                    ""
+                }
+                None => base_path.path.as_str(),
+            },
+            None => "",
+        };
+        collect_routes(self, base_path, &mut routes, &mut diagnostics);
+        validate_route_paths(&routes, &mut diagnostics);
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+}
+
+/// Collect all the routes registered against `bp` and its nested blueprints,
+/// alongside their full path.
+/// Nesting prefixes are validated along the way.
+fn collect_routes<'a>(
+    bp: &'a Blueprint,
+    path_prefix: &str,
+    routes: &mut Vec<(String, &'a RegisteredRoute)>,
+    diagnostics: &mut Vec<BlueprintDiagnostic>,
+) {
+    for route in &bp.routes {
+        let path = &route.path;
+        if !path.is_empty() && !path.starts_with('/') {
+            diagnostics.push(BlueprintDiagnostic {
+                message: format!(
+                    "Route paths must either be empty or begin with a forward slash, `/`.\n\
+                    `{path}` is not empty and it doesn't begin with a `/`."
+                ),
+                location: route.request_handler.location.clone(),
+            });
+            continue;
+        }
+        routes.push((format!("{path_prefix}{path}"), route));
+    }
+    for nested in &bp.nested_blueprints {
+        let nested_prefix = match &nested.path_prefix {
+            Some(prefix) => {
+                if let Some(message) = invalid_prefix_message(prefix, "nest_at") {
+                    diagnostics.push(BlueprintDiagnostic {
+                        message,
+                        location: nested.nesting_location.clone(),
+                    });
+                    continue;
+                }
+                format!("{path_prefix}{prefix}")
+            }
+            None => path_prefix.to_owned(),
+        };
+        collect_routes(&nested.blueprint, &nested_prefix, routes, diagnostics);
+    }
+}
+
+/// Check that a path prefix (passed to `method`) is not empty, begins with a forward slash
+/// and doesn't end with one.
+/// It returns an error message if it isn't.
+fn invalid_prefix_message(prefix: &str, method: &str) -> Option<String> {
+    if prefix.is_empty() {
+        Some(format!(
+            "The path prefix passed to `{method}` cannot be empty."
+        ))
+    } else if !prefix.starts_with('/') {
+        Some(format!(
+            "The path prefix passed to `{method}` must begin with a forward slash, `/`.\n\
+            `{prefix}` doesn't."
+        ))
+    } else if prefix.ends_with('/') {
+        Some(format!(
+            "The path prefix passed to `{method}` can't end with a trailing slash, `/`. \
+            `{prefix}` does."
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check that route paths are well-formed, that they don't conflict with each other and
+/// that there is at most one request handler for each combination of path and HTTP method.
+fn validate_route_paths(
+    routes: &[(String, &RegisteredRoute)],
+    diagnostics: &mut Vec<BlueprintDiagnostic>,
+) {
+    // Routes with different priorities are matched using different routers,
+    // therefore they can't conflict with each other.
+    let mut priority2router = BTreeMap::new();
+    let mut path2routes: IndexMap<&str, Vec<&RegisteredRoute>> = IndexMap::new();
+    for (path, route) in routes {
+        let router = priority2router
+            .entry(route.priority)
+            .or_insert_with(matchit::Router::new);
+        if let Err(e) = router.insert(path.clone(), ()) {
+            let message = match e {
+                // The same path can be registered multiple times, with different method guards.
+                InsertError::Conflict { with } if &with == path => None,
+                InsertError::Conflict { with } => Some(format!(
+                    "This route path, `{path}`, conflicts with the path of another route you \
+                    already registered, `{with}`."
+                )),
+                InsertError::TooManyParams => Some(
+                    "You can only register one path parameter per each path segment.".to_string(),
+                ),
+                InsertError::UnnamedParam => Some(
+                    "All path parameters must be named. \
+                    You can't use anonymous parameters like `:` or `*`."
+                        .to_string(),
+                ),
+                InsertError::InvalidCatchAll => Some(
+                    "You can only use catch-all parameters at the end of a route path.".to_string(),
+                ),
+                e => Some(format!("`{path}` is not a valid route path: {e}")),
+            };
+            if let Some(message) = message {
+                diagnostics.push(BlueprintDiagnostic {
+                    message,
+                    location: route.request_handler.location.clone(),
+                });
+                continue;
+            }
+        }
+        path2routes.entry(path.as_str()).or_default().push(route);
+    }
+
+    for (path, routes) in path2routes {
+        for (i, route) in routes.iter().enumerate() {
+            if let Some(previous) = routes[..i]
+                .iter()
+                .find(|previous| previous.priority != route.priority)
+            {
+                let Location { line, column, file } = &previous.request_handler.location;
+                diagnostics.push(BlueprintDiagnostic {
+                    message: format!(
+                        "All the routes registered for `{path}` must have the same priority. \
+                        This route has priority {}, while the one registered at \
+                        {file}:{line}:{column} has priority {}.",
+                        route.priority, previous.priority
+                    ),
+                    location: route.request_handler.location.clone(),
+                });
+                continue;
+            }
+            let overlapping = routes[..i].iter().find(|previous| {
+                overlap(
+                    &previous.method_guard.allowed_methods(),
+                    &route.method_guard.allowed_methods(),
+                )
+            });
+            if let Some(previous) = overlapping {
+                let Location { line, column, file } = &previous.request_handler.location;
+                diagnostics.push(BlueprintDiagnostic {
+                    message: format!(
+                        "There are multiple request handlers for the same combination of path \
+                        and HTTP method, `{path}`. \
+                        The other one was registered at {file}:{line}:{column}."
+                    ),
+                    location: route.request_handler.location.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Returns `true` if there is at least one HTTP method that's allowed by both `a` and `b`.
+fn overlap(a: &AllowedMethods, b: &AllowedMethods) -> bool {
+    match (a, b) {
+        (AllowedMethods::Some(a), AllowedMethods::Some(b)) => {
+            a.iter().any(|method| b.iter().any(|other| other == method))
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blueprint::router::{ANY, GET, POST};
+    use crate::blueprint::Blueprint;
+    use crate::f;
+
+    fn messages(bp: &Blueprint) -> Vec<String> {
+        bp.validate()
+            .unwrap_err()
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn a_well_formed_blueprint_passes_validation() {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/home", f!(crate::home));
+        bp.route(POST, "/home", f!(crate::home));
+        bp.nest_at("/users", {
+            let mut bp = Blueprint::new();
+            bp.route(GET, "/:id", f!(crate::user));
+            bp
+        });
+        assert!(bp.validate().is_ok());
+    }
+
+    #[test]
+    fn malformed_route_templates_are_caught() {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "home", f!(crate::home));
+        bp.route(GET, "/users/:id:name", f!(crate::user));
+        bp.route(GET, "/files/*path/details", f!(crate::file));
+        insta::assert_debug_snapshot!(messages(&bp), @r###"
+        [
+            "Route paths must either be empty or begin with a forward slash, `/`.\n`home` is not empty and it doesn't begin with a `/`.",
+            "You can only register one path parameter per each path segment.",
+            "You can only use catch-all parameters at the end of a route path.",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn malformed_nesting_prefixes_are_caught() {
+        let mut bp = Blueprint::new();
+        bp.nest_at("", Blueprint::new());
+        bp.nest_at("api", Blueprint::new());
+        bp.nest_at("/api/", Blueprint::new());
+        insta::assert_debug_snapshot!(messages(&bp), @r###"
+        [
+            "The path prefix passed to `nest_at` cannot be empty.",
+            "The path prefix passed to `nest_at` must begin with a forward slash, `/`.\n`api` doesn't.",
+            "The path prefix passed to `nest_at` can't end with a trailing slash, `/`. `/api/` does.",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn base_path_stacks_with_nesting_prefixes() {
+        let mut bp = Blueprint::new();
+        bp.base_path("/service-a");
+        bp.nest_at("/api", {
+            let mut bp = Blueprint::new();
+            bp.route(GET, "/home", f!(crate::home));
+            bp.route(GET, "/home", f!(crate::home));
+            bp
+        });
+        let diagnostics = bp.validate().unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .starts_with("There are multiple request handlers for the same combination of path and HTTP method, `/service-a/api/home`."));
+    }
+
+    #[test]
+    fn malformed_base_path_is_caught() {
+        let mut bp = Blueprint::new();
+        bp.base_path("/service-a/");
+        insta::assert_debug_snapshot!(messages(&bp), @r###"
+        [
+            "The path prefix passed to `base_path` can't end with a trailing slash, `/`. `/service-a/` does.",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn conflicting_method_guards_are_caught() {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/home", f!(crate::home));
+        bp.nest({
+            let mut bp = Blueprint::new();
+            bp.route(ANY, "/home", f!(crate::home));
+            bp
+        });
+        let diagnostics = bp.validate().unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .starts_with("There are multiple request handlers for the same combination of path and HTTP method, `/home`."));
+    }
+
+    #[test]
+    fn routes_with_different_priorities_do_not_conflict() {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/users/:id", f!(crate::user));
+        bp.route(GET, "/users/:user_id", f!(crate::user))
+            .priority(1);
+        assert!(bp.validate().is_ok());
+    }
+
+    #[test]
+    fn mismatched_priorities_for_the_same_path_are_caught() {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/home", f!(crate::home)).priority(1);
+        bp.route(POST, "/home", f!(crate::home));
+        let diagnostics = bp.validate().unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .starts_with("All the routes registered for `/home` must have the same priority."));
+    }
+}
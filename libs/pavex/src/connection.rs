@@ -0,0 +1,75 @@
+//! Information about the connection an incoming request was received on.
+//!
+//! Check out [`LocalAddr`] for more details.
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The local address of the socket that accepted the connection an incoming
+/// request was received on.
+///
+/// It's the address your server is listening on, as seen by the operating system—e.g.
+/// `127.0.0.1:8080`.
+/// If your server is bound to multiple addresses (or to a wildcard address,
+/// like `0.0.0.0`), it tells you which one the client connected to.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::connection::LocalAddr;
+///
+/// pub fn handler(local_addr: LocalAddr) -> String {
+///     format!("You are talking to {}", local_addr.addr())
+/// }
+/// ```
+///
+/// # Framework primitive
+///
+/// `LocalAddr` is a framework primitive—you don't need to register any constructor
+/// with [`Blueprint`] to use it in your application.
+/// It's populated by Pavex's [`Server`] when a new connection is accepted.
+///
+/// # Lifecycle
+///
+/// `LocalAddr` is **per-connection**: all the requests received over the same
+/// connection (e.g. when using HTTP/1.1 keep-alive or HTTP/2) see the same value.
+/// From the point of view of the dependency injection system, it behaves like a
+/// request-scoped component: it is available to request handlers, middlewares,
+/// error handlers and constructors.
+/// Internal requests issued via a [`Dispatcher`] inherit the value of the request
+/// that spawned them.
+///
+/// [`Blueprint`]: crate::blueprint::Blueprint
+/// [`Server`]: crate::server::Server
+/// [`Dispatcher`]: crate::router::Dispatcher
+#[doc(alias("LocalAddress"))]
+#[doc(alias("SocketAddr"))]
+pub struct LocalAddr(SocketAddr);
+
+impl LocalAddr {
+    /// Wrap the local address of a connection.
+    ///
+    /// You don't need to call this in your application code: Pavex's [`Server`]
+    /// populates [`LocalAddr`] for you.
+    ///
+    /// [`Server`]: crate::server::Server
+    pub fn new(addr: SocketAddr) -> Self {
+        Self(addr)
+    }
+
+    /// The local address of the connection.
+    pub fn addr(&self) -> SocketAddr {
+        self.0
+    }
+}
+
+impl From<LocalAddr> for SocketAddr {
+    fn from(value: LocalAddr) -> Self {
+        value.0
+    }
+}
+
+impl std::fmt::Display for LocalAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
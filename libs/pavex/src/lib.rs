@@ -12,13 +12,20 @@
 // Re-export the dependencies that we use in the generated application code.
 pub use error::Error;
 
+pub mod blocking;
 pub mod blueprint;
+pub mod connection;
 mod error;
 pub mod http;
 pub mod middleware;
 pub mod request;
 pub mod response;
+pub mod rng;
 pub mod router;
 pub mod serialization;
 #[cfg(feature = "server")]
 pub mod server;
+#[cfg(feature = "session")]
+pub mod session;
+pub mod telemetry;
+pub mod transaction;
@@ -5,6 +5,10 @@
 //! [`Blueprint::wrap`]: crate::blueprint::Blueprint::wrap
 use std::future::IntoFuture;
 
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::http::StatusCode;
 use crate::response::Response;
 
 /// A handle to trigger the execution of the rest of the request processing pipeline.
@@ -43,3 +47,159 @@ where
         self.request_pipeline.into_future()
     }
 }
+
+/// The wrapping middleware registered by [`Blueprint::require_auth`].
+///
+/// It doesn't do anything on its own: asking for `&T` forces Pavex to invoke the
+/// authenticator for `T` before the rest of the request processing pipeline.
+/// If the authenticator fails, the pipeline is short-circuited and this middleware
+/// is never invoked.
+///
+/// You should not register it directly—use [`Blueprint::require_auth`] instead.
+///
+/// [`Blueprint::require_auth`]: crate::blueprint::Blueprint::require_auth
+#[doc(hidden)]
+pub async fn authenticated<C, T>(next: Next<C>, _identity: &T) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    next.into_future().await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The lowest status code that should be treated as an error response.
+///
+/// It lets you write wrapping middlewares that only act on error responses—e.g.
+/// to format all error bodies consistently or to attach diagnostic headers.
+///
+/// # Installation
+///
+/// Register the [default constructor](ErrorResponseThreshold::register), which
+/// treats all responses with a status code greater than or equal to `400` as errors,
+/// and use [`ErrorResponseThreshold::map`] in your own middleware:
+///
+/// ```rust
+/// use std::future::IntoFuture;
+/// use pavex::f;
+/// use pavex::blueprint::Blueprint;
+/// use pavex::http::{HeaderName, HeaderValue};
+/// use pavex::middleware::{ErrorResponseThreshold, Next};
+/// use pavex::response::Response;
+///
+/// pub async fn error_headers<C>(next: Next<C>, threshold: ErrorResponseThreshold) -> Response
+/// where
+///     C: IntoFuture<Output = Response>,
+/// {
+///     threshold
+///         .map(next, |response| {
+///             response.insert_header(
+///                 HeaderName::from_static("x-error-docs"),
+///                 HeaderValue::from_static("https://example.com/errors"),
+///             )
+///         })
+///         .await
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     ErrorResponseThreshold::register(&mut bp);
+///     bp.wrap(f!(crate::error_headers));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// If you want a different threshold (e.g. to only act on `5xx` responses), register
+/// your own constructor for [`ErrorResponseThreshold`] via [`ErrorResponseThreshold::new`].
+pub struct ErrorResponseThreshold {
+    min_status: StatusCode,
+}
+
+impl ErrorResponseThreshold {
+    /// Treat all responses with a status code greater than or equal to `min_status`
+    /// as error responses.
+    pub fn new(min_status: StatusCode) -> Self {
+        Self { min_status }
+    }
+
+    /// Register the [default constructor](ErrorResponseThreshold::default)
+    /// for [`ErrorResponseThreshold`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::middleware::ErrorResponseThreshold as std::default::Default>::default),
+            Lifecycle::RequestScoped,
+        )
+    }
+
+    /// The lowest status code that is treated as an error response.
+    pub fn min_status(&self) -> StatusCode {
+        self.min_status
+    }
+
+    /// Returns `true` if the status code of `response` is at or above the threshold.
+    pub fn is_error(&self, response: &Response) -> bool {
+        response.status() >= self.min_status
+    }
+
+    /// Execute the rest of the request processing pipeline and, if it produced an
+    /// error response, transform it using `transform`.
+    ///
+    /// Responses below the threshold are returned as they are.
+    pub async fn map<C, F>(&self, next: Next<C>, transform: F) -> Response
+    where
+        C: IntoFuture<Output = Response>,
+        F: FnOnce(Response) -> Response,
+    {
+        let response = next.into_future().await;
+        if self.is_error(&response) {
+            transform(response)
+        } else {
+            response
+        }
+    }
+}
+
+impl Default for ErrorResponseThreshold {
+    fn default() -> Self {
+        Self::new(StatusCode::BAD_REQUEST)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::{HeaderName, HeaderValue, StatusCode};
+    use crate::response::Response;
+
+    use super::{ErrorResponseThreshold, Next};
+
+    fn tag(response: Response) -> Response {
+        response.insert_header(
+            HeaderName::from_static("x-error"),
+            HeaderValue::from_static("true"),
+        )
+    }
+
+    #[tokio::test]
+    async fn error_responses_are_transformed() {
+        let next = Next::new(async { Response::not_found() });
+        let response = ErrorResponseThreshold::default().map(next, tag).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers().get("x-error").unwrap(), "true");
+    }
+
+    #[tokio::test]
+    async fn successful_responses_are_left_untouched() {
+        let next = Next::new(async { Response::ok() });
+        let response = ErrorResponseThreshold::default().map(next, tag).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get("x-error").is_none());
+    }
+
+    #[tokio::test]
+    async fn the_threshold_can_be_customized() {
+        let threshold = ErrorResponseThreshold::new(StatusCode::INTERNAL_SERVER_ERROR);
+        let next = Next::new(async { Response::not_found() });
+        let response = threshold.map(next, tag).await;
+        assert!(response.headers().get("x-error").is_none());
+    }
+}
@@ -1,5 +1,6 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 use http::header::CONTENT_LENGTH;
+use http::HeaderMap;
 use http_body_util::{BodyExt, Limited};
 
 use crate::blueprint::constructor::{Constructor, Lifecycle};
@@ -154,6 +155,12 @@ use super::{
 pub struct BufferedBody {
     /// The buffer of bytes that represents the body of the incoming request.
     pub bytes: Bytes,
+    /// The trailers sent by the client after the body, if any.
+    ///
+    /// Trailers are primarily used over HTTP/2 connections (e.g. by gRPC clients).
+    /// They are always `None` for HTTP/1.1 requests: trailers sent alongside a chunked
+    /// HTTP/1.1 body are discarded by the server.
+    pub trailers: Option<HeaderMap>,
 }
 
 impl BufferedBody {
@@ -171,6 +178,7 @@ impl BufferedBody {
             }
             BodySizeLimit::Disabled => match body.collect().await {
                 Ok(collected) => Ok(Self {
+                    trailers: collected.trailers().cloned(),
                     bytes: collected.to_bytes(),
                 }),
                 Err(e) => Err(UnexpectedBufferError { source: e.into() }.into()),
@@ -227,10 +235,22 @@ impl BufferedBody {
         // is smaller than the maximum size limit, we start buffering the body while keeping
         // track of the size limit.
         let limited_body = Limited::new(body, max_n_bytes);
-        match limited_body.collect().await {
-            Ok(collected) => Ok(Self {
-                bytes: collected.to_bytes(),
+        let buffered = match content_length {
+            // We know how many bytes to expect: we can allocate a buffer of the right size
+            // upfront, rather than growing it (and copying its contents over) as chunks come in.
+            // We can't trust the `Content-Length` header blindly, but we have already verified
+            // that it's within the size limit: a lying client can't trick us into allocating
+            // more than `max_n_bytes` ahead of time.
+            Some(len) => buffer_with_capacity(limited_body, len)
+                .await
+                .map(|(buffer, trailers)| (buffer.freeze(), trailers)),
+            None => limited_body.collect().await.map(|collected| {
+                let trailers = collected.trailers().cloned();
+                (collected.to_bytes(), trailers)
             }),
+        };
+        match buffered {
+            Ok((bytes, trailers)) => Ok(Self { bytes, trailers }),
             Err(e) => {
                 if e.downcast_ref::<http_body_util::LengthLimitError>()
                     .is_some()
@@ -244,6 +264,36 @@ impl BufferedBody {
     }
 }
 
+/// Buffer `body` in memory, using a pre-allocated buffer with the specified capacity.
+///
+/// The buffer will grow if the body turns out to be larger than `capacity`.
+/// The trailers sent after the body, if any, are returned alongside the buffer.
+async fn buffer_with_capacity<B>(
+    body: B,
+    capacity: usize,
+) -> Result<(BytesMut, Option<HeaderMap>), B::Error>
+where
+    B: hyper::body::Body,
+{
+    let mut body = std::pin::pin!(body);
+    let mut buffer = BytesMut::with_capacity(capacity);
+    let mut trailers: Option<HeaderMap> = None;
+    while let Some(frame) = body.frame().await {
+        match frame?.into_data() {
+            Ok(data) => buffer.put(data),
+            Err(frame) => {
+                if let Ok(t) = frame.into_trailers() {
+                    match &mut trailers {
+                        Some(trailers) => trailers.extend(t),
+                        None => trailers = Some(t),
+                    }
+                }
+            }
+        }
+    }
+    Ok((buffer, trailers))
+}
+
 impl From<BufferedBody> for Bytes {
     fn from(buffered_body: BufferedBody) -> Self {
         buffered_body.bytes
@@ -252,28 +302,22 @@ impl From<BufferedBody> for Bytes {
 
 #[cfg(test)]
 mod tests {
+    use std::convert::Infallible;
+
     use http::HeaderMap;
+    use http_body::Frame;
+    use http_body_util::StreamBody;
 
     use crate::request::RequestHead;
 
-    use super::{BufferedBody, Bytes};
-
-    // No headers.
-    fn dummy_request_head() -> RequestHead {
-        RequestHead {
-            method: http::Method::GET,
-            target: "/".parse().unwrap(),
-            version: http::Version::HTTP_11,
-            headers: HeaderMap::new(),
-        }
-    }
+    use super::{buffer_with_capacity, BufferedBody, Bytes};
 
     #[tokio::test]
     async fn error_if_body_above_size_limit_without_content_length() {
         let body = crate::response::body::raw::Full::new(Bytes::from(vec![0; 1000]));
         // Smaller than the size of the body.
         let max_n_bytes = 100;
-        let err = BufferedBody::_extract_with_limit(&dummy_request_head(), body, max_n_bytes)
+        let err = BufferedBody::_extract_with_limit(&RequestHead::for_tests(), body, max_n_bytes)
             .await
             .unwrap_err();
         insta::assert_display_snapshot!(err, @"The request body is larger than the maximum size limit enforced by this server.");
@@ -318,4 +362,67 @@ mod tests {
         )
         "###);
     }
+
+    #[tokio::test]
+    async fn buffer_is_allocated_once_if_content_length_is_known() {
+        let chunk_size = 1024;
+        let n_chunks = 1024;
+        let content_length = chunk_size * n_chunks;
+        let chunks = (0..n_chunks)
+            .map(|_| Ok::<_, Infallible>(Frame::data(Bytes::from(vec![0; chunk_size]))));
+        let body = StreamBody::new(futures_util::stream::iter(chunks));
+
+        let (buffer, trailers) = buffer_with_capacity(body, content_length).await.unwrap();
+
+        assert!(trailers.is_none());
+        assert_eq!(buffer.len(), content_length);
+        // The buffer would have grown past its initial capacity if it had been
+        // reallocated while reading the body.
+        assert_eq!(buffer.capacity(), content_length);
+    }
+
+    #[tokio::test]
+    async fn body_is_buffered_if_content_length_is_within_limit() {
+        let mut request_head = dummy_request_head();
+        let body = crate::response::body::raw::Full::new(Bytes::from(vec![0; 500]));
+        request_head
+            .headers
+            .insert("Content-Length", "500".parse().unwrap());
+
+        let buffered = BufferedBody::_extract_with_limit(&request_head, body, 1000)
+            .await
+            .unwrap();
+        assert_eq!(buffered.bytes.len(), 500);
+    }
+
+    #[tokio::test]
+    async fn trailers_are_captured() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc".parse().unwrap());
+        let frames = || {
+            vec![
+                Ok::<_, Infallible>(Frame::data(Bytes::from_static(b"hello"))),
+                Ok(Frame::trailers(trailers.clone())),
+            ]
+        };
+        // With a known content length.
+        let mut request_head = dummy_request_head();
+        request_head
+            .headers
+            .insert("Content-Length", "5".parse().unwrap());
+        let body = StreamBody::new(futures_util::stream::iter(frames()));
+        let buffered = BufferedBody::_extract_with_limit(&request_head, body, 1000)
+            .await
+            .unwrap();
+        assert_eq!(buffered.bytes, "hello");
+        assert_eq!(buffered.trailers.unwrap()["x-checksum"], "abc");
+
+        // Without a content length.
+        let body = StreamBody::new(futures_util::stream::iter(frames()));
+        let buffered = BufferedBody::_extract_with_limit(&RequestHead::for_tests(), body, 1000)
+            .await
+            .unwrap();
+        assert_eq!(buffered.bytes, "hello");
+        assert_eq!(buffered.trailers.unwrap()["x-checksum"], "abc");
+    }
 }
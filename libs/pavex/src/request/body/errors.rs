@@ -14,6 +14,9 @@ pub enum ExtractJsonBodyError {
     /// See [`JsonContentTypeMismatch`] for details.
     ContentTypeMismatch(#[from] JsonContentTypeMismatch),
     #[error(transparent)]
+    /// See [`UnsupportedJsonCharset`] for details.
+    UnsupportedCharset(#[from] UnsupportedJsonCharset),
+    #[error(transparent)]
     /// See [`JsonDeserializationError`] for details.
     DeserializationError(#[from] JsonDeserializationError),
 }
@@ -23,7 +26,8 @@ impl ExtractJsonBodyError {
     pub fn into_response(&self) -> Response {
         match self {
             ExtractJsonBodyError::MissingContentType(_)
-            | ExtractJsonBodyError::ContentTypeMismatch(_) => Response::unsupported_media_type(),
+            | ExtractJsonBodyError::ContentTypeMismatch(_)
+            | ExtractJsonBodyError::UnsupportedCharset(_) => Response::unsupported_media_type(),
             ExtractJsonBodyError::DeserializationError(_) => Response::bad_request(),
         }
         .set_typed_body(format!("{}", self))
@@ -108,3 +112,18 @@ pub struct JsonContentTypeMismatch {
     /// The actual value of the `Content-Type` header for this request.
     pub actual: String,
 }
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+"The `Content-Type` header declares `{charset}` as charset. This endpoint expects JSON documents to be encoded as UTF-8"
+)]
+#[non_exhaustive]
+/// The `Content-Type` header declares a charset other than UTF-8.
+///
+/// JSON documents exchanged between systems must be encoded as UTF-8
+/// (see [RFC 8259](https://www.rfc-editor.org/rfc/rfc8259#section-8.1)),
+/// therefore Pavex rejects the request instead of trying to transcode the body.
+pub struct UnsupportedJsonCharset {
+    /// The charset declared in the `Content-Type` header for this request.
+    pub charset: String,
+}
@@ -10,7 +10,7 @@ use super::{
     buffered_body::BufferedBody,
     errors::{
         ExtractJsonBodyError, JsonContentTypeMismatch, JsonDeserializationError,
-        MissingJsonContentType,
+        MissingJsonContentType, UnsupportedJsonCharset,
     },
 };
 
@@ -118,6 +118,7 @@ impl<T> JsonBody<T> {
     ///
     /// - the `Content-Type` is missing
     /// - the `Content-Type` header is not set to `application/json` or another `application/*+json` MIME type
+    /// - the `Content-Type` header declares a charset other than UTF-8 (e.g. `charset=iso-8859-1`)
     /// - the request body is not a valid JSON document
     ///
     /// In all of the above cases, an [`ExtractJsonBodyError`] is returned.
@@ -157,6 +158,7 @@ impl JsonBody<()> {
 
 /// Check that the `Content-Type` header is set to `application/json`, or another
 /// `application/*+json` MIME type.
+/// If the header specifies a `charset` parameter, it must be UTF-8.
 ///
 /// Return an error otherwise.
 fn check_json_content_type(headers: &HeaderMap) -> Result<(), ExtractJsonBodyError> {
@@ -182,6 +184,16 @@ fn check_json_content_type(headers: &HeaderMap) -> Result<(), ExtractJsonBodyErr
         }
         .into());
     }
+
+    if let Some(charset) = mime.get_param(mime::CHARSET) {
+        let charset = charset.as_str();
+        if !charset.eq_ignore_ascii_case("utf-8") && !charset.eq_ignore_ascii_case("utf8") {
+            return Err(UnsupportedJsonCharset {
+                charset: charset.to_string(),
+            }
+            .into());
+        }
+    }
     Ok(())
 }
 
@@ -272,6 +284,37 @@ mod tests {
         assert!(outcome.is_ok());
     }
 
+    #[test]
+    fn json_content_type_with_uppercase_charset() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "application/json; charset=UTF-8".parse().unwrap(),
+        );
+
+        let outcome = super::check_json_content_type(&headers);
+        assert!(outcome.is_ok());
+    }
+
+    #[test]
+    fn json_content_type_with_non_utf8_charset() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            "application/json; charset=iso-8859-1".parse().unwrap(),
+        );
+
+        let err = super::check_json_content_type(&headers).unwrap_err();
+        insta::assert_display_snapshot!(err, @"The `Content-Type` header declares `iso-8859-1` as charset. This endpoint expects JSON documents to be encoded as UTF-8");
+        insta::assert_debug_snapshot!(err, @r###"
+        UnsupportedCharset(
+            UnsupportedJsonCharset {
+                charset: "iso-8859-1",
+            },
+        )
+        "###);
+    }
+
     #[test]
     /// Let's check the error quality when the request body is missing
     /// a required field.
@@ -285,17 +328,8 @@ mod tests {
             age: u8,
         }
 
-        let mut headers = http::HeaderMap::new();
-        headers.insert(
-            http::header::CONTENT_TYPE,
-            "application/json; charset=utf-8".parse().unwrap(),
-        );
-        let request_head = crate::request::RequestHead {
-            headers,
-            method: http::Method::GET,
-            target: "/".parse().unwrap(),
-            version: http::Version::HTTP_11,
-        };
+        let request_head = crate::request::RequestHead::for_tests()
+            .with_header("content-type", "application/json; charset=utf-8");
         let body = serde_json::json!({
             "name": "John Doe",
             "age": 43,
@@ -304,6 +338,7 @@ mod tests {
         // Act
         let buffered_body = crate::request::body::BufferedBody {
             bytes: serde_json::to_vec(&body).unwrap().into(),
+            trailers: None,
         };
         let outcome: Result<JsonBody<BodySchema>, _> =
             JsonBody::extract(&request_head, &buffered_body);
@@ -1,7 +1,9 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use http_body::{Body, Frame};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::Full;
 use hyper::body::Incoming;
 use pin_project_lite::pin_project;
 
@@ -22,14 +24,32 @@ pin_project! {
     /// You should generally prefer [`BufferedBody`] instead, which enforces a size limit on the
     /// incoming body.
     ///
+    /// # Full control
+    ///
+    /// You can pair `RawIncomingBody` with a [`ResponseSender`] if you need to take over
+    /// the entire exchange—e.g. to stream the response while the request body is still
+    /// coming in.
+    ///
     /// [`BufferedBody`]: crate::request::body::BufferedBody
+    /// [`ResponseSender`]: crate::response::ResponseSender
     #[derive(Debug)]
     pub struct RawIncomingBody {
-        #[pin] inner: Incoming,
+        #[pin] inner: Inner,
+    }
+}
+
+pin_project! {
+    #[project = InnerProj]
+    #[derive(Debug)]
+    enum Inner {
+        // The body of a request received from the network.
+        Incoming { #[pin] body: Incoming },
+        // The body of a request dispatched internally, via a `Dispatcher`.
+        Buffered { #[pin] body: Full<Bytes> },
     }
 }
 
-// We just delegate to the underlying `Incoming` `Body` implementation.
+// We just delegate to the underlying `Body` implementation.
 impl Body for RawIncomingBody {
     type Data = <Incoming as Body>::Data;
     type Error = <Incoming as Body>::Error;
@@ -38,12 +58,47 @@ impl Body for RawIncomingBody {
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        self.project().inner.poll_frame(cx)
+        match self.project().inner.project() {
+            InnerProj::Incoming { body } => body.poll_frame(cx),
+            InnerProj::Buffered { body } => body
+                .poll_frame(cx)
+                .map(|frame| frame.map(|result| result.map_err(|e| match e {}))),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.inner {
+            Inner::Incoming { body } => body.is_end_stream(),
+            Inner::Buffered { body } => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.inner {
+            Inner::Incoming { body } => body.size_hint(),
+            Inner::Buffered { body } => body.size_hint(),
+        }
     }
 }
 
 impl From<Incoming> for RawIncomingBody {
-    fn from(inner: Incoming) -> Self {
-        Self { inner }
+    fn from(body: Incoming) -> Self {
+        Self {
+            inner: Inner::Incoming { body },
+        }
+    }
+}
+
+impl From<Bytes> for RawIncomingBody {
+    /// Build a `RawIncomingBody` from an in-memory buffer.
+    ///
+    /// It is primarily used to build the body of requests that are
+    /// [dispatched internally](crate::router::Dispatcher).
+    fn from(bytes: Bytes) -> Self {
+        Self {
+            inner: Inner::Buffered {
+                body: Full::new(bytes),
+            },
+        }
     }
 }
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+/// The format of the identifiers generated by Pavex for requests that don't carry one.
+///
+/// Check out [`RequestIdConfig`](super::RequestIdConfig) for more details.
+pub struct RequestIdFormat(FormatKind);
+
+#[derive(Clone)]
+enum FormatKind {
+    UuidV4,
+    UuidV7,
+    Ulid,
+    Custom(Arc<dyn Fn() -> String + Send + Sync>),
+}
+
+impl RequestIdFormat {
+    /// A random [UUID (version 4)](https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-4)—e.g.
+    /// `3f2a6c1e-94b2-4d8e-8a57-0c1f7b9e2d41`.
+    ///
+    /// It's the default format.
+    pub fn uuid_v4() -> Self {
+        Self(FormatKind::UuidV4)
+    }
+
+    /// A time-ordered [UUID (version 7)](https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-7)—e.g.
+    /// `018b3a4c-9f1e-7d2a-9c41-6e0b2f8d5a73`.
+    pub fn uuid_v7() -> Self {
+        Self(FormatKind::UuidV7)
+    }
+
+    /// A time-ordered [ULID](https://github.com/ulid/spec)—e.g. `01HCX4S7RZ9B3Q2W8K5N6T0VJM`.
+    pub fn ulid() -> Self {
+        Self(FormatKind::Ulid)
+    }
+
+    /// Generate identifiers using your own function.
+    ///
+    /// It's primarily meant for tests, where you may want deterministic identifiers.
+    /// The generated identifiers must be valid header values—i.e. visible ASCII characters
+    /// only. Pavex falls back to [`RequestIdFormat::uuid_v4`] for any identifier that
+    /// doesn't satisfy this requirement.
+    pub fn custom<F>(generator: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        Self(FormatKind::Custom(Arc::new(generator)))
+    }
+
+    /// Generate a new identifier.
+    pub fn generate(&self) -> String {
+        match &self.0 {
+            FormatKind::UuidV4 => uuid_v4(),
+            FormatKind::UuidV7 => uuid_v7(),
+            FormatKind::Ulid => ulid(),
+            FormatKind::Custom(generator) => generator(),
+        }
+    }
+}
+
+impl Default for RequestIdFormat {
+    fn default() -> Self {
+        Self::uuid_v4()
+    }
+}
+
+impl std::fmt::Debug for RequestIdFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match &self.0 {
+            FormatKind::UuidV4 => "UuidV4",
+            FormatKind::UuidV7 => "UuidV7",
+            FormatKind::Ulid => "Ulid",
+            FormatKind::Custom(_) => "Custom",
+        };
+        f.debug_tuple("RequestIdFormat").field(&kind).finish()
+    }
+}
+
+/// Milliseconds since the Unix epoch, truncated to 48 bits.
+fn unix_timestamp_ms() -> u128 {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    elapsed.as_millis() & 0xFFFF_FFFF_FFFF
+}
+
+/// Set the version and variant bits of a UUID, as specified in RFC 9562.
+fn uuid_with_version(bits: u128, version: u128) -> u128 {
+    let bits = (bits & !(0xF << 76)) | (version << 76);
+    (bits & !(0b11 << 62)) | (0b10 << 62)
+}
+
+fn format_uuid(bits: u128) -> String {
+    let hex = format!("{bits:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn uuid_v4() -> String {
+    format_uuid(uuid_with_version(rand::random(), 4))
+}
+
+fn uuid_v7() -> String {
+    let random = rand::random::<u128>() & ((1 << 80) - 1);
+    format_uuid(uuid_with_version((unix_timestamp_ms() << 80) | random, 7))
+}
+
+fn ulid() -> String {
+    const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    let random = rand::random::<u128>() & ((1 << 80) - 1);
+    let bits = (unix_timestamp_ms() << 80) | random;
+    // 26 characters, 5 bits each: the first character only carries 3 bits.
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD[((bits >> (i * 5)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestIdFormat;
+
+    #[test]
+    fn uuids_have_the_expected_version_and_variant() {
+        for (format, version) in [
+            (RequestIdFormat::uuid_v4(), '4'),
+            (RequestIdFormat::uuid_v7(), '7'),
+        ] {
+            let id = format.generate();
+            assert_eq!(id.len(), 36);
+            let segments: Vec<_> = id.split('-').map(str::len).collect();
+            assert_eq!(segments, [8, 4, 4, 4, 12]);
+            assert_eq!(id.chars().nth(14), Some(version));
+            assert!(matches!(id.chars().nth(19), Some('8' | '9' | 'a' | 'b')));
+        }
+    }
+
+    #[test]
+    fn ulids_are_26_crockford_characters() {
+        let id = RequestIdFormat::ulid().generate();
+        assert_eq!(id.len(), 26);
+        assert!(id
+            .chars()
+            .all(|c| c.is_ascii_digit() || (c.is_ascii_uppercase() && !"ILOU".contains(c))));
+    }
+
+    #[test]
+    fn time_ordered_formats_are_sortable() {
+        for format in [RequestIdFormat::uuid_v7(), RequestIdFormat::ulid()] {
+            let first = format.generate();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            let second = format.generate();
+            assert!(first < second, "{first} should sort before {second}");
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Assign a unique identifier to each incoming request.
+//!
+//! Check out [`RequestId`] for more details.
+
+mod format;
+mod request_id;
+
+pub use format::RequestIdFormat;
+pub use request_id::{propagate_request_id, RequestId, RequestIdConfig};
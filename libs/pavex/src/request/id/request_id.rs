@@ -0,0 +1,329 @@
+use std::future::IntoFuture;
+
+use http::header::{HeaderName, HeaderValue};
+
+use crate::blueprint::constructor::{CloningStrategy, Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::middleware::Next;
+use crate::request::RequestHead;
+use crate::response::Response;
+
+use super::RequestIdFormat;
+
+/// The maximum length of an identifier that Pavex accepts from an incoming request.
+const MAX_INCOMING_ID_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A unique identifier for the incoming request.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Configuration](#configuration)
+/// - [Deterministic identifiers](#deterministic-identifiers)
+///
+/// # Installation
+///
+/// Register the [default constructor](RequestId::register) for [`RequestId`] alongside the
+/// [default constructor](RequestIdConfig::register) for [`RequestIdConfig`].
+/// [`RequestId::register`] doesn't register a constructor for [`RequestIdConfig`]: you need both,
+/// otherwise Pavex will reject your blueprint since [`RequestId::extract`] can't be invoked.
+/// Wrap your routes with [`propagate_request_id`] if you want the identifier to be
+/// attached to the outgoing response:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{router::GET, Blueprint};
+/// use pavex::request::id::{RequestId, RequestIdConfig};
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     RequestIdConfig::register(&mut bp);
+///     RequestId::register(&mut bp);
+///     bp.wrap(f!(pavex::request::id::propagate_request_id));
+///     bp.route(GET, "/", f!(crate::handler));
+///     bp
+/// }
+///
+/// pub fn handler(request_id: &RequestId) -> String {
+///     format!("Your request id is {request_id}")
+/// }
+/// ```
+///
+/// If the incoming request already carries an identifier (e.g. it was assigned by a load
+/// balancer), Pavex reuses it. Otherwise, a new identifier is generated.
+///
+/// # Configuration
+///
+/// By default, the identifier is read from (and written to) the `X-Request-Id` header
+/// and new identifiers are [random UUIDs](RequestIdFormat::uuid_v4).
+/// Register your own constructor for [`RequestIdConfig`] to change either:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{constructor::Lifecycle, Blueprint};
+/// use pavex::http::HeaderName;
+/// use pavex::request::id::{RequestId, RequestIdConfig, RequestIdFormat};
+///
+/// pub fn request_id_config() -> RequestIdConfig {
+///     RequestIdConfig::default()
+///         .header_name(HeaderName::from_static("x-correlation-id"))
+///         .format(RequestIdFormat::ulid())
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(f!(crate::request_id_config), Lifecycle::Singleton);
+///     RequestId::register(&mut bp);
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// # Deterministic identifiers
+///
+/// Use [`RequestIdFormat::custom`] to take full control of identifier generation—e.g.
+/// to get predictable identifiers in your test suite:
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use pavex::request::id::{RequestIdConfig, RequestIdFormat};
+///
+/// pub fn test_request_id_config() -> RequestIdConfig {
+///     let counter = AtomicU64::new(0);
+///     RequestIdConfig::default().format(RequestIdFormat::custom(move || {
+///         format!("test-{}", counter.fetch_add(1, Ordering::Relaxed))
+///     }))
+/// }
+/// ```
+#[doc(alias = "CorrelationId")]
+pub struct RequestId(HeaderValue);
+
+impl RequestId {
+    /// The default constructor for [`RequestId`].
+    ///
+    /// It reuses the identifier carried by the incoming request in the header specified
+    /// by [`RequestIdConfig`], if there is one.
+    /// The incoming identifier is ignored if it's empty, longer than 200 bytes or if it
+    /// contains characters other than visible ASCII.
+    ///
+    /// Otherwise, a new identifier is generated using the configured [`RequestIdFormat`].
+    pub fn extract(request_head: &RequestHead, config: &RequestIdConfig) -> Self {
+        let incoming = request_head
+            .headers
+            .get(&config.header_name)
+            .filter(|value| is_valid_id(value.as_bytes()));
+        if let Some(value) = incoming {
+            return Self(value.clone());
+        }
+        Self::generate(&config.format)
+    }
+
+    /// Generate a new identifier using the specified format.
+    ///
+    /// It falls back to [`RequestIdFormat::uuid_v4`] if the generated identifier is not a
+    /// valid header value.
+    pub fn generate(format: &RequestIdFormat) -> Self {
+        let id = format.generate();
+        match HeaderValue::try_from(id) {
+            Ok(value) if is_valid_id(value.as_bytes()) => Self(value),
+            _ => Self::generate(&RequestIdFormat::uuid_v4()),
+        }
+    }
+
+    /// Register the [default constructor](RequestId::extract)
+    /// for [`RequestId`] with a [`Blueprint`].
+    ///
+    /// It doesn't register a constructor for [`RequestIdConfig`]: use
+    /// [`RequestIdConfig::register`] or register your own.
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::request::id::RequestId::extract),
+            Lifecycle::RequestScoped,
+        )
+        .cloning(CloningStrategy::CloneIfNecessary)
+    }
+
+    /// The identifier, as a string.
+    pub fn as_str(&self) -> &str {
+        // Validated to be visible ASCII when the identifier was built.
+        self.0.to_str().unwrap_or_default()
+    }
+
+    /// The identifier, as a header value.
+    pub fn header_value(&self) -> &HeaderValue {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn is_valid_id(id: &[u8]) -> bool {
+    !id.is_empty() && id.len() <= MAX_INCOMING_ID_LENGTH && id.iter().all(u8::is_ascii_graphic)
+}
+
+#[derive(Debug, Clone)]
+/// Determine how [`RequestId`]s are read from incoming requests, generated and propagated.
+///
+/// Check out [`RequestId`]'s documentation for more details.
+pub struct RequestIdConfig {
+    header_name: HeaderName,
+    format: RequestIdFormat,
+}
+
+impl RequestIdConfig {
+    /// Set the name of the header that carries the request identifier, both on incoming
+    /// requests and outgoing responses.
+    ///
+    /// It defaults to `X-Request-Id`.
+    pub fn header_name(mut self, header_name: HeaderName) -> Self {
+        self.header_name = header_name;
+        self
+    }
+
+    /// Set the format of the identifiers generated for requests that don't carry one.
+    ///
+    /// It defaults to [`RequestIdFormat::uuid_v4`].
+    pub fn format(mut self, format: RequestIdFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Register the [default constructor](RequestIdConfig::default)
+    /// for [`RequestIdConfig`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::request::id::RequestIdConfig as std::default::Default>::default),
+            Lifecycle::Singleton,
+        )
+    }
+}
+
+impl Default for RequestIdConfig {
+    fn default() -> Self {
+        Self {
+            header_name: HeaderName::from_static("x-request-id"),
+            format: RequestIdFormat::default(),
+        }
+    }
+}
+
+/// A wrapping middleware that attaches the [`RequestId`] to the outgoing response,
+/// using the header specified by [`RequestIdConfig`].
+///
+/// An identifier set by the request handler (or by other middlewares) is left untouched.
+pub async fn propagate_request_id<C>(
+    next: Next<C>,
+    request_id: RequestId,
+    config: &RequestIdConfig,
+) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    let mut response = next.into_future().await;
+    response
+        .headers_mut()
+        .entry(config.header_name.clone())
+        .or_insert(request_id.0);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use http::{HeaderName, HeaderValue};
+
+    use super::{propagate_request_id, RequestId, RequestIdConfig};
+    use crate::middleware::Next;
+    use crate::request::id::RequestIdFormat;
+    use crate::request::RequestHead;
+    use crate::response::Response;
+
+    fn deterministic_config() -> RequestIdConfig {
+        let counter = AtomicU64::new(0);
+        RequestIdConfig::default().format(RequestIdFormat::custom(move || {
+            format!("test-{}", counter.fetch_add(1, Ordering::Relaxed))
+        }))
+    }
+
+    #[test]
+    fn incoming_ids_are_reused() {
+        let head = RequestHead::for_tests().with_header("x-request-id", "abc-123");
+        let id = RequestId::extract(&head, &RequestIdConfig::default());
+        assert_eq!(id.as_str(), "abc-123");
+    }
+
+    #[test]
+    fn invalid_incoming_ids_are_replaced() {
+        let config = deterministic_config();
+        let head = RequestHead::for_tests().with_header("x-request-id", "");
+        assert_eq!(RequestId::extract(&head, &config).as_str(), "test-0");
+        let head = RequestHead::for_tests().with_header("x-request-id", "with spaces");
+        assert_eq!(RequestId::extract(&head, &config).as_str(), "test-1");
+    }
+
+    #[test]
+    fn custom_header_name() {
+        let config =
+            RequestIdConfig::default().header_name(HeaderName::from_static("x-correlation-id"));
+        let head = RequestHead::for_tests().with_headers(&[
+            ("x-request-id", "ignored"),
+            ("x-correlation-id", "correlated"),
+        ]);
+        assert_eq!(RequestId::extract(&head, &config).as_str(), "correlated");
+    }
+
+    #[test]
+    fn deterministic_generator() {
+        let config = deterministic_config();
+        let head = RequestHead::for_tests();
+        let ids: Vec<_> = (0..3)
+            .map(|_| RequestId::extract(&head, &config).to_string())
+            .collect();
+        assert_eq!(ids, ["test-0", "test-1", "test-2"]);
+    }
+
+    #[test]
+    fn invalid_generated_ids_fall_back_to_uuids() {
+        let format = RequestIdFormat::custom(|| "not\na header value".into());
+        let id = RequestId::generate(&format);
+        assert_eq!(id.as_str().len(), 36);
+    }
+
+    #[tokio::test]
+    async fn the_id_is_attached_to_the_response() {
+        let config =
+            RequestIdConfig::default().header_name(HeaderName::from_static("x-correlation-id"));
+        let request_id = RequestId::extract(
+            &RequestHead::for_tests().with_header("x-correlation-id", "abc"),
+            &config,
+        );
+        let next = Next::new(async { Response::ok() });
+        let response = propagate_request_id(next, request_id, &config).await;
+        assert_eq!(response.headers()["x-correlation-id"], "abc");
+        assert!(response.headers().get("x-request-id").is_none());
+    }
+
+    #[tokio::test]
+    async fn ids_set_by_the_handler_are_preserved() {
+        let config = RequestIdConfig::default();
+        let request_id = RequestId::extract(
+            &RequestHead::for_tests().with_header("x-request-id", "abc"),
+            &config,
+        );
+        let next = Next::new(async {
+            Response::ok().insert_header(
+                HeaderName::from_static("x-request-id"),
+                HeaderValue::from_static("from-handler"),
+            )
+        });
+        let response = propagate_request_id(next, request_id, &config).await;
+        assert_eq!(response.headers()["x-request-id"], "from-handler");
+    }
+}
@@ -1,7 +1,13 @@
 //! Process and extract data from incoming HTTP requests.
-pub use request_head::RequestHead;
+pub use params_error_status::ParamsErrorStatus;
+pub use request_head::{RequestHead, RequestHeadView};
 
 pub mod body;
+pub mod id;
+mod params_error_status;
 pub mod path;
 pub mod query;
+pub mod range;
 mod request_head;
+pub mod url;
+pub mod version;
@@ -0,0 +1,123 @@
+use http::StatusCode;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The status codes returned to the caller when the route parameters or the query parameters
+/// of an incoming request can't be deserialized.
+///
+/// # Failure categories
+///
+/// Deserialization failures are grouped into three categories:
+///
+/// - **Malformed input**: the raw data can't be processed—e.g. a percent-decoded route
+///   parameter is not valid UTF-8 or a custom deserializer rejected a query parameter.
+/// - **Type mismatch**: the value is well-formed, but it doesn't fit the expected type—e.g.
+///   `?page=abc` for a `u32` field or `?page=-1` for an unsigned integer.
+/// - **Missing field**: a required query parameter is absent—e.g. `?page=1` when both `page`
+///   and `size` are required.
+///   Route parameters can't be missing: Pavex verifies that `T` in [`PathParams<T>`] matches
+///   the route template at compile-time.
+///
+/// All categories default to `400 Bad Request`.
+///
+/// Route parameters that are rejected by a custom deserializer are an exception: Pavex can't
+/// tell if the rejection is caused by the caller or by a programmer error, so it always
+/// returns a `500 Internal Server Error`, regardless of the configured status codes.
+///
+/// # Installation
+///
+/// Register a constructor for [`ParamsErrorStatus`] and use the `into_response_with`
+/// error handlers instead of the default ones:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{constructor::Lifecycle, Blueprint};
+/// use pavex::http::StatusCode;
+/// use pavex::request::path::PathParams;
+/// use pavex::request::query::QueryParams;
+/// use pavex::request::ParamsErrorStatus;
+///
+/// pub fn params_error_status() -> ParamsErrorStatus {
+///     ParamsErrorStatus::default().type_mismatch(StatusCode::UNPROCESSABLE_ENTITY)
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(f!(crate::params_error_status), Lifecycle::Singleton);
+///     PathParams::register(&mut bp).error_handler(f!(
+///         pavex::request::path::errors::ExtractPathParamsError::into_response_with
+///     ));
+///     QueryParams::register(&mut bp).error_handler(f!(
+///         pavex::request::query::errors::ExtractQueryParamsError::into_response_with
+///     ));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// [`PathParams<T>`]: struct@crate::request::path::PathParams
+pub struct ParamsErrorStatus {
+    malformed: StatusCode,
+    type_mismatch: StatusCode,
+    missing_field: StatusCode,
+}
+
+impl ParamsErrorStatus {
+    /// Set the status code returned when the incoming data is malformed.
+    ///
+    /// It defaults to `400 Bad Request`.
+    pub fn malformed(mut self, status: StatusCode) -> Self {
+        self.malformed = status;
+        self
+    }
+
+    /// Set the status code returned when a value doesn't match the expected type.
+    ///
+    /// It defaults to `400 Bad Request`.
+    pub fn type_mismatch(mut self, status: StatusCode) -> Self {
+        self.type_mismatch = status;
+        self
+    }
+
+    /// Set the status code returned when a required query parameter is missing.
+    ///
+    /// It defaults to `400 Bad Request`.
+    pub fn missing_field(mut self, status: StatusCode) -> Self {
+        self.missing_field = status;
+        self
+    }
+
+    /// Register the [default constructor](ParamsErrorStatus::default)
+    /// for [`ParamsErrorStatus`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::request::ParamsErrorStatus as std::default::Default>::default),
+            Lifecycle::Singleton,
+        )
+    }
+
+    pub(crate) fn for_malformed(&self) -> StatusCode {
+        self.malformed
+    }
+
+    pub(crate) fn for_type_mismatch(&self) -> StatusCode {
+        self.type_mismatch
+    }
+
+    pub(crate) fn for_missing_field(&self) -> StatusCode {
+        self.missing_field
+    }
+}
+
+impl Default for ParamsErrorStatus {
+    fn default() -> Self {
+        Self {
+            malformed: StatusCode::BAD_REQUEST,
+            type_mismatch: StatusCode::BAD_REQUEST,
+            missing_field: StatusCode::BAD_REQUEST,
+        }
+    }
+}
@@ -30,6 +30,7 @@
 // DEALINGS IN THE SOFTWARE.
 use std::any::type_name;
 use std::borrow::Cow;
+use std::num::{IntErrorKind, ParseFloatError, ParseIntError};
 
 use serde::{
     de::{self, DeserializeSeed, EnumAccess, Error, MapAccess, VariantAccess, Visitor},
@@ -214,10 +215,25 @@ impl<'de, 'request> MapAccess<'request> for MapDeserializer<'de, 'request> {
         V: DeserializeSeed<'request>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(ValueDeserializer {
-                key: self.key.take(),
-                value: value.clone(),
-            }),
+            Some(value) => {
+                let key = self.key.take();
+                seed.deserialize(ValueDeserializer {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .map_err(|e| match (e.kind, key) {
+                    // Errors raised by custom deserialization logic (e.g. `deserialize_with`)
+                    // don't know which key they were invoked for: we add it here.
+                    (ErrorKind::InvalidValue { expected, .. }, Some(key)) => {
+                        PathDeserializationError::new(ErrorKind::InvalidValueAtKey {
+                            key: key.0.to_string(),
+                            value: value.to_string(),
+                            expected,
+                        })
+                    }
+                    (kind, _) => PathDeserializationError::new(kind),
+                })
+            }
             None => Err(PathDeserializationError::custom("value is missing")),
         }
     }
@@ -261,36 +277,86 @@ impl<'de> Deserializer<'de> for KeyDeserializer<'de> {
 
 macro_rules! parse_value {
     ($trait_fn:ident, $visit_fn:ident, $ty:literal) => {
+        parse_value!($trait_fn, $visit_fn, $ty, |_| ParseFailure::Invalid);
+    };
+    ($trait_fn:ident, $visit_fn:ident, $ty:literal, $classify:expr) => {
         fn $trait_fn<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
         where
             V: Visitor<'request>,
         {
-            let v = self.value.parse().map_err(|_| {
-                if let Some(key) = self.key.take() {
-                    let kind = ErrorKind::ParseErrorAtKey {
-                        key: key.0.to_string(),
-                        value: self.value.to_string(),
-                        expected_type: $ty,
-                    };
-                    PathDeserializationError::new(kind)
-                } else {
-                    PathDeserializationError::new(ErrorKind::ParseError {
-                        value: self.value.to_string(),
-                        expected_type: $ty,
-                    })
-                }
-            })?;
+            let v = self
+                .value
+                .parse()
+                .map_err(|e| self.parse_error($ty, ($classify)(e)))?;
             visitor.$visit_fn(v)
         }
     };
 }
 
+/// Why a path parameter value couldn't be parsed into the expected type.
+enum ParseFailure {
+    Invalid,
+    OutOfRange,
+    InvalidFloat,
+}
+
+fn integer_failure(e: ParseIntError) -> ParseFailure {
+    match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseFailure::OutOfRange,
+        _ => ParseFailure::Invalid,
+    }
+}
+
+fn float_failure(_: ParseFloatError) -> ParseFailure {
+    ParseFailure::InvalidFloat
+}
+
 #[derive(Debug)]
 struct ValueDeserializer<'request> {
     key: Option<Key<'request>>,
     value: Cow<'request, str>,
 }
 
+impl<'request> ValueDeserializer<'request> {
+    fn parse_error(
+        &mut self,
+        expected_type: &'static str,
+        failure: ParseFailure,
+    ) -> PathDeserializationError {
+        let value = self.value.to_string();
+        let kind = match (self.key.take(), failure) {
+            (Some(key), ParseFailure::Invalid) => ErrorKind::ParseErrorAtKey {
+                key: key.0.to_string(),
+                value,
+                expected_type,
+            },
+            (None, ParseFailure::Invalid) => ErrorKind::ParseError {
+                value,
+                expected_type,
+            },
+            (Some(key), ParseFailure::OutOfRange) => ErrorKind::OutOfRangeAtKey {
+                key: key.0.to_string(),
+                value,
+                expected_type,
+            },
+            (None, ParseFailure::OutOfRange) => ErrorKind::OutOfRange {
+                value,
+                expected_type,
+            },
+            (Some(key), ParseFailure::InvalidFloat) => ErrorKind::InvalidFloatAtKey {
+                key: key.0.to_string(),
+                value,
+                expected_type,
+            },
+            (None, ParseFailure::InvalidFloat) => ErrorKind::InvalidFloat {
+                value,
+                expected_type,
+            },
+        };
+        PathDeserializationError::new(kind)
+    }
+}
+
 impl<'request> Deserializer<'request> for ValueDeserializer<'request> {
     type Error = PathDeserializationError;
 
@@ -298,18 +364,18 @@ impl<'request> Deserializer<'request> for ValueDeserializer<'request> {
     unsupported_type!(deserialize_identifier);
 
     parse_value!(deserialize_bool, visit_bool, "bool");
-    parse_value!(deserialize_i8, visit_i8, "i8");
-    parse_value!(deserialize_i16, visit_i16, "i16");
-    parse_value!(deserialize_i32, visit_i32, "i32");
-    parse_value!(deserialize_i64, visit_i64, "i64");
-    parse_value!(deserialize_i128, visit_i128, "i128");
-    parse_value!(deserialize_u8, visit_u8, "u8");
-    parse_value!(deserialize_u16, visit_u16, "u16");
-    parse_value!(deserialize_u32, visit_u32, "u32");
-    parse_value!(deserialize_u64, visit_u64, "u64");
-    parse_value!(deserialize_u128, visit_u128, "u128");
-    parse_value!(deserialize_f32, visit_f32, "f32");
-    parse_value!(deserialize_f64, visit_f64, "f64");
+    parse_value!(deserialize_i8, visit_i8, "i8", integer_failure);
+    parse_value!(deserialize_i16, visit_i16, "i16", integer_failure);
+    parse_value!(deserialize_i32, visit_i32, "i32", integer_failure);
+    parse_value!(deserialize_i64, visit_i64, "i64", integer_failure);
+    parse_value!(deserialize_i128, visit_i128, "i128", integer_failure);
+    parse_value!(deserialize_u8, visit_u8, "u8", integer_failure);
+    parse_value!(deserialize_u16, visit_u16, "u16", integer_failure);
+    parse_value!(deserialize_u32, visit_u32, "u32", integer_failure);
+    parse_value!(deserialize_u64, visit_u64, "u64", integer_failure);
+    parse_value!(deserialize_u128, visit_u128, "u128", integer_failure);
+    parse_value!(deserialize_f32, visit_f32, "f32", float_failure);
+    parse_value!(deserialize_f64, visit_f64, "f64", float_failure);
     parse_value!(deserialize_string, visit_string, "String");
     parse_value!(deserialize_byte_buf, visit_string, "String");
     parse_value!(deserialize_char, visit_char, "char");
@@ -345,7 +411,13 @@ impl<'request> Deserializer<'request> for ValueDeserializer<'request> {
     where
         V: Visitor<'request>,
     {
-        visitor.visit_some(self)
+        // A catch-all parameter may capture an empty path suffix:
+        // we treat it as a missing value.
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -638,6 +710,45 @@ mod tests {
         );
     }
 
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct OptionalStruct {
+        id: u32,
+        rest: Option<String>,
+    }
+
+    #[test]
+    fn test_parse_struct_with_present_optional_field() {
+        let raw_params = vec![("id", "1"), ("rest", "a/b")];
+        let url_params = create_url_params(&raw_params);
+        assert_eq!(
+            OptionalStruct::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            OptionalStruct {
+                id: 1,
+                rest: Some("a/b".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_with_absent_optional_field() {
+        let raw_params = vec![("id", "1")];
+        let url_params = create_url_params(&raw_params);
+        assert_eq!(
+            OptionalStruct::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            OptionalStruct { id: 1, rest: None }
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_with_empty_optional_field() {
+        let raw_params = vec![("id", "1"), ("rest", "")];
+        let url_params = create_url_params(&raw_params);
+        assert_eq!(
+            OptionalStruct::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            OptionalStruct { id: 1, rest: None }
+        );
+    }
+
     macro_rules! test_parse_error {
         (
             $params:expr,
@@ -815,6 +926,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_out_of_range_error_at_key() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Params {
+            amount: i32,
+        }
+        test_parse_error!(
+            vec![("amount", "2147483648")],
+            Params,
+            ErrorKind::OutOfRangeAtKey {
+                key: "amount".to_owned(),
+                value: "2147483648".to_owned(),
+                expected_type: "i32",
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_float_error_at_key() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Params {
+            amount: f64,
+        }
+        test_parse_error!(
+            vec![("amount", "ten")],
+            Params,
+            ErrorKind::InvalidFloatAtKey {
+                key: "amount".to_owned(),
+                value: "ten".to_owned(),
+                expected_type: "f64",
+            }
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn comma_separated_point<'de, D>(deserializer: D) -> Result<Point, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = Cow::<'de, str>::deserialize(deserializer)?;
+        raw.split_once(',')
+            .and_then(|(x, y)| {
+                Some(Point {
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| {
+                de::Error::invalid_value(de::Unexpected::Str(&raw), &"two comma-separated integers")
+            })
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Location {
+        #[serde(deserialize_with = "comma_separated_point")]
+        point: Point,
+        name: String,
+    }
+
+    #[test]
+    fn test_deserialize_with() {
+        let raw_params = vec![("point", "3%2C-4"), ("name", "home")];
+        let url_params = create_url_params(&raw_params);
+        let deserializer = PathDeserializer::new(&url_params);
+        assert_eq!(
+            Location::deserialize(deserializer).unwrap(),
+            Location {
+                point: Point { x: 3, y: -4 },
+                name: "home".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_with_invalid_value_error_at_key() {
+        test_parse_error!(
+            vec![("point", "3"), ("name", "home")],
+            Location,
+            ErrorKind::InvalidValueAtKey {
+                key: "point".to_owned(),
+                value: "3".to_owned(),
+                expected: "two comma-separated integers".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_signed_and_floating_point_values() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Params {
+            delta: i64,
+            amount: f64,
+        }
+        let raw_params = vec![("delta", "-42"), ("amount", "-3.5")];
+        let url_params = create_url_params(&raw_params);
+        assert_eq!(
+            Params::deserialize(PathDeserializer::new(&url_params)).unwrap(),
+            Params {
+                delta: -42,
+                amount: -3.5,
+            }
+        );
+    }
+
     #[test]
     fn test_unsupported_type_error_nested_data_structure() {
         test_parse_error!(
@@ -1,6 +1,7 @@
 //! Errors that can happen when extracting route parameters.
 use std::str::Utf8Error;
 
+use crate::request::ParamsErrorStatus;
 use crate::response::Response;
 
 /// The error returned by [`PathParams::extract`] when the extraction fails.
@@ -88,14 +89,35 @@ impl ExtractPathParamsError {
     ///
     /// [`PathParams<T>`]: struct@crate::request::path::PathParams
     pub fn into_response(&self) -> Response {
+        self.into_response_with(&ParamsErrorStatus::default())
+    }
+
+    /// Convert an [`ExtractPathParamsError`] into an HTTP response, using the status codes
+    /// specified in [`ParamsErrorStatus`].
+    ///
+    /// Invalid UTF-8 sequences are treated as malformed input, while values that can't be
+    /// parsed into the expected type are treated as type mismatches.  
+    /// Programmer errors (e.g. `T` in [`PathParams<T>`] is an unsupported type) are still
+    /// converted into a `500 Internal Server Error`.
+    ///
+    /// Check out [`ParamsErrorStatus`] for more details on how to register this error handler.
+    ///
+    /// [`PathParams<T>`]: struct@crate::request::path::PathParams
+    pub fn into_response_with(&self, status: &ParamsErrorStatus) -> Response {
         match self {
             ExtractPathParamsError::InvalidUtf8InPathParameter(e) => {
-                Response::bad_request().set_typed_body(format!("Invalid URL.\n{}", e))
+                Response::new(status.for_malformed()).set_typed_body(format!("Invalid URL.\n{}", e))
             }
             ExtractPathParamsError::PathDeserializationError(e) => match e.kind {
-                ErrorKind::ParseErrorAtKey { .. } | ErrorKind::ParseError { .. } => {
-                    Response::bad_request().set_typed_body(format!("Invalid URL.\n{}", e.kind))
-                }
+                ErrorKind::ParseErrorAtKey { .. }
+                | ErrorKind::ParseError { .. }
+                | ErrorKind::OutOfRangeAtKey { .. }
+                | ErrorKind::OutOfRange { .. }
+                | ErrorKind::InvalidFloatAtKey { .. }
+                | ErrorKind::InvalidFloat { .. }
+                | ErrorKind::InvalidValueAtKey { .. }
+                | ErrorKind::InvalidValue { .. } => Response::new(status.for_type_mismatch())
+                    .set_typed_body(format!("Invalid URL.\n{}", e.kind)),
                 // We put the "custom" message variant here as well because it's not clear
                 // whether it's a programmer error or not. We err on the side of safety and
                 // prefer to return a 500 with an opaque error message.
@@ -146,6 +168,17 @@ impl serde::de::Error for PathDeserializationError {
             kind: ErrorKind::Message(msg.to_string()),
         }
     }
+
+    fn invalid_value(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Self {
+        let value = match unexp {
+            serde::de::Unexpected::Str(s) => s.to_owned(),
+            unexp => unexp.to_string(),
+        };
+        Self::new(ErrorKind::InvalidValue {
+            value,
+            expected: exp.to_string(),
+        })
+    }
 }
 
 impl std::fmt::Display for PathDeserializationError {
@@ -188,6 +221,75 @@ pub enum ErrorKind {
         expected_type: &'static str,
     },
 
+    /// The value at a specific key is a valid integer, but it doesn't fit into the
+    /// expected integer type (e.g. `2147483648` for an `i32` field).
+    ///
+    /// This variant is used when deserializing into types that have named fields, such as structs.
+    OutOfRangeAtKey {
+        /// The key at which the value was located.
+        key: String,
+        /// The value from the URI.
+        value: String,
+        /// The expected type of the value.
+        expected_type: &'static str,
+    },
+
+    /// The value is a valid integer, but it doesn't fit into the expected integer type.
+    ///
+    /// This variant is used when deserializing into a primitive type (such as `u32`).
+    OutOfRange {
+        /// The value from the URI.
+        value: String,
+        /// The expected type of the value.
+        expected_type: &'static str,
+    },
+
+    /// Failed to parse the value at a specific key as a floating point number.
+    ///
+    /// This variant is used when deserializing into types that have named fields, such as structs.
+    InvalidFloatAtKey {
+        /// The key at which the value was located.
+        key: String,
+        /// The value from the URI.
+        value: String,
+        /// The expected type of the value.
+        expected_type: &'static str,
+    },
+
+    /// Failed to parse a value as a floating point number.
+    ///
+    /// This variant is used when deserializing into a primitive type (such as `f64`).
+    InvalidFloat {
+        /// The value from the URI.
+        value: String,
+        /// The expected type of the value.
+        expected_type: &'static str,
+    },
+
+    /// The value at a specific key was rejected by the deserialization logic of the
+    /// expected type—e.g. a custom deserializer specified via `#[serde(deserialize_with = "..")]`
+    /// returned an error built with [`serde::de::Error::invalid_value`].
+    ///
+    /// This variant is used when deserializing into types that have named fields, such as structs.
+    InvalidValueAtKey {
+        /// The key at which the value was located.
+        key: String,
+        /// The value from the URI.
+        value: String,
+        /// A description of what was expected instead.
+        expected: String,
+    },
+
+    /// A value was rejected by the deserialization logic of the expected type.
+    ///
+    /// This variant is used when deserializing into a primitive type (such as `u32`).
+    InvalidValue {
+        /// The value from the URI.
+        value: String,
+        /// A description of what was expected instead.
+        expected: String,
+    },
+
     /// Tried to serialize into an unsupported type such as collections, tuples or nested maps.
     ///
     /// This error kind is caused by programmer errors and thus gets converted into a `500 Internal
@@ -225,6 +327,112 @@ impl std::fmt::Display for ErrorKind {
                 value,
                 expected_type,
             } => write!(f, "We can't parse `{value}` as a `{expected_type}`"),
+            ErrorKind::OutOfRangeAtKey {
+                key,
+                value,
+                expected_type,
+            } => write!(
+                f,
+                "`{key}` is set to `{value}`, which is out of the range of values \
+                that a `{expected_type}` can represent"
+            ),
+            ErrorKind::OutOfRange {
+                value,
+                expected_type,
+            } => write!(
+                f,
+                "`{value}` is out of the range of values that a `{expected_type}` can represent"
+            ),
+            ErrorKind::InvalidFloatAtKey {
+                key,
+                value,
+                expected_type,
+            } => write!(
+                f,
+                "`{key}` is set to `{value}`, which is not a valid floating point number \
+                (`{expected_type}`)"
+            ),
+            ErrorKind::InvalidFloat {
+                value,
+                expected_type,
+            } => write!(
+                f,
+                "`{value}` is not a valid floating point number (`{expected_type}`)"
+            ),
+            ErrorKind::InvalidValueAtKey {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "`{key}` is set to `{value}`, which is not a valid value. Expected {expected}"
+            ),
+            ErrorKind::InvalidValue { value, expected } => {
+                write!(f, "`{value}` is not a valid value. Expected {expected}")
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+
+    fn type_mismatch() -> ExtractPathParamsError {
+        ExtractPathParamsError::PathDeserializationError(PathDeserializationError::new(
+            ErrorKind::ParseErrorAtKey {
+                key: "home_id".into(),
+                value: "abc".into(),
+                expected_type: "u32",
+            },
+        ))
+    }
+
+    fn malformed() -> ExtractPathParamsError {
+        let source = std::str::from_utf8(&[0xDE, 0x7E, 0xC7]).unwrap_err();
+        ExtractPathParamsError::InvalidUtf8InPathParameter(InvalidUtf8InPathParam {
+            invalid_key: "home_id".into(),
+            invalid_raw_segment: "%DE~%C7".into(),
+            source,
+        })
+    }
+
+    #[test]
+    fn default_mapping_returns_bad_request() {
+        assert_eq!(
+            type_mismatch().into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            malformed().into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn custom_mapping_distinguishes_type_mismatches_from_malformed_input() {
+        let status = ParamsErrorStatus::default().type_mismatch(StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            type_mismatch().into_response_with(&status).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            malformed().into_response_with(&status).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn programmer_errors_are_not_remapped() {
+        let error = ExtractPathParamsError::PathDeserializationError(
+            PathDeserializationError::unsupported_type("Vec<u32>"),
+        );
+        let status = ParamsErrorStatus::default().type_mismatch(StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            error.into_response_with(&status).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -31,7 +31,38 @@ use std::fmt::Formatter;
 /// It lets you strip away the dynamic parts of the request path, thus reducing the cardinality of
 /// your metrics and making it easier to aggregate them.
 ///
+/// # Middlewares
+///
+/// [`MatchedPathPattern`] is available to middlewares as well as request handlers.
+/// Routing happens before any middleware is invoked, therefore the matched route is already
+/// known when your middleware starts executing—e.g. to make authorization decisions
+/// or to tag telemetry data before calling `next`.
+///
+/// ```rust
+/// use std::future::IntoFuture;
+/// use pavex::middleware::Next;
+/// use pavex::request::path::MatchedPathPattern;
+/// use pavex::response::Response;
+///
+/// pub async fn log_route<C>(route: MatchedPathPattern, next: Next<C>) -> Response
+/// where
+///     C: IntoFuture<Output = Response>,
+/// {
+///     println!("Processing a request for {route}");
+///     next.await
+/// }
+/// ```
+///
+/// The matched route is **read-only**: a middleware can't swap the request handler that's
+/// going to be invoked, since Pavex resolves (and type-checks) each route's dependency graph
+/// at compile-time.
+/// If you need to serve a request using another route, use a [`Dispatcher`] to re-dispatch it:
+/// the internal request goes through routing again and it is processed by the middlewares
+/// and the request handler of the route it matches, using fresh request-scoped components.
+/// When a request is not matched by any route, [`MatchedPathPattern`] is set to `*`.
+///
 /// [`Blueprint`]: crate::blueprint::Blueprint
+/// [`Dispatcher`]: crate::router::Dispatcher
 #[doc(alias("MatchedPath"))]
 #[doc(alias("MatchedPathTemplate"))]
 #[doc(alias("PathPattern"))]
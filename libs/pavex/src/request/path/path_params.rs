@@ -14,8 +14,10 @@ use super::RawPathParams;
 ///
 /// - [Example](#example)
 /// - [Supported types](#supported-types)
+/// - [Optional route parameters](#optional-route-parameters)
 /// - [Unsupported types](#unsupported-types)
 /// - [Additional compile-time checks](#additional-compile-time-checks)
+/// - [Custom deserializers](#custom-deserializers)
 /// - [Avoiding allocations](#avoiding-allocations)
 /// - [Working with raw route parameters](#working-with-raw-route-parameters)
 ///
@@ -92,6 +94,46 @@ use super::RawPathParams;
 /// }
 /// ```
 ///
+/// # Optional route parameters
+///
+/// A field of type `Option<T>` is set to `None` if the corresponding route parameter
+/// is absent, rather than failing the extraction.
+/// There are two ways for a route parameter to be absent:
+///
+/// - the route that matched the incoming request doesn't have it in its path template.
+///   This comes up when the same request handler is registered for multiple routes;
+/// - it is a catch-all parameter (e.g. `*rest`) that captured an empty string.
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{router::GET, Blueprint};
+/// use pavex::request::path::PathParams;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     // [...]
+///     bp.route(GET, "/files", f!(crate::get_file));
+///     bp.route(GET, "/files/*path", f!(crate::get_file));
+///     bp
+/// }
+///
+/// #[PathParams]
+/// pub struct File {
+///     // `None` for `/files`, `Some` for `/files/a/b.txt`.
+///     path: Option<String>,
+/// }
+///
+/// pub fn get_file(params: &PathParams<File>) -> String {
+///     match &params.0.path {
+///         Some(path) => format!("Serving {path}"),
+///         None => "Listing all files".into(),
+///     }
+/// }
+/// ```
+///
+/// Pavex won't complain, at compile-time, if an `Option<T>` field doesn't appear in
+/// the path template of one of the routes.
+///
 /// # Unsupported types
 ///
 /// Pavex wants to enable local reasoning: it should be easy to understand what
@@ -165,7 +207,7 @@ use super::RawPathParams;
 /// In particular, Pavex becomes able to:
 ///
 /// - verify that for each field in the struct there is a corresponding route parameter
-///   in the route's path.
+///   in the route's path (unless the field is [optional](#optional-route-parameters)).
 /// - detect the usage of common unsupported types as fields, e.g. vectors, tuples.
 /// - detect common errors that might result in a runtime error, e.g. using `&str` as a field type
 ///   instead of `Cow<'_, str>` (see [`Avoiding allocations`](#avoiding-allocations)).
@@ -174,6 +216,63 @@ use super::RawPathParams;
 /// to know more about the role played by the [`PathParams`](macro@crate::request::path::PathParams)
 /// macro in enabling these additional compile-time checks.
 ///
+/// # Custom deserializers
+///
+/// Some route parameters need a non-standard parsing logic—e.g. a comma-separated pair
+/// of coordinates that should be deserialized into a `Point`.
+/// `PathParams` honours `serde`'s field-level hooks, such as
+/// `#[serde(deserialize_with = "...")]`.
+///
+/// The [`PathParams`](macro@crate::request::path::PathParams) macro doesn't accept
+/// `serde` attributes: you must derive [`serde::Deserialize`] directly.
+///
+/// ```rust
+/// use pavex::request::path::PathParams;
+/// use serde::{de::Error, Deserialize, Deserializer};
+/// use std::borrow::Cow;
+///
+/// pub struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn comma_separated<'de, D>(deserializer: D) -> Result<Point, D::Error>
+/// where
+///     D: Deserializer<'de>,
+/// {
+///     let raw = Cow::<'de, str>::deserialize(deserializer)?;
+///     let invalid = || Error::invalid_value(
+///         serde::de::Unexpected::Str(&raw),
+///         &"two comma-separated integers",
+///     );
+///     let (x, y) = raw.split_once(',').ok_or_else(invalid)?;
+///     Ok(Point {
+///         x: x.parse().map_err(|_| invalid())?,
+///         y: y.parse().map_err(|_| invalid())?,
+///     })
+/// }
+///
+/// #[derive(Deserialize)]
+/// pub struct Tile {
+///     // Deserializes `3,-4` into `Point { x: 3, y: -4 }`
+///     #[serde(deserialize_with = "comma_separated")]
+///     origin: Point,
+/// }
+///
+/// pub fn get_tile(params: &PathParams<Tile>) -> String {
+///     let origin = &params.0.origin;
+///     format!("The tile starts at ({}, {})", origin.x, origin.y)
+/// }
+/// ```
+///
+/// Use [`serde::de::Error::invalid_value`] to reject a malformed value: it is reported as
+/// [`ErrorKind::InvalidValueAtKey`] and the default error handler returns
+/// a `400 Bad Request`.
+/// Errors built with [`serde::de::Error::custom`], instead, are treated as
+/// programmer errors and result in a `500 Internal Server Error`.
+///
+/// [`ErrorKind::InvalidValueAtKey`]: crate::request::path::errors::ErrorKind::InvalidValueAtKey
+///
 /// # Avoiding allocations
 ///
 /// If you want to squeeze out the last bit of performance from your application, you can try to
@@ -1,5 +1,6 @@
 //! Errors that can happen when extracting query parameters.
 
+use crate::request::ParamsErrorStatus;
 use crate::response::Response;
 
 /// The error returned by [`QueryParams::extract`] when the extraction fails.
@@ -16,6 +17,9 @@ pub enum ExtractQueryParamsError {
     #[error(transparent)]
     /// See [`QueryDeserializationError`] for details.
     QueryDeserializationError(QueryDeserializationError),
+    #[error(transparent)]
+    /// See [`TooManyQueryFields`] for details.
+    TooManyFields(TooManyQueryFields),
 }
 
 impl ExtractQueryParamsError {
@@ -23,13 +27,48 @@ impl ExtractQueryParamsError {
     ///
     /// It returns a `400 Bad Request` to the caller.
     pub fn into_response(&self) -> Response {
+        self.into_response_with(&ParamsErrorStatus::default())
+    }
+
+    /// Convert an [`ExtractQueryParamsError`] into an HTTP response, using the status codes
+    /// specified in [`ParamsErrorStatus`].
+    ///
+    /// Check out [`ParamsErrorStatus`] for more details on how failures are categorized
+    /// and how to register this error handler.
+    ///
+    /// A query string with [too many fields](TooManyQueryFields) is always rejected
+    /// with a `400 Bad Request`, regardless of the configured status codes.
+    pub fn into_response_with(&self, status: &ParamsErrorStatus) -> Response {
         match self {
-            Self::QueryDeserializationError(e) => Response::bad_request()
-                .set_typed_body(format!("Invalid query parameters.\n{:?}", e)),
+            Self::QueryDeserializationError(e) => {
+                let status = if e.is_missing_field() {
+                    status.for_missing_field()
+                } else if e.is_type_mismatch() {
+                    status.for_type_mismatch()
+                } else {
+                    status.for_malformed()
+                };
+                Response::new(status).set_typed_body(format!("Invalid query parameters.\n{:?}", e))
+            }
+            Self::TooManyFields(e) => Response::bad_request().set_typed_body(e.to_string()),
         }
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The query string contains more than {max_n_fields} fields, the maximum number of query parameters accepted by this server."
+)]
+#[non_exhaustive]
+/// The query string contains more fields than the limit enforced by this server.
+///
+/// Pavex rejects the request before attempting the deserialization.
+/// Check out [`QueryFieldLimit`](crate::request::query::QueryFieldLimit) for more details.
+pub struct TooManyQueryFields {
+    /// The maximum number of fields accepted by this server.
+    pub max_n_fields: usize,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 /// Something went wrong when trying to deserialize the percent-decoded query parameters into
@@ -44,4 +83,163 @@ impl QueryDeserializationError {
     pub(super) fn new(e: serde_html_form::de::Error) -> Self {
         Self { inner: e }
     }
+
+    // `serde_html_form` doesn't expose structured error details: we have to rely on the
+    // error messages produced by `serde` and by the `FromStr` implementations of primitive types.
+    // Each prefix is pinned by a test case in the `tests` module below.
+    fn is_missing_field(&self) -> bool {
+        self.inner.to_string().starts_with("missing field")
+    }
+
+    fn is_type_mismatch(&self) -> bool {
+        const PREFIXES: [&str; 10] = [
+            "invalid type",
+            "invalid value",
+            "invalid length",
+            "unknown variant",
+            "invalid digit",
+            "cannot parse integer",
+            "number too large",
+            "number too small",
+            "invalid float literal",
+            "provided string was not `true` or `false`",
+        ];
+        let message = self.inner.to_string();
+        PREFIXES.iter().any(|prefix| message.starts_with(prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde::de::DeserializeOwned;
+
+    use super::{ExtractQueryParamsError, QueryDeserializationError};
+    use crate::request::ParamsErrorStatus;
+
+    /// Deserialize `query` into `T` and return the status code of the resulting error.
+    ///
+    /// Each category gets its own status code, so that we can tell them apart.
+    fn status_for<T: DeserializeOwned + std::fmt::Debug>(query: &str) -> StatusCode {
+        let status = ParamsErrorStatus::default()
+            .malformed(StatusCode::BAD_REQUEST)
+            .type_mismatch(StatusCode::UNPROCESSABLE_ENTITY)
+            .missing_field(StatusCode::NOT_FOUND);
+        let e = serde_html_form::from_str::<T>(query).unwrap_err();
+        ExtractQueryParamsError::QueryDeserializationError(QueryDeserializationError::new(e))
+            .into_response_with(&status)
+            .status()
+    }
+
+    // The classification relies on the wording of the error messages emitted by `serde`
+    // and by the `FromStr` implementations in `core`.
+    // Each prefix is pinned by one of the cases below: if the wording changes,
+    // these tests fail rather than silently moving errors to another category.
+
+    #[test]
+    fn missing_fields_are_detected() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Q {
+            page: u32,
+            size: u32,
+        }
+        assert_eq!(status_for::<Q>("page=1"), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn type_mismatches_are_detected() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Integer {
+            n: u8,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Signed {
+            n: i8,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Float {
+            n: f64,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Flag {
+            flag: bool,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Letter {
+            c: char,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(rename_all = "lowercase")]
+        enum Order {
+            Asc,
+            Desc,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Sorting {
+            order: Order,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Inner {
+            n: u32,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Nested {
+            inner: Inner,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Pair {
+            pair: (u32, u32),
+        }
+
+        let cases = [
+            // "invalid digit"
+            ("n=abc", status_for::<Integer>("n=abc")),
+            ("n=-1", status_for::<Integer>("n=-1")),
+            // "cannot parse integer"
+            ("n=", status_for::<Integer>("n=")),
+            // "number too large"
+            ("n=300", status_for::<Integer>("n=300")),
+            // "number too small"
+            ("n=-300", status_for::<Signed>("n=-300")),
+            // "invalid float literal"
+            ("n=abc", status_for::<Float>("n=abc")),
+            // "provided string was not `true` or `false`"
+            ("flag=yes", status_for::<Flag>("flag=yes")),
+            // "invalid value"
+            ("c=ab", status_for::<Letter>("c=ab")),
+            // "unknown variant"
+            ("order=random", status_for::<Sorting>("order=random")),
+            // "invalid type"
+            ("inner=1", status_for::<Nested>("inner=1")),
+            // "invalid length"
+            ("pair=1", status_for::<Pair>("pair=1")),
+        ];
+        for (query, status) in cases {
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{query}");
+        }
+    }
+
+    #[test]
+    fn custom_rejections_are_treated_as_malformed_input() {
+        fn reject<'de, D: serde::Deserializer<'de>>(_: D) -> Result<u32, D::Error> {
+            Err(serde::de::Error::custom("this value is not accepted"))
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Q {
+            #[serde(deserialize_with = "reject")]
+            n: u32,
+        }
+        assert_eq!(status_for::<Q>("n=1"), StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,33 @@
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+
+#[derive(Debug, Clone, Copy)]
+/// An upper limit on the number of fields in the query string of incoming requests.
+///
+/// Check out the documentation of [`QueryParams`](crate::request::query::QueryParams) for more details.
+pub enum QueryFieldLimit {
+    /// There is an active limit on the number of fields in the query string.
+    Enabled {
+        /// The maximum number of `key=value` pairs in the query string.
+        max_n_fields: usize,
+    },
+    /// There is no limit on the number of fields in the query string.
+    Disabled,
+}
+
+impl QueryFieldLimit {
+    /// Register the [default constructor](QueryFieldLimit::default) for [`QueryFieldLimit`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::request::query::QueryFieldLimit as std::default::Default>::default),
+            Lifecycle::RequestScoped,
+        )
+    }
+}
+
+impl Default for QueryFieldLimit {
+    fn default() -> Self {
+        Self::Enabled { max_n_fields: 1000 }
+    }
+}
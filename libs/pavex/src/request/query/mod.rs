@@ -1,6 +1,8 @@
 //! Extract data from the query parameters of incoming requests.
 
 pub mod errors;
+mod limit;
 mod query_params;
 
+pub use limit::QueryFieldLimit;
 pub use query_params::QueryParams;
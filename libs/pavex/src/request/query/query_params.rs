@@ -3,7 +3,8 @@ use crate::blueprint::Blueprint;
 use crate::f;
 use crate::request::RequestHead;
 
-use super::errors::{ExtractQueryParamsError, QueryDeserializationError};
+use super::errors::{ExtractQueryParamsError, QueryDeserializationError, TooManyQueryFields};
+use super::QueryFieldLimit;
 
 /// Extract (typed) route parameters from the query parameters of an incoming request.
 ///
@@ -13,8 +14,10 @@ use super::errors::{ExtractQueryParamsError, QueryDeserializationError};
 /// - [Installation](#installtion)
 /// - [Supported types](#supported-types)
 ///   - [Sequences](#sequences)
+///   - [Custom deserializers](#custom-deserializers)
 /// - [Unsupported types](#unsupported-types)
 /// - [Avoiding allocations](#avoiding-allocations)
+/// - [Field count limit](#field-count-limit)
 ///
 /// # Example
 ///
@@ -101,6 +104,36 @@ use super::errors::{ExtractQueryParamsError, QueryDeserializationError};
 /// }
 /// ```
 ///
+/// ## Custom deserializers
+///
+/// `QueryParams` honours `serde`'s field-level hooks, such as
+/// `#[serde(deserialize_with = "...")]`.
+/// You can use them for query parameters that require a non-standard parsing logic:
+///
+/// ```rust
+/// use pavex::request::query::QueryParams;
+/// use serde::{Deserialize, Deserializer};
+/// use std::borrow::Cow;
+///
+/// fn comma_separated<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+/// where
+///     D: Deserializer<'de>,
+/// {
+///     let raw = Cow::<'de, str>::deserialize(deserializer)?;
+///     Ok(raw.split(',').map(ToOwned::to_owned).collect())
+/// }
+///
+/// #[derive(Deserialize)]
+/// pub struct Search {
+///     // Converts `?tags=rust,web` into `vec!["rust", "web"]`.
+///     #[serde(deserialize_with = "comma_separated")]
+///     tags: Vec<String>,
+/// }
+/// ```
+///
+/// If your custom deserializer fails, the extraction fails too and the default error
+/// handler returns a `400 Bad Request`.
+///
 /// # Unsupported types
 ///
 /// Pavex does not support the following types as `T` in `PathParams<T>`:
@@ -162,6 +195,90 @@ use super::errors::{ExtractQueryParamsError, QueryDeserializationError};
 ///    format!("The payee's name is {}", params.0.name)
 /// }
 /// ```
+///
+/// # Field count limit
+///
+/// To prevent denial-of-service attacks, Pavex enforces an upper limit on the number of
+/// fields (i.e. `key=value` pairs) in the query string. The default limit is 1000 fields.  
+/// Fields are counted before attempting the deserialization: a query string that breaches
+/// the limit is rejected with the [`TooManyFields`](ExtractQueryParamsError::TooManyFields)
+/// error variant, and the default error handler returns a `400 Bad Request`.
+///
+/// [`QueryParams::register`] always enforces the [default limit](QueryFieldLimit::default).
+/// If you want to customize it, register [`QueryParams::extract_with_limit`] as constructor
+/// alongside a constructor for [`QueryFieldLimit`]:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{Blueprint, constructor::Lifecycle};
+/// use pavex::request::query::QueryFieldLimit;
+///
+/// pub fn query_field_limit() -> QueryFieldLimit {
+///     QueryFieldLimit::Enabled {
+///         max_n_fields: 50
+///     }
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(
+///         f!(pavex::request::query::QueryParams::extract_with_limit),
+///         Lifecycle::RequestScoped,
+///     )
+///     .error_handler(f!(
+///         pavex::request::query::errors::ExtractQueryParamsError::into_response
+///     ));
+///     // Register a custom constructor for `QueryFieldLimit`.
+///     bp.constructor(f!(crate::query_field_limit), Lifecycle::RequestScoped);
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// Use [`QueryFieldLimit::Disabled`] to disable the limit entirely.
+///
+/// You can leverage nesting if you want a different limit for a subset of your routes:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{Blueprint, constructor::Lifecycle, router::GET};
+/// use pavex::request::query::QueryFieldLimit;
+/// # pub fn home() -> String { todo!() }
+/// # pub fn search() -> String { todo!() }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(
+///         f!(pavex::request::query::QueryParams::extract_with_limit),
+///         Lifecycle::RequestScoped,
+///     )
+///     .error_handler(f!(
+///         pavex::request::query::errors::ExtractQueryParamsError::into_response
+///     ));
+///     QueryFieldLimit::register(&mut bp);
+///     bp.route(GET, "/", f!(crate::home));
+///     bp.nest(search_bp());
+///     // [...]
+///     bp
+/// }
+///
+/// fn search_bp() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     // This limit will only apply to the routes registered
+///     // in this nested blueprint.
+///     bp.constructor(f!(crate::search_field_limit), Lifecycle::RequestScoped);
+///     bp.route(GET, "/search", f!(crate::search));
+///     bp
+/// }
+///
+/// pub fn search_field_limit() -> QueryFieldLimit {
+///     QueryFieldLimit::Enabled {
+///         max_n_fields: 10_000
+///     }
+/// }
+/// ```
+///
+/// Check out `Blueprint::nest` and `Blueprint::nest_at` for more details on nesting.
 #[doc(alias = "Query")]
 pub struct QueryParams<T>(
     /// The extracted query parameters, deserialized into `T`, the type you specified.
@@ -173,14 +290,35 @@ impl<T> QueryParams<T> {
     ///
     /// If the extraction fails, an [`ExtractQueryParamsError`] is returned.
     ///
+    /// It enforces the [default field count limit](QueryFieldLimit::default).
+    ///
     /// Check out [`QueryParams`] for more information on query parameters.
     pub fn extract<'request>(
         request_head: &'request RequestHead,
     ) -> Result<Self, ExtractQueryParamsError>
+    where
+        T: serde::Deserialize<'request>,
+    {
+        Self::extract_with_limit(request_head, QueryFieldLimit::default())
+    }
+
+    /// An alternative constructor for [`QueryParams`], with a configurable limit on
+    /// the number of fields in the query string.
+    ///
+    /// If the extraction fails, an [`ExtractQueryParamsError`] is returned.
+    ///
+    /// Check out [`QueryParams`] for more information on the field count limit.
+    pub fn extract_with_limit<'request>(
+        request_head: &'request RequestHead,
+        field_limit: QueryFieldLimit,
+    ) -> Result<Self, ExtractQueryParamsError>
     where
         T: serde::Deserialize<'request>,
     {
         let query = request_head.target.query().unwrap_or_default();
+        if let QueryFieldLimit::Enabled { max_n_fields } = field_limit {
+            check_field_count(query, max_n_fields)?;
+        }
         parse(query).map(QueryParams)
     }
 }
@@ -200,6 +338,21 @@ impl QueryParams<()> {
     }
 }
 
+/// Fail if the query string contains more than `max_n_fields` fields.
+///
+/// Empty segments (e.g. `a=1&&b=2`) are skipped when deserializing, therefore they are
+/// not counted.
+/// We stop scanning as soon as the limit is breached.
+fn check_field_count(s: &str, max_n_fields: usize) -> Result<(), ExtractQueryParamsError> {
+    let mut fields = s.split('&').filter(|field| !field.is_empty());
+    if fields.nth(max_n_fields).is_some() {
+        return Err(ExtractQueryParamsError::TooManyFields(TooManyQueryFields {
+            max_n_fields,
+        }));
+    }
+    Ok(())
+}
+
 /// Parse a query string into a `T`.
 fn parse<'a, T>(s: &'a str) -> Result<T, ExtractQueryParamsError>
 where
@@ -265,4 +418,149 @@ mod tests {
         let actual: Home = parse(query).unwrap();
         assert_eq!(expected, actual);
     }
+
+    fn comma_separated<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = <Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        raw.split(',')
+            .map(|id| id.parse().map_err(serde::de::Error::custom))
+            .collect()
+    }
+
+    #[test]
+    fn test_deserialize_with() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Home {
+            #[serde(deserialize_with = "comma_separated")]
+            room_ids: Vec<u32>,
+            home_id: u32,
+        }
+
+        let query = "room_ids=1%2C2%2C3&home_id=7";
+        let expected = Home {
+            room_ids: vec![1, 2, 3],
+            home_id: 7,
+        };
+        let actual: Home = parse(query).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_with_error() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Home {
+            #[serde(deserialize_with = "comma_separated")]
+            room_ids: Vec<u32>,
+        }
+
+        let err = parse::<Home>("room_ids=1,two").unwrap_err();
+        assert_eq!(
+            err.into_response().status(),
+            crate::http::StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Rooms {
+        room_id: Vec<u32>,
+    }
+
+    #[test]
+    fn test_field_count_at_limit() {
+        let head = RequestHead::for_tests().with_target("/homes?room_id=1&room_id=2&&room_id=3");
+        let limit = QueryFieldLimit::Enabled { max_n_fields: 3 };
+        let params = QueryParams::<Rooms>::extract_with_limit(&head, limit).unwrap();
+        assert_eq!(params.0.room_id, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_field_count_over_limit() {
+        let head =
+            RequestHead::for_tests().with_target("/homes?room_id=1&room_id=2&room_id=3&room_id=4");
+        let limit = QueryFieldLimit::Enabled { max_n_fields: 3 };
+        let err = QueryParams::<Rooms>::extract_with_limit(&head, limit).unwrap_err();
+        let ExtractQueryParamsError::TooManyFields(e) = &err else {
+            panic!("Expected a `TooManyFields` error, got {:?}", err);
+        };
+        assert_eq!(e.max_n_fields, 3);
+        assert_eq!(
+            err.into_response().status(),
+            crate::http::StatusCode::BAD_REQUEST
+        );
+
+        // The limit can be disabled.
+        let params =
+            QueryParams::<Rooms>::extract_with_limit(&head, QueryFieldLimit::Disabled).unwrap();
+        assert_eq!(params.0.room_id, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_default_field_count_limit() {
+        let at_limit = format!("/homes?{}", "room_id=1&".repeat(1000));
+        let params =
+            QueryParams::<Rooms>::extract(&RequestHead::for_tests().with_target(&at_limit))
+                .unwrap();
+        assert_eq!(params.0.room_id.len(), 1000);
+
+        let over_limit = format!("/homes?{}", "room_id=1&".repeat(1001));
+        let err = QueryParams::<Rooms>::extract(&RequestHead::for_tests().with_target(&over_limit))
+            .unwrap_err();
+        assert!(matches!(err, ExtractQueryParamsError::TooManyFields(_)));
+    }
+
+    #[test]
+    fn test_custom_error_status() {
+        use crate::http::StatusCode;
+        use crate::request::ParamsErrorStatus;
+
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Page {
+            number: u32,
+            size: u32,
+        }
+
+        fn malformed<'de, D>(_deserializer: D) -> Result<u32, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            Err(serde::de::Error::custom("unbalanced brackets"))
+        }
+
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Filter {
+            #[serde(deserialize_with = "malformed")]
+            range: u32,
+        }
+
+        let status = ParamsErrorStatus::default()
+            .type_mismatch(StatusCode::UNPROCESSABLE_ENTITY)
+            .missing_field(StatusCode::NOT_FOUND);
+
+        let type_mismatch = parse::<Page>("number=one&size=10").unwrap_err();
+        assert_eq!(
+            type_mismatch.into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            type_mismatch.into_response_with(&status).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let missing_field = parse::<Page>("number=1").unwrap_err();
+        assert_eq!(
+            missing_field.into_response_with(&status).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let malformed = parse::<Filter>("range=[1,2").unwrap_err();
+        assert_eq!(
+            malformed.into_response_with(&status).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
 }
@@ -0,0 +1,37 @@
+//! Errors that can happen when resolving the byte ranges requested by the caller.
+
+use http::header::{HeaderValue, CONTENT_RANGE};
+
+use crate::response::Response;
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "None of the requested byte ranges overlap with the selected representation, \
+    which is {complete_length} bytes long."
+)]
+#[non_exhaustive]
+/// None of the ranges listed in the `Range` header can be served.
+///
+/// It's returned by [`RangeHeader::satisfiable_ranges`] when all the requested ranges
+/// start after the end of the selected representation—e.g. `bytes=500-` for a 100-byte file.
+///
+/// [`RangeHeader::satisfiable_ranges`]: crate::request::range::RangeHeader::satisfiable_ranges
+pub struct RangeNotSatisfiable {
+    /// The length of the selected representation, in bytes.
+    pub complete_length: u64,
+}
+
+impl RangeNotSatisfiable {
+    /// Convert a [`RangeNotSatisfiable`] error into an HTTP response.
+    ///
+    /// It returns a `416 Range Not Satisfiable` to the caller, with a `Content-Range` header
+    /// that specifies the length of the selected representation—e.g. `bytes */1234`.
+    pub fn into_response(&self) -> Response {
+        let content_range = format!("bytes */{}", self.complete_length);
+        Response::range_not_satisfiable().insert_header(
+            CONTENT_RANGE,
+            // A number can always be converted into a header value.
+            HeaderValue::try_from(content_range).unwrap(),
+        )
+    }
+}
@@ -0,0 +1,8 @@
+//! Extract and resolve the byte ranges requested via the `Range` header.
+//!
+//! Check out [`RangeHeader`] for more details.
+
+pub mod errors;
+mod range_header;
+
+pub use range_header::{ByteRange, RangeHeader, SatisfiableRanges};
@@ -0,0 +1,498 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use http::header::{HeaderValue, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use http::Method;
+use http_body_util::Full;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::request::RequestHead;
+use crate::response::Response;
+
+use super::errors::RangeNotSatisfiable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The byte ranges requested by the caller via the `Range` header—e.g. `Range: bytes=0-499`.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Serving ranges](#serving-ranges)
+/// - [Invalid headers](#invalid-headers)
+///
+/// # Installation
+///
+/// Register the [constructor](RangeHeader::register) for `Option<RangeHeader>`:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::request::range::RangeHeader;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     RangeHeader::register(&mut bp);
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// You can then inject `Option<RangeHeader>` (or a reference to it) into your components.
+/// It's set to `None` if the caller is asking for the whole representation.
+///
+/// # Serving ranges
+///
+/// The requested ranges can only be validated once you know the length of the
+/// representation you want to serve.
+/// Use [`RangeHeader::satisfiable_ranges`] to resolve them and then build
+/// a `206 Partial Content` response via [`SatisfiableRanges::into_response`]:
+///
+/// ```rust
+/// use pavex::http::HeaderValue;
+/// use pavex::request::range::RangeHeader;
+/// use pavex::response::Response;
+///
+/// pub fn video(range: &Option<RangeHeader>) -> Response {
+///     let video: &'static [u8] = b"<binary data>";
+///     let content_type = HeaderValue::from_static("video/mp4");
+///     let Some(range) = range else {
+///         return Response::ok().set_typed_body(video);
+///     };
+///     match range.satisfiable_ranges(video.len() as u64) {
+///         Ok(ranges) => ranges.into_response(video, content_type),
+///         // A `416 Range Not Satisfiable` response.
+///         Err(e) => e.into_response(),
+///     }
+/// }
+/// ```
+///
+/// # Invalid headers
+///
+/// Pavex ignores the `Range` header, as allowed by
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-range), if:
+///
+/// - the request method is not `GET`;
+/// - the range unit is not `bytes`;
+/// - the header is malformed—e.g. `bytes=500-100`;
+/// - the header lists more than [`RangeHeader::MAX_RANGES`] ranges.
+///
+/// The whole representation should be served in all those cases.
+#[doc(alias = "Range")]
+#[doc(alias = "PartialContent")]
+pub struct RangeHeader {
+    ranges: Vec<RangeSpec>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeSpec {
+    /// `bytes=500-999`
+    Bounded { first: u64, last: u64 },
+    /// `bytes=500-`
+    From { first: u64 },
+    /// `bytes=-500`
+    Suffix { length: u64 },
+}
+
+impl RangeHeader {
+    /// The maximum number of ranges that can be listed in a `Range` header.
+    ///
+    /// Headers with more ranges are ignored: serving them would let a caller force the
+    /// server to assemble a response far larger than the representation itself.
+    pub const MAX_RANGES: usize = 32;
+
+    /// Register the [default constructor](RangeHeader::extract)
+    /// for `Option<RangeHeader>` with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::request::range::RangeHeader::extract),
+            Lifecycle::RequestScoped,
+        )
+    }
+
+    /// Extract the requested byte ranges from the `Range` header of an incoming request.
+    ///
+    /// It returns `None` if there is no `Range` header or if it must be ignored.
+    /// Check out the [type-level documentation](RangeHeader#invalid-headers) for more details.
+    pub fn extract(request_head: &RequestHead) -> Option<RangeHeader> {
+        if request_head.method != Method::GET {
+            return None;
+        }
+        let mut values = request_head.headers.get_all(RANGE).iter();
+        let value = values.next()?;
+        // Multiple `Range` headers can't be combined into a valid list.
+        if values.next().is_some() {
+            return None;
+        }
+        Self::parse(value.to_str().ok()?)
+    }
+
+    /// Parse the value of a `Range` header—e.g. `bytes=0-499, -500`.
+    ///
+    /// It returns `None` if the value is malformed, if the range unit is not `bytes` or
+    /// if more than [`RangeHeader::MAX_RANGES`] ranges are listed.
+    pub fn parse(value: &str) -> Option<RangeHeader> {
+        let (unit, ranges) = value.trim().split_once('=')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+        let ranges = ranges
+            .split(',')
+            .map(str::trim)
+            // Empty list elements are allowed and must be ignored.
+            .filter(|range| !range.is_empty())
+            .map(parse_range_spec)
+            .collect::<Option<Vec<_>>>()?;
+        if ranges.is_empty() || ranges.len() > Self::MAX_RANGES {
+            return None;
+        }
+        Some(Self { ranges })
+    }
+
+    /// Resolve the requested ranges against a representation that is `complete_length`
+    /// bytes long.
+    ///
+    /// Ranges that extend past the end of the representation are truncated, while ranges
+    /// that don't overlap with it are discarded.
+    /// Overlapping or adjacent ranges are merged, so that no byte is served twice.
+    /// If none of the requested ranges can be served, a [`RangeNotSatisfiable`] error
+    /// is returned.
+    pub fn satisfiable_ranges(
+        &self,
+        complete_length: u64,
+    ) -> Result<SatisfiableRanges, RangeNotSatisfiable> {
+        let mut resolved: Vec<_> = self
+            .ranges
+            .iter()
+            .filter_map(|spec| spec.resolve(complete_length))
+            .collect();
+        resolved.sort_unstable_by_key(|range| range.start);
+        let mut ranges: Vec<ByteRange> = Vec::with_capacity(resolved.len());
+        for range in resolved {
+            match ranges.last_mut() {
+                // `end + 1` can't overflow: `end` is smaller than `complete_length`.
+                Some(last) if range.start <= last.end + 1 => last.end = last.end.max(range.end),
+                _ => ranges.push(range),
+            }
+        }
+        if ranges.is_empty() {
+            return Err(RangeNotSatisfiable { complete_length });
+        }
+        Ok(SatisfiableRanges {
+            ranges,
+            complete_length,
+        })
+    }
+}
+
+impl RangeSpec {
+    fn resolve(&self, complete_length: u64) -> Option<ByteRange> {
+        let last_byte = complete_length.checked_sub(1)?;
+        match *self {
+            RangeSpec::Bounded { first, last } if first <= last_byte => Some(ByteRange {
+                start: first,
+                end: last.min(last_byte),
+            }),
+            RangeSpec::From { first } if first <= last_byte => Some(ByteRange {
+                start: first,
+                end: last_byte,
+            }),
+            RangeSpec::Suffix { length } if length > 0 => Some(ByteRange {
+                start: complete_length.saturating_sub(length),
+                end: last_byte,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn parse_range_spec(spec: &str) -> Option<RangeSpec> {
+    let (first, last) = spec.split_once('-')?;
+    let parse = |s: &str| -> Option<u64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse().ok()
+    };
+    match (first, last) {
+        ("", length) => Some(RangeSpec::Suffix {
+            length: parse(length)?,
+        }),
+        (first, "") => Some(RangeSpec::From {
+            first: parse(first)?,
+        }),
+        (first, last) => {
+            let (first, last) = (parse(first)?, parse(last)?);
+            (first <= last).then_some(RangeSpec::Bounded { first, last })
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A range of bytes that can be served to the caller.
+///
+/// Both ends are inclusive: `bytes=0-499` is represented as a range starting at `0`
+/// and ending at `499`.
+pub struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    /// The offset of the first byte in the range.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// The offset of the last byte in the range (inclusive).
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// The number of bytes in the range.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// The value of the `Content-Range` header for this range—e.g. `bytes 0-499/1234`.
+    pub fn content_range(&self, complete_length: u64) -> HeaderValue {
+        let value = format!("bytes {}-{}/{complete_length}", self.start, self.end);
+        // A sequence of numbers can always be converted into a header value.
+        HeaderValue::try_from(value).unwrap()
+    }
+
+    /// The slice of `data` that falls within this range.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the range is out of bounds for `data`.
+    pub fn slice<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        &data[self.start as usize..=self.end as usize]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The ranges that can be served to the caller, as returned by
+/// [`RangeHeader::satisfiable_ranges`].
+pub struct SatisfiableRanges {
+    ranges: Vec<ByteRange>,
+    complete_length: u64,
+}
+
+impl SatisfiableRanges {
+    /// The ranges to be served, sorted by their first byte.
+    ///
+    /// They never overlap: overlapping or adjacent ranges are merged.
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+
+    /// The length of the selected representation, in bytes.
+    pub fn complete_length(&self) -> u64 {
+        self.complete_length
+    }
+
+    /// Build a `206 Partial Content` response for the requested ranges of `data`.
+    ///
+    /// If a single range was requested, the body contains the requested bytes and
+    /// the `Content-Range` header describes the range.
+    ///
+    /// If multiple ranges were requested, the body is a `multipart/byteranges` document:
+    /// each part contains one of the requested ranges, alongside its own `Content-Type` and
+    /// `Content-Range` headers.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `data` is shorter than the
+    /// [length](SatisfiableRanges::complete_length) the ranges were resolved against.
+    pub fn into_response(self, data: &[u8], content_type: HeaderValue) -> Response {
+        if let [range] = self.ranges.as_slice() {
+            let body = Bytes::copy_from_slice(range.slice(data));
+            return Response::partial_content()
+                .set_raw_body(Full::new(body))
+                .insert_header(CONTENT_TYPE, content_type)
+                .insert_header(CONTENT_RANGE, range.content_range(self.complete_length));
+        }
+
+        let boundary = format!("{:016x}", rand::random::<u64>());
+        let mut body = BytesMut::new();
+        for range in &self.ranges {
+            body.put_slice(format!("\r\n--{boundary}\r\n").as_bytes());
+            body.put_slice(b"Content-Type: ");
+            body.put_slice(content_type.as_bytes());
+            body.put_slice(b"\r\nContent-Range: ");
+            body.put_slice(range.content_range(self.complete_length).as_bytes());
+            body.put_slice(b"\r\n\r\n");
+            body.put_slice(range.slice(data));
+        }
+        body.put_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        let multipart_content_type = format!("multipart/byteranges; boundary={boundary}");
+        Response::partial_content()
+            .set_raw_body(Full::new(body.freeze()))
+            .insert_header(
+                CONTENT_TYPE,
+                // The boundary is made of hex digits: it's always a valid header value.
+                HeaderValue::try_from(multipart_content_type).unwrap(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::{CONTENT_RANGE, CONTENT_TYPE};
+    use http::{HeaderValue, Method, StatusCode};
+    use http_body_util::BodyExt;
+
+    use super::{ByteRange, RangeHeader};
+    use crate::request::RequestHead;
+
+    const DATA: &[u8] = b"0123456789";
+
+    fn resolve(header: &str, complete_length: u64) -> Option<Vec<(u64, u64)>> {
+        let ranges = RangeHeader::parse(header)
+            .unwrap()
+            .satisfiable_ranges(complete_length)
+            .ok()?;
+        Some(ranges.ranges().iter().map(|r| (r.start, r.end)).collect())
+    }
+
+    #[test]
+    fn single_range() {
+        assert_eq!(resolve("bytes=0-4", 10), Some(vec![(0, 4)]));
+        assert_eq!(resolve("bytes=5-", 10), Some(vec![(5, 9)]));
+        // The last position is truncated to the end of the representation.
+        assert_eq!(resolve("bytes=5-100", 10), Some(vec![(5, 9)]));
+    }
+
+    #[test]
+    fn suffix_range() {
+        assert_eq!(resolve("bytes=-3", 10), Some(vec![(7, 9)]));
+        // The whole representation, if the suffix is longer than the representation itself.
+        assert_eq!(resolve("bytes=-100", 10), Some(vec![(0, 9)]));
+    }
+
+    #[test]
+    fn multiple_ranges() {
+        assert_eq!(
+            resolve("bytes=0-1, 4-5,, -2", 10),
+            Some(vec![(0, 1), (4, 5), (8, 9)])
+        );
+        // Unsatisfiable ranges are discarded.
+        assert_eq!(resolve("bytes=0-1, 20-30", 10), Some(vec![(0, 1)]));
+        // Ranges are sorted by their first byte.
+        assert_eq!(resolve("bytes=-2, 0-1", 10), Some(vec![(0, 1), (8, 9)]));
+    }
+
+    #[test]
+    fn overlapping_and_adjacent_ranges_are_merged() {
+        assert_eq!(resolve("bytes=0-4, 2-6", 10), Some(vec![(0, 6)]));
+        assert_eq!(resolve("bytes=0-4, 5-6", 10), Some(vec![(0, 6)]));
+        assert_eq!(resolve("bytes=4-5, 0-9", 10), Some(vec![(0, 9)]));
+        assert_eq!(resolve("bytes=0-1, 3-4, 1-3", 10), Some(vec![(0, 4)]));
+        // The whole representation is served once, no matter how many times it's requested.
+        assert_eq!(resolve("bytes=0-, 0-, -100, 0-", 10), Some(vec![(0, 9)]));
+    }
+
+    #[test]
+    fn headers_with_too_many_ranges_are_ignored() {
+        let header = |spec: &str, n: usize| format!("bytes={}", vec![spec; n].join(","));
+        assert!(RangeHeader::parse(&header("0-", RangeHeader::MAX_RANGES)).is_some());
+        assert_eq!(
+            RangeHeader::parse(&header("0-", RangeHeader::MAX_RANGES + 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_range() {
+        assert_eq!(resolve("bytes=10-", 10), None);
+        assert_eq!(resolve("bytes=-0", 10), None);
+        assert_eq!(resolve("bytes=0-5", 0), None);
+
+        let err = RangeHeader::parse("bytes=20-30")
+            .unwrap()
+            .satisfiable_ranges(10)
+            .unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */10");
+    }
+
+    #[test]
+    fn invalid_headers_are_ignored() {
+        for header in [
+            "bytes=5-1",
+            "bytes=",
+            "bytes=a-b",
+            "bytes=1-2-3",
+            "bytes=+1-2",
+            "items=0-1",
+            "0-1",
+        ] {
+            assert_eq!(RangeHeader::parse(header), None, "{header}");
+        }
+        assert!(RangeHeader::parse("Bytes=0-1").is_some());
+    }
+
+    #[test]
+    fn ranges_are_only_extracted_for_get_requests() {
+        let head = RequestHead::for_tests()
+            .with_method(Method::GET)
+            .with_header("range", "bytes=0-1");
+        assert!(RangeHeader::extract(&head).is_some());
+        let head = RequestHead::for_tests()
+            .with_method(Method::POST)
+            .with_header("range", "bytes=0-1");
+        assert!(RangeHeader::extract(&head).is_none());
+        let head = RequestHead::for_tests();
+        assert!(RangeHeader::extract(&head).is_none());
+    }
+
+    #[test]
+    fn byte_range_helpers() {
+        let range = ByteRange { start: 2, end: 4 };
+        assert_eq!(range.len(), 3);
+        assert_eq!(range.slice(DATA), b"234");
+        assert_eq!(range.content_range(10), "bytes 2-4/10");
+    }
+
+    #[tokio::test]
+    async fn single_range_response() {
+        let response = RangeHeader::parse("bytes=-3")
+            .unwrap()
+            .satisfiable_ranges(DATA.len() as u64)
+            .unwrap()
+            .into_response(DATA, HeaderValue::from_static("text/plain"));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 7-9/10");
+        let (_, body) = response.into_parts();
+        let body = body.collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"789");
+    }
+
+    #[tokio::test]
+    async fn multi_range_response() {
+        let response = RangeHeader::parse("bytes=0-1,8-")
+            .unwrap()
+            .satisfiable_ranges(DATA.len() as u64)
+            .unwrap()
+            .into_response(DATA, HeaderValue::from_static("text/plain"));
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert!(response.headers().get(CONTENT_RANGE).is_none());
+        let content_type = response.headers()[CONTENT_TYPE].to_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap()
+            .to_owned();
+        let (_, body) = response.into_parts();
+        let body = body.collect().await.unwrap().to_bytes();
+        let expected = format!(
+            "\r\n--{boundary}\r\n\
+            Content-Type: text/plain\r\nContent-Range: bytes 0-1/10\r\n\r\n01\
+            \r\n--{boundary}\r\n\
+            Content-Type: text/plain\r\nContent-Range: bytes 8-9/10\r\n\r\n89\
+            \r\n--{boundary}--\r\n"
+        );
+        assert_eq!(std::str::from_utf8(&body).unwrap(), expected);
+    }
+}
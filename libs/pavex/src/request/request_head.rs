@@ -1,5 +1,9 @@
 use http::{HeaderMap, Method, Uri, Version};
 
+use crate::blueprint::constructor::{CloningStrategy, Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+
 #[non_exhaustive]
 #[derive(Debug)]
 /// All the information that is transmitted as part of an HTTP request ahead of the body.
@@ -12,7 +16,10 @@ use http::{HeaderMap, Method, Uri, Version};
 /// `RequestHead` is a framework primitive—you don't need to register any constructor
 /// with [`Blueprint`] to use it in your application.
 ///
-/// [`Blueprint`]: crate::blueprint::Blueprint
+/// # Borrowed views
+///
+/// If you only need to read a few fields, you can use [`RequestHead::view`] to get a
+/// [`RequestHeadView`]—a `Copy`-able snapshot of the head that borrows from it.
 pub struct RequestHead {
     pub method: Method,
     pub target: Uri,
@@ -30,3 +37,167 @@ impl From<http::request::Parts> for RequestHead {
         }
     }
 }
+
+impl RequestHead {
+    /// Get a [`RequestHeadView`] over this request head.
+    ///
+    /// It doesn't allocate: all the string-like fields borrow from `self`.
+    pub fn view(&self) -> RequestHeadView<'_> {
+        RequestHeadView::new(self)
+    }
+}
+
+#[cfg(test)]
+impl RequestHead {
+    /// A `GET /` HTTP/1.1 request head, with no headers.
+    ///
+    /// Customise it via the `with_*` methods to build fixtures for unit tests.
+    pub(crate) fn for_tests() -> Self {
+        Self {
+            method: Method::GET,
+            target: Uri::from_static("/"),
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Set the HTTP method.
+    pub(crate) fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the request target—e.g. `/users?sort=asc`.
+    pub(crate) fn with_target(mut self, target: &str) -> Self {
+        self.target = target.parse().unwrap();
+        self
+    }
+
+    /// Append a header. Previous values for the same header are preserved.
+    pub(crate) fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.append(
+            http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            http::HeaderValue::from_str(value).unwrap(),
+        );
+        self
+    }
+
+    /// Append several headers, in order.
+    pub(crate) fn with_headers(self, headers: &[(&str, &str)]) -> Self {
+        headers
+            .iter()
+            .fold(self, |head, (name, value)| head.with_header(name, value))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A lightweight, read-only view over a [`RequestHead`].
+///
+/// It exposes the method, the HTTP version, the path and the query of the incoming request
+/// without owning them: copying the fields of a [`RequestHead`] allocates (e.g. for the headers),
+/// while a [`RequestHeadView`] is `Copy`.
+///
+/// # Lifetime
+///
+/// `RequestHeadView<'head>` borrows from the [`RequestHead`] it was built from.
+/// Since [`RequestHead`] lives for the whole duration of the request processing
+/// pipeline, you can inject a [`RequestHeadView`] into any middleware, constructor or
+/// request handler—as long as it doesn't outlive the request.
+///
+/// # Installation
+///
+/// Register the [default constructor](RequestHeadView::new) with your [`Blueprint`]:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::request::RequestHeadView;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     RequestHeadView::register(&mut bp);
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use std::future::IntoFuture;
+/// use pavex::middleware::Next;
+/// use pavex::request::RequestHeadView;
+/// use pavex::response::Response;
+///
+/// pub async fn log_request<C>(head: RequestHeadView<'_>, next: Next<C>) -> Response
+/// where
+///     C: IntoFuture<Output = Response>,
+/// {
+///     println!("{} {} ({:?})", head.method, head.path, head.version);
+///     next.await
+/// }
+/// ```
+#[non_exhaustive]
+pub struct RequestHeadView<'head> {
+    /// The HTTP method of the request.
+    pub method: &'head Method,
+    /// The HTTP version of the request.
+    pub version: Version,
+    /// The path of the request target—e.g. `/users/123`.
+    pub path: &'head str,
+    /// The query of the request target, if there is one—e.g. `sort=asc` for
+    /// `/users?sort=asc`.
+    pub query: Option<&'head str>,
+    /// The headers of the request.
+    pub headers: &'head HeaderMap,
+}
+
+impl<'head> RequestHeadView<'head> {
+    /// The default constructor for [`RequestHeadView`].
+    pub fn new(request_head: &'head RequestHead) -> Self {
+        Self {
+            method: &request_head.method,
+            version: request_head.version,
+            path: request_head.target.path(),
+            query: request_head.target.query(),
+            headers: &request_head.headers,
+        }
+    }
+}
+
+impl RequestHeadView<'static> {
+    /// Register the [default constructor](RequestHeadView::new)
+    /// for [`RequestHeadView`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::request::RequestHeadView::new),
+            Lifecycle::RequestScoped,
+        )
+        .cloning(CloningStrategy::CloneIfNecessary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderValue, Method, Version};
+
+    use super::RequestHead;
+
+    #[test]
+    fn the_view_borrows_the_head_fields() {
+        let (parts, _) = http::Request::builder()
+            .method(Method::POST)
+            .uri("https://example.com/users/123?sort=asc")
+            .header("x-request-id", HeaderValue::from_static("abc"))
+            .body(())
+            .unwrap()
+            .into_parts();
+        let head: RequestHead = parts.into();
+
+        let view = head.view();
+        assert_eq!(view.method, &Method::POST);
+        assert_eq!(view.version, Version::HTTP_11);
+        assert_eq!(view.path, "/users/123");
+        assert_eq!(view.query, Some("sort=asc"));
+        assert_eq!(view.headers["x-request-id"], "abc");
+    }
+}
@@ -0,0 +1,69 @@
+//! Errors that can happen when reconstructing the absolute URL of incoming requests.
+
+use crate::response::Response;
+
+/// The error returned by [`RequestUrl::extract`] when the extraction fails.
+///
+/// See [`RequestUrl::extract`] and the documentation of each error variant for more details.
+///
+/// Pavex provides [`ExtractRequestUrlError::into_response`] as the default error handler for
+/// this failure.
+///
+/// [`RequestUrl::extract`]: crate::request::url::RequestUrl::extract
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExtractRequestUrlError {
+    #[error(transparent)]
+    /// See [`MissingHost`] for details.
+    MissingHost(#[from] MissingHost),
+    #[error(transparent)]
+    /// See [`InvalidHost`] for details.
+    InvalidHost(#[from] InvalidHost),
+    #[error(transparent)]
+    /// See [`InvalidScheme`] for details.
+    InvalidScheme(#[from] InvalidScheme),
+}
+
+impl ExtractRequestUrlError {
+    /// Convert an [`ExtractRequestUrlError`] into an HTTP response.
+    ///
+    /// It returns a `400 Bad Request` to the caller.
+    pub fn into_response(&self) -> Response {
+        Response::bad_request().set_typed_body(format!("{self}"))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The request doesn't specify which host it targets: \
+    it has no `Host` header and its request target is not an absolute URL."
+)]
+#[non_exhaustive]
+/// The host targeted by the request can't be determined.
+///
+/// HTTP/1.1 requests must carry a `Host` header, while HTTP/2 requests specify the host
+/// in the `:authority` pseudo-header.
+/// This error is returned when neither is present (nor a trusted forwarded header, if
+/// enabled via [`RequestUrlConfig::trust_forwarded_headers`]).
+///
+/// [`RequestUrlConfig::trust_forwarded_headers`]: crate::request::url::RequestUrlConfig::trust_forwarded_headers
+pub struct MissingHost;
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{value}` is not a valid host")]
+#[non_exhaustive]
+/// The host specified by the request (or by a trusted forwarded header) is not a valid
+/// URL authority—e.g. it contains whitespaces or a path.
+pub struct InvalidHost {
+    /// The invalid host value.
+    pub value: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{value}` is not a valid URL scheme")]
+#[non_exhaustive]
+/// The scheme specified by a trusted forwarded header is not a valid URL scheme.
+pub struct InvalidScheme {
+    /// The invalid scheme value.
+    pub value: String,
+}
@@ -0,0 +1,8 @@
+//! Reconstruct the absolute URL of incoming requests.
+//!
+//! Check out [`RequestUrl`] for more details.
+
+pub mod errors;
+mod request_url;
+
+pub use request_url::{RequestUrl, RequestUrlConfig};
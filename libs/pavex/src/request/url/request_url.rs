@@ -0,0 +1,495 @@
+use http::header::{HeaderMap, FORWARDED, HOST};
+use http::uri::{Authority, PathAndQuery, Scheme};
+use http::Uri;
+
+use crate::blueprint::constructor::{CloningStrategy, Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::request::RequestHead;
+
+use super::errors::{ExtractRequestUrlError, InvalidHost, InvalidScheme, MissingHost};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The absolute URL of the incoming request—e.g. `https://example.com/users/1?active=true`.
+///
+/// It's useful whenever you need to generate absolute URLs that point back to your
+/// application—e.g. links in emails or `Location` headers.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Resolution](#resolution)
+/// - [Reverse proxies](#reverse-proxies)
+///
+/// # Installation
+///
+/// Register the [default constructor](RequestUrl::register) for [`RequestUrl`] alongside the
+/// [default constructor](RequestUrlConfig::register) for [`RequestUrlConfig`]:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{router::GET, Blueprint};
+/// use pavex::request::url::{RequestUrl, RequestUrlConfig};
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     RequestUrlConfig::register(&mut bp);
+///     RequestUrl::register(&mut bp);
+///     bp.route(GET, "/", f!(crate::handler));
+///     bp
+/// }
+///
+/// pub fn handler(url: &RequestUrl) -> String {
+///     format!("You are visiting {url}")
+/// }
+/// ```
+///
+/// # Resolution
+///
+/// The path and the query come from the request target.
+/// The host comes from the request target, if it's an absolute URL (e.g. for HTTP/2
+/// requests), or from the `Host` header otherwise.
+/// If the host can't be determined, the extraction fails with [`MissingHost`] and the
+/// caller receives a `400 Bad Request`.
+///
+/// The scheme comes from the request target, if it's an absolute URL.
+/// Otherwise, it falls back to [`RequestUrlConfig::default_scheme`]—`http`, unless you
+/// change it.
+///
+/// # Reverse proxies
+///
+/// If your application sits behind a reverse proxy (e.g. a load balancer that terminates TLS),
+/// the request that reaches your server doesn't carry the URL requested by the client.
+/// Most proxies forward that information using either the standard `Forwarded` header
+/// (`proto` and `host` parameters) or the `X-Forwarded-Proto`/`X-Forwarded-Host` headers.
+///
+/// Those headers are ignored by default: any client can set them.
+/// Enable [`RequestUrlConfig::trust_forwarded_headers`] if, and only if, all requests go
+/// through your proxies. When enabled, `Forwarded` takes precedence over `X-Forwarded-*`.
+///
+/// Proxies append an element to those headers for each hop, after whatever the client sent.
+/// Pavex only looks at the element added by the outermost of your proxies: it's the right-most
+/// one if there is a single proxy in front of your application. Use
+/// [`RequestUrlConfig::trusted_proxy_hops`] if there are more.
+///
+/// [`MissingHost`]: super::errors::MissingHost
+pub struct RequestUrl(Uri);
+
+impl RequestUrl {
+    /// The default constructor for [`RequestUrl`].
+    ///
+    /// Check out [`RequestUrl`]'s documentation for more details on how the URL is
+    /// reconstructed.
+    pub fn extract(
+        request_head: &RequestHead,
+        config: &RequestUrlConfig,
+    ) -> Result<Self, ExtractRequestUrlError> {
+        let forwarded = if config.trust_forwarded_headers {
+            ForwardedParts::parse(&request_head.headers, config.trusted_proxy_hops)
+        } else {
+            ForwardedParts::default()
+        };
+        let target = &request_head.target;
+
+        let scheme = match forwarded.proto {
+            Some(proto) => {
+                Scheme::try_from(proto.as_str()).map_err(|_| InvalidScheme { value: proto })?
+            }
+            None => target
+                .scheme()
+                .cloned()
+                .unwrap_or_else(|| config.default_scheme.clone()),
+        };
+
+        let host = match forwarded.host {
+            Some(host) => host,
+            None => match target.authority() {
+                Some(authority) => authority.as_str().to_owned(),
+                None => {
+                    let host = request_head.headers.get(HOST).ok_or(MissingHost)?;
+                    let host = host.to_str().map_err(|_| InvalidHost {
+                        value: String::from_utf8_lossy(host.as_bytes()).into_owned(),
+                    })?;
+                    host.to_owned()
+                }
+            },
+        };
+        if host.is_empty() {
+            return Err(MissingHost.into());
+        }
+        // The host must not carry user information (e.g. `user@example.com`).
+        let authority = match Authority::try_from(host.as_str()) {
+            Ok(authority) if !host.contains('@') => authority,
+            _ => return Err(InvalidHost { value: host }.into()),
+        };
+
+        let path_and_query = target
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"));
+        let uri = Uri::builder()
+            .scheme(scheme)
+            .authority(authority)
+            .path_and_query(path_and_query)
+            .build()
+            .expect("A URL assembled from valid components is always valid");
+        Ok(Self(uri))
+    }
+
+    /// Register the [default constructor](RequestUrl::extract)
+    /// and [error handler](ExtractRequestUrlError::into_response)
+    /// for [`RequestUrl`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::request::url::RequestUrl::extract),
+            Lifecycle::RequestScoped,
+        )
+        .error_handler(f!(
+            pavex::request::url::errors::ExtractRequestUrlError::into_response
+        ))
+        .cloning(CloningStrategy::CloneIfNecessary)
+    }
+
+    /// The scheme of the URL—e.g. `https`.
+    pub fn scheme(&self) -> &str {
+        // Always set by `RequestUrl::extract`.
+        self.0.scheme_str().unwrap_or_default()
+    }
+
+    /// The host of the URL, without the port—e.g. `example.com`.
+    pub fn host(&self) -> &str {
+        // Always set by `RequestUrl::extract`.
+        self.0.host().unwrap_or_default()
+    }
+
+    /// The port of the URL, if it was specified explicitly.
+    pub fn port(&self) -> Option<u16> {
+        self.0.port_u16()
+    }
+
+    /// The path of the URL—e.g. `/users/1`.
+    pub fn path(&self) -> &str {
+        self.0.path()
+    }
+
+    /// The query of the URL, without the leading `?`—e.g. `active=true`.
+    pub fn query(&self) -> Option<&str> {
+        self.0.query()
+    }
+
+    /// The URL, as a [`Uri`].
+    pub fn as_uri(&self) -> &Uri {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<RequestUrl> for Uri {
+    fn from(value: RequestUrl) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Determine how the absolute URL of incoming requests is reconstructed by [`RequestUrl`].
+///
+/// Check out [`RequestUrl`]'s documentation for more details.
+pub struct RequestUrlConfig {
+    trust_forwarded_headers: bool,
+    trusted_proxy_hops: usize,
+    default_scheme: Scheme,
+}
+
+impl RequestUrlConfig {
+    /// Honor the `Forwarded`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers set by
+    /// reverse proxies.
+    ///
+    /// It defaults to `false`.
+    /// Only enable it if all requests reach your application through your proxies:
+    /// clients could otherwise spoof the URL they requested.
+    pub fn trust_forwarded_headers(mut self, trust: bool) -> Self {
+        self.trust_forwarded_headers = trust;
+        self
+    }
+
+    /// Set the number of reverse proxies, in front of your application, that append an
+    /// element to the forwarded headers.
+    ///
+    /// It defaults to `1`: the right-most element is used.
+    /// With `n` proxies, the `n`-th element from the right is used—the one added by the
+    /// outermost proxy. The elements to its left were set by the client and are ignored.
+    /// `0` is treated as `1`.
+    ///
+    /// It has no effect unless [`trust_forwarded_headers`](Self::trust_forwarded_headers)
+    /// is enabled.
+    pub fn trusted_proxy_hops(mut self, hops: usize) -> Self {
+        self.trusted_proxy_hops = hops.max(1);
+        self
+    }
+
+    /// Set the scheme used when the incoming request doesn't specify one.
+    ///
+    /// It defaults to `http`, since Pavex's server doesn't terminate TLS.
+    pub fn default_scheme(mut self, scheme: Scheme) -> Self {
+        self.default_scheme = scheme;
+        self
+    }
+
+    /// Register the [default constructor](RequestUrlConfig::default)
+    /// for [`RequestUrlConfig`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::request::url::RequestUrlConfig as std::default::Default>::default),
+            Lifecycle::Singleton,
+        )
+    }
+}
+
+impl Default for RequestUrlConfig {
+    fn default() -> Self {
+        Self {
+            trust_forwarded_headers: false,
+            trusted_proxy_hops: 1,
+            default_scheme: Scheme::HTTP,
+        }
+    }
+}
+
+/// The scheme and host requested by the client, as reported by a reverse proxy.
+#[derive(Default)]
+struct ForwardedParts {
+    proto: Option<String>,
+    host: Option<String>,
+}
+
+impl ForwardedParts {
+    fn parse(headers: &HeaderMap, trusted_hops: usize) -> Self {
+        let mut parts = Self::default();
+        if let Some(element) = trusted_value(headers, FORWARDED, trusted_hops) {
+            for pair in split_unquoted(element, ';') {
+                let Some((key, value)) = pair.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"').to_owned();
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "proto" => parts.proto = Some(value),
+                    "host" => parts.host = Some(value),
+                    _ => {}
+                }
+            }
+        }
+        if parts.proto.is_none() {
+            parts.proto =
+                trusted_value(headers, "x-forwarded-proto", trusted_hops).map(ToOwned::to_owned);
+        }
+        if parts.host.is_none() {
+            parts.host =
+                trusted_value(headers, "x-forwarded-host", trusted_hops).map(ToOwned::to_owned);
+        }
+        parts
+    }
+}
+
+/// The element added by the outermost trusted proxy to a comma-separated header—i.e.
+/// the `trusted_hops`-th from the right, across all the occurrences of the header.
+///
+/// If there are fewer elements than trusted hops, they were all added by trusted proxies:
+/// the left-most one is returned.
+fn trusted_value<K>(headers: &HeaderMap, key: K, trusted_hops: usize) -> Option<&str>
+where
+    K: http::header::AsHeaderName,
+{
+    let elements = headers
+        .get_all(key)
+        .iter()
+        .map(|value| value.to_str().ok())
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .flat_map(|value| split_unquoted(value, ','))
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+        .collect::<Vec<_>>();
+    let index = elements.len().checked_sub(trusted_hops).unwrap_or_default();
+    elements.get(index).copied()
+}
+
+/// Split `value` on `delimiter`, skipping the delimiters that appear within a quoted
+/// string—e.g. `host="a,b"`.
+fn split_unquoted(value: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut in_quotes, mut escaped) = (0, false, false);
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use http::uri::Scheme;
+
+    use super::{RequestUrl, RequestUrlConfig};
+    use crate::request::url::errors::ExtractRequestUrlError;
+    use crate::request::RequestHead;
+
+    fn trusted() -> RequestUrlConfig {
+        RequestUrlConfig::default().trust_forwarded_headers(true)
+    }
+
+    #[test]
+    fn direct_request_with_host_header() {
+        let head = RequestHead::for_tests()
+            .with_target("/users/1?active=true")
+            .with_header("host", "example.com:8080");
+        let url = RequestUrl::extract(&head, &RequestUrlConfig::default()).unwrap();
+        assert_eq!(
+            url.to_string(),
+            "http://example.com:8080/users/1?active=true"
+        );
+        assert_eq!(url.scheme(), "http");
+        assert_eq!(url.host(), "example.com");
+        assert_eq!(url.port(), Some(8080));
+        assert_eq!(url.path(), "/users/1");
+        assert_eq!(url.query(), Some("active=true"));
+    }
+
+    #[test]
+    fn direct_request_with_absolute_target() {
+        // E.g. an HTTP/2 request, where scheme and authority are pseudo-headers.
+        let head = RequestHead::for_tests()
+            .with_target("https://example.com/users")
+            .with_header("host", "ignored.com");
+        let url = RequestUrl::extract(&head, &RequestUrlConfig::default()).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/users");
+    }
+
+    #[test]
+    fn the_default_scheme_can_be_customized() {
+        let config = RequestUrlConfig::default().default_scheme(Scheme::HTTPS);
+        let head = RequestHead::for_tests()
+            .with_target("/")
+            .with_header("host", "example.com");
+        let url = RequestUrl::extract(&head, &config).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/");
+    }
+
+    #[test]
+    fn proxied_request_with_x_forwarded_headers() {
+        let head = RequestHead::for_tests()
+            .with_target("/login?next=%2Fhome")
+            .with_headers(&[
+                ("host", "10.0.0.1:3000"),
+                ("x-forwarded-proto", "https"),
+                ("x-forwarded-host", "example.com"),
+            ]);
+        let url = RequestUrl::extract(&head, &trusted()).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/login?next=%2Fhome");
+    }
+
+    #[test]
+    fn values_set_by_the_client_are_ignored() {
+        // The proxy appended its own value to the one sent by the client.
+        let head = RequestHead::for_tests().with_target("/").with_headers(&[
+            ("x-forwarded-proto", "http, https"),
+            ("x-forwarded-host", "spoofed.com, example.com"),
+        ]);
+        let url = RequestUrl::extract(&head, &trusted()).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/");
+
+        // Values can also be appended as a separate header line.
+        let head = RequestHead::for_tests().with_target("/").with_headers(&[
+            ("x-forwarded-host", "spoofed.com"),
+            ("x-forwarded-host", "example.com"),
+        ]);
+        let url = RequestUrl::extract(&head, &trusted()).unwrap();
+        assert_eq!(url.host(), "example.com");
+    }
+
+    #[test]
+    fn the_outermost_trusted_proxy_wins() {
+        let head = RequestHead::for_tests()
+            .with_target("/")
+            .with_header("x-forwarded-host", "spoofed.com, example.com, cdn.internal");
+        let config = trusted().trusted_proxy_hops(2);
+        let url = RequestUrl::extract(&head, &config).unwrap();
+        assert_eq!(url.host(), "example.com");
+
+        // Fewer elements than trusted hops: they were all added by trusted proxies.
+        let config = trusted().trusted_proxy_hops(5);
+        let url = RequestUrl::extract(&head, &config).unwrap();
+        assert_eq!(url.host(), "spoofed.com");
+    }
+
+    #[test]
+    fn proxied_request_with_forwarded_header() {
+        let head = RequestHead::for_tests().with_target("/").with_headers(&[
+            ("host", "10.0.0.1:3000"),
+            (
+                "forwarded",
+                "for=192.0.2.60;host=spoofed.com, for=10.0.0.2;proto=https;host=\"example.com\"",
+            ),
+            ("x-forwarded-host", "ignored.com"),
+        ]);
+        let url = RequestUrl::extract(&head, &trusted()).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/");
+    }
+
+    #[test]
+    fn quoted_forwarded_values_can_contain_commas() {
+        let head = RequestHead::for_tests().with_target("/").with_header(
+            "forwarded",
+            "for=192.0.2.60;by=\"a, b;c\";proto=https;host=example.com",
+        );
+        let url = RequestUrl::extract(&head, &trusted()).unwrap();
+        assert_eq!(url.to_string(), "https://example.com/");
+    }
+
+    #[test]
+    fn forwarded_headers_are_ignored_unless_trusted() {
+        let head = RequestHead::for_tests().with_target("/").with_headers(&[
+            ("host", "10.0.0.1:3000"),
+            ("x-forwarded-proto", "https"),
+            ("x-forwarded-host", "example.com"),
+        ]);
+        let url = RequestUrl::extract(&head, &RequestUrlConfig::default()).unwrap();
+        assert_eq!(url.to_string(), "http://10.0.0.1:3000/");
+    }
+
+    #[test]
+    fn missing_host_is_rejected() {
+        let head = RequestHead::for_tests().with_target("/");
+        let err = RequestUrl::extract(&head, &RequestUrlConfig::default()).unwrap_err();
+        assert!(matches!(err, ExtractRequestUrlError::MissingHost(_)));
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn invalid_forwarded_values_are_rejected() {
+        let head = RequestHead::for_tests()
+            .with_target("/")
+            .with_header("x-forwarded-host", "user@example.com");
+        let err = RequestUrl::extract(&head, &trusted()).unwrap_err();
+        assert!(matches!(err, ExtractRequestUrlError::InvalidHost(_)));
+
+        let head = RequestHead::for_tests()
+            .with_target("/")
+            .with_headers(&[("host", "example.com"), ("x-forwarded-proto", "ht tp")]);
+        let err = RequestUrl::extract(&head, &trusted()).unwrap_err();
+        assert!(matches!(err, ExtractRequestUrlError::InvalidScheme(_)));
+    }
+}
@@ -0,0 +1,280 @@
+use http::header::ACCEPT;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::request::path::MatchedPathPattern;
+use crate::request::RequestHead;
+
+use super::errors::{ExtractApiVersionError, MissingApiVersion};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The version of your API targeted by an incoming request—e.g. `v2`.
+///
+/// # Guide
+///
+/// Pavex supports two versioning schemes:
+///
+/// - **Path-based**: the version is encoded as a path segment, e.g. `/v2/users`.
+/// - **Header-based**: the version is encoded in the media type listed in the `Accept`
+///   header, e.g. `Accept: application/vnd.api.v2+json`.
+///
+/// # Registration
+///
+/// Use [`ApiVersion::nest`] to nest the routes of a specific version under the
+/// corresponding prefix, and [`ApiVersion::register`] to make [`ApiVersion`] injectable:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{router::GET, Blueprint};
+/// use pavex::request::version::ApiVersion;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     ApiVersion::register(&mut bp);
+///     // All routes in `v1` are nested under `/v1`.
+///     ApiVersion::new(1).nest(&mut bp, v1());
+///     // All routes in `v2` are nested under `/v2`.
+///     ApiVersion::new(2).nest(&mut bp, v2());
+///     bp
+/// }
+///
+/// fn v1() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.route(GET, "/greet", f!(crate::greet));
+///     bp
+/// }
+///
+/// fn v2() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.route(GET, "/greet", f!(crate::greet));
+///     bp
+/// }
+///
+/// // Invoked for both `/v1/greet` and `/v2/greet`.
+/// pub fn greet(version: ApiVersion) -> String {
+///     format!("Hello from {version}!")
+/// }
+/// ```
+///
+/// # Precedence
+///
+/// The path always takes precedence over the `Accept` header.
+/// If the matched route was nested via [`ApiVersion::nest`], the `Accept` header is not
+/// inspected: a `GET /v1/greet` request carrying an `Accept: application/vnd.api.v2+json`
+/// header targets `v1`.
+/// The path determines which request handler is invoked—it'd be misleading to report
+/// a different version to it.
+///
+/// Only the prefixes added by [`ApiVersion::nest`] count: a versioned segment that you wrote
+/// yourself in a route path (e.g. `/archive/v2`) or passed to [`Blueprint::nest_at`]
+/// is ignored.
+///
+/// The `Accept` header is used for all other routes.
+/// If the header lists multiple versioned media types, the first one wins.
+///
+/// If the `Accept` header doesn't specify a version, the extraction fails
+/// with an [`ExtractApiVersionError`].
+#[doc(alias = "Version")]
+#[doc(alias = "Versioning")]
+pub struct ApiVersion(u32);
+
+impl ApiVersion {
+    /// Create a new [`ApiVersion`].
+    pub fn new(version: u32) -> Self {
+        Self(version)
+    }
+
+    /// The version number—e.g. `2` for `v2`.
+    pub fn number(&self) -> u32 {
+        self.0
+    }
+
+    /// The path prefix for this version—e.g. `/v2` for `v2`.
+    pub fn path_prefix(&self) -> String {
+        format!("/v{}", self.0)
+    }
+
+    #[track_caller]
+    /// Nest a [`Blueprint`] under the path prefix for this version—e.g. `/v2` for `v2`.
+    ///
+    /// It's equivalent to [`Blueprint::nest_at`], with an extra step: it registers
+    /// [`ApiVersion::extract_from_path`] as the constructor for [`ApiVersion`] in the nested
+    /// blueprint. The routes of the nested blueprint get their version from the path,
+    /// no matter what the `Accept` header says.
+    ///
+    /// Check out [`Blueprint::nest_at`] for more details on nesting.
+    pub fn nest(&self, bp: &mut Blueprint, mut blueprint: Blueprint) {
+        blueprint
+            .constructor(
+                f!(pavex::request::version::ApiVersion::extract_from_path),
+                Lifecycle::RequestScoped,
+            )
+            .error_handler(f!(
+                pavex::request::version::errors::ExtractApiVersionError::into_response
+            ));
+        bp.nest_at(&self.path_prefix(), blueprint)
+    }
+
+    /// The default constructor for [`ApiVersion`].
+    ///
+    /// It looks for a versioned media type in the `Accept` header—e.g.
+    /// `application/vnd.api.v2+json`.
+    /// It's not used for the routes nested via [`ApiVersion::nest`]: they rely on
+    /// [`ApiVersion::extract_from_path`] instead.
+    ///
+    /// If the extraction fails, an [`ExtractApiVersionError`] is returned.
+    ///
+    /// Check out [`ApiVersion`] for more details on precedence.
+    pub fn extract(request_head: &RequestHead) -> Result<Self, ExtractApiVersionError> {
+        request_head
+            .headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(version_from_media_type)
+            .ok_or_else(|| MissingApiVersion.into())
+    }
+
+    /// The constructor for [`ApiVersion`] used by the routes nested via [`ApiVersion::nest`].
+    ///
+    /// It returns the first versioned segment (e.g. `v2`) in the matched route template—e.g.
+    /// `/v2/users/:id`.
+    /// You don't need to register it yourself: [`ApiVersion::nest`] takes care of it.
+    pub fn extract_from_path(
+        matched_path_pattern: MatchedPathPattern,
+    ) -> Result<Self, ExtractApiVersionError> {
+        version_from_path(matched_path_pattern.inner()).ok_or_else(|| MissingApiVersion.into())
+    }
+
+    /// Register the [default constructor](ApiVersion::extract)
+    /// and [error handler](ExtractApiVersionError::into_response)
+    /// for [`ApiVersion`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::request::version::ApiVersion::extract),
+            Lifecycle::RequestScoped,
+        )
+        .error_handler(f!(
+            pavex::request::version::errors::ExtractApiVersionError::into_response
+        ))
+    }
+}
+
+impl std::fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", self.0)
+    }
+}
+
+/// Parse a version identifier, e.g. `v2`.
+fn parse_version(s: &str) -> Option<ApiVersion> {
+    let digits = s.strip_prefix('v')?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().map(ApiVersion)
+}
+
+/// Look for the first versioned segment in a route template—e.g. `/v2/users/:id`.
+///
+/// Dynamic segments (e.g. `:id`) never match, since they start with `:` or `*`.
+fn version_from_path(path: &str) -> Option<ApiVersion> {
+    path.split('/').find_map(parse_version)
+}
+
+/// Look for a version in a vendor-specific media type—e.g. `application/vnd.api.v2+json`.
+fn version_from_media_type(media_type: &str) -> Option<ApiVersion> {
+    // Ignore media type parameters—e.g. `q=0.9`.
+    let essence = media_type.split(';').next()?.trim();
+    let (_, subtype) = essence.split_once('/')?;
+    let vendor = subtype.strip_prefix("vnd.")?;
+    // Ignore the structured syntax suffix—e.g. `+json`.
+    let vendor = vendor.split('+').next()?;
+    vendor.rsplit('.').next().and_then(parse_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_path(path: &'static str) -> Option<u32> {
+        ApiVersion::extract_from_path(MatchedPathPattern::new(path))
+            .ok()
+            .map(|v| v.number())
+    }
+
+    fn from_header(accept: &'static str) -> Option<u32> {
+        let head = RequestHead::for_tests().with_header("accept", accept);
+        ApiVersion::extract(&head).ok().map(|v| v.number())
+    }
+
+    #[test]
+    fn path_based_versioning() {
+        assert_eq!(from_path("/v1/users/:id"), Some(1));
+        assert_eq!(from_path("/v2/users/:id"), Some(2));
+        assert_eq!(from_path("/api/v10/users"), Some(10));
+    }
+
+    #[test]
+    fn the_first_versioned_segment_wins() {
+        assert_eq!(from_path("/v1/archive/v2"), Some(1));
+    }
+
+    #[test]
+    fn dynamic_and_unversioned_segments_are_ignored() {
+        assert_eq!(from_path("/:v1/users"), None);
+        assert_eq!(from_path("/vendors/v/users"), None);
+        assert_eq!(from_path("/v2beta/users"), None);
+    }
+
+    #[test]
+    fn header_based_versioning() {
+        assert_eq!(from_header("application/vnd.api.v1+json"), Some(1));
+        assert_eq!(from_header("application/vnd.api.v2+json"), Some(2));
+        assert_eq!(from_header("application/vnd.acme.v3"), Some(3));
+        assert_eq!(
+            from_header("text/html, application/vnd.api.v2+json;q=0.9"),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn non_vendor_media_types_are_ignored() {
+        assert_eq!(from_header("application/json"), None);
+        assert_eq!(from_header("application/vnd.api+json"), None);
+    }
+
+    #[test]
+    fn the_default_constructor_ignores_the_path() {
+        // Only the routes nested via `ApiVersion::nest` get their version from the path.
+        let head = RequestHead::for_tests().with_target("/v2/users");
+        let err = ApiVersion::extract(&head).unwrap_err();
+        assert!(matches!(err, ExtractApiVersionError::MissingApiVersion(_)));
+    }
+
+    #[test]
+    fn nested_routes_get_their_version_from_the_path() {
+        let mut bp = Blueprint::new();
+        ApiVersion::new(2).nest(&mut bp, Blueprint::new());
+        let crate::blueprint::internals::NestedBlueprint {
+            path_prefix,
+            blueprint,
+            ..
+        } = &bp.nested_blueprints[0];
+        assert_eq!(path_prefix.as_deref(), Some("/v2"));
+        let constructor = &blueprint.constructors[0].constructor.callable;
+        assert_eq!(
+            constructor.fully_qualified_path().join("::"),
+            "pavex::request::version::ApiVersion::extract_from_path"
+        );
+    }
+
+    #[test]
+    fn missing_version() {
+        let err = ApiVersion::extract(&RequestHead::for_tests()).unwrap_err();
+        assert!(matches!(err, ExtractApiVersionError::MissingApiVersion(_)));
+        assert_eq!(err.into_response().status(), http::StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,44 @@
+//! Errors that can happen when determining the API version of incoming requests.
+
+use crate::response::Response;
+
+/// The error returned by [`ApiVersion::extract`] when the extraction fails.
+///
+/// See [`ApiVersion::extract`] and the documentation of each error variant for more details.
+///
+/// Pavex provides [`ExtractApiVersionError::into_response`] as the default error handler for
+/// this failure.
+///
+/// [`ApiVersion::extract`]: crate::request::version::ApiVersion::extract
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExtractApiVersionError {
+    #[error(transparent)]
+    /// See [`MissingApiVersion`] for details.
+    MissingApiVersion(#[from] MissingApiVersion),
+}
+
+impl ExtractApiVersionError {
+    /// Convert an [`ExtractApiVersionError`] into an HTTP response.
+    ///
+    /// It returns a `400 Bad Request` to the caller.
+    pub fn into_response(&self) -> Response {
+        match self {
+            Self::MissingApiVersion(e) => Response::bad_request().set_typed_body(format!("{e}")),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The request doesn't specify which API version it targets. \
+    Use a versioned path (e.g. `/v1/...`) or a versioned media type in the `Accept` header \
+    (e.g. `application/vnd.api.v1+json`)."
+)]
+#[non_exhaustive]
+/// The matched route is not nested under a versioned prefix (e.g. `/v1`) via
+/// [`ApiVersion::nest`] and the `Accept` header doesn't specify a versioned media type
+/// (e.g. `application/vnd.api.v1+json`).
+///
+/// [`ApiVersion::nest`]: crate::request::version::ApiVersion::nest
+pub struct MissingApiVersion;
@@ -0,0 +1,8 @@
+//! Determine which version of your API an incoming request is targeting.
+//!
+//! Check out [`ApiVersion`] for more details.
+
+mod api_version;
+pub mod errors;
+
+pub use api_version::ApiVersion;
@@ -1,2 +1,28 @@
 //! Errors that can arise when building a [`Response`](crate::response::Response) body.
 pub use super::json::JsonSerializationError;
+
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The response body is larger than the maximum size limit enforced by this server.")]
+#[non_exhaustive]
+/// The error returned by [`ResponseBodySizeLimit::enforce`] when the body of the outgoing
+/// response is larger than the configured limit.
+///
+/// [`ResponseBodySizeLimit::enforce`]: super::ResponseBodySizeLimit::enforce
+pub struct ResponseBodySizeLimitExceeded {
+    /// The maximum size limit enforced by this server.
+    pub max_n_bytes: usize,
+    /// The size of the response body that breached the limit.
+    pub body_n_bytes: u64,
+}
+
+impl ResponseBodySizeLimitExceeded {
+    /// Convert a [`ResponseBodySizeLimitExceeded`] error into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error`: the failure is on the server side,
+    /// the client has no way to fix it.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
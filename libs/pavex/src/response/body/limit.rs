@@ -0,0 +1,271 @@
+use std::future::IntoFuture;
+
+use http::header::CONTENT_LENGTH;
+use http_body_util::BodyExt;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::middleware::Next;
+use crate::response::{Response, ResponseBody};
+
+use super::errors::ResponseBodySizeLimitExceeded;
+use super::raw::{Full, RawBody};
+
+#[derive(Debug, Clone, Copy)]
+/// An upper limit on the size of the bodies of outgoing responses.
+///
+/// It is the response-side counterpart of
+/// [`BodySizeLimit`](crate::request::body::BodySizeLimit): it protects your application from
+/// buffering unbounded amounts of data in memory when a handler serializes a large
+/// collection into the response body.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Per-route limits](#per-route-limits)
+/// - [Streaming bodies](#streaming-bodies)
+///
+/// # Installation
+///
+/// The limit is enforced by [`ResponseBodySizeLimit::enforce`], a wrapping middleware.
+/// Register it alongside the [default constructor](ResponseBodySizeLimit::register)
+/// for [`ResponseBodySizeLimit`]:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::Blueprint;
+/// use pavex::response::body::ResponseBodySizeLimit;
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     ResponseBodySizeLimit::register(&mut bp);
+///     bp.wrap(f!(pavex::response::body::ResponseBodySizeLimit::enforce))
+///         .error_handler(f!(
+///             pavex::response::body::errors::ResponseBodySizeLimitExceeded::into_response
+///         ));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// # Per-route limits
+///
+/// There is no route-level override: nesting is how you set a different limit for
+/// a specific route (or group of routes).
+/// The middleware uses the [`ResponseBodySizeLimit`] that is visible in the scope
+/// it was registered against, therefore the nested blueprint must register **both**
+/// its own constructor for [`ResponseBodySizeLimit`] and [`ResponseBodySizeLimit::enforce`]:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{Blueprint, constructor::Lifecycle, router::GET};
+/// use pavex::response::body::{LimitExceededPolicy, ResponseBodySizeLimit};
+/// # pub fn home() -> String { todo!() }
+/// # pub fn export() -> String { todo!() }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.route(GET, "/", f!(crate::home));
+///     bp.nest(export_bp());
+///     bp
+/// }
+///
+/// fn export_bp() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     // This limit will only apply to the routes registered
+///     // in this nested blueprint.
+///     bp.constructor(f!(crate::export_size_limit), Lifecycle::Singleton);
+///     bp.wrap(f!(pavex::response::body::ResponseBodySizeLimit::enforce))
+///         .error_handler(f!(
+///             pavex::response::body::errors::ResponseBodySizeLimitExceeded::into_response
+///         ));
+///     bp.route(GET, "/export", f!(crate::export));
+///     bp
+/// }
+///
+/// pub fn export_size_limit() -> ResponseBodySizeLimit {
+///     ResponseBodySizeLimit::Enabled {
+///         max_n_bytes: 1_048_576, // 1 MB
+///         on_exceeded: LimitExceededPolicy::Truncate,
+///     }
+/// }
+/// ```
+///
+/// # Streaming bodies
+///
+/// The limit is only enforced on **buffered** bodies, i.e. bodies whose exact size is known
+/// ahead of transmission (e.g. those built via [`Response::set_typed_body`] with a `String`,
+/// a `Vec<u8>` or [`Json`](super::Json)).
+/// Bodies without an exact [size hint](http_body::Body::size_hint)—e.g. streaming bodies—are
+/// let through **unchecked**: the limit is not enforced while the body is being streamed,
+/// no matter how many bytes end up being sent to the client.
+/// Enforce an upper bound in the stream itself if you need one.
+pub enum ResponseBodySizeLimit {
+    /// There is an active limit on the size of outgoing response bodies.
+    Enabled {
+        /// The maximum size of outgoing response bodies, in bytes.
+        max_n_bytes: usize,
+        /// What should happen when a response body is larger than `max_n_bytes`.
+        on_exceeded: LimitExceededPolicy,
+    },
+    /// There is no limit on the size of outgoing response bodies.
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The behaviour of [`ResponseBodySizeLimit::enforce`] when a response body
+/// is larger than the configured limit.
+pub enum LimitExceededPolicy {
+    /// Discard the response and return a [`ResponseBodySizeLimitExceeded`] error instead.
+    Error,
+    /// Log a warning and truncate the response body to the maximum allowed size.
+    ///
+    /// The `Content-Length` header, if it was set explicitly, is removed: it no longer matches
+    /// the size of the body.
+    Truncate,
+}
+
+impl ResponseBodySizeLimit {
+    /// Register the [default constructor](ResponseBodySizeLimit::default)
+    /// for [`ResponseBodySizeLimit`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::response::body::ResponseBodySizeLimit as std::default::Default>::default),
+            Lifecycle::RequestScoped,
+        )
+    }
+
+    /// A wrapping middleware that enforces the configured [`ResponseBodySizeLimit`] on
+    /// the response returned by the rest of the request processing pipeline.
+    ///
+    /// Check out [`ResponseBodySizeLimit`]'s documentation for more details.
+    pub async fn enforce<C>(
+        next: Next<C>,
+        limit: ResponseBodySizeLimit,
+    ) -> Result<Response, ResponseBodySizeLimitExceeded>
+    where
+        C: IntoFuture<Output = Response>,
+    {
+        let response = next.into_future().await;
+        let ResponseBodySizeLimit::Enabled {
+            max_n_bytes,
+            on_exceeded,
+        } = limit
+        else {
+            return Ok(response);
+        };
+        // Streaming bodies don't know their size ahead of time: we skip the check for them.
+        let Some(n_bytes) = response.body().size_hint().exact() else {
+            return Ok(response);
+        };
+        if n_bytes <= max_n_bytes as u64 {
+            return Ok(response);
+        }
+
+        let error = ResponseBodySizeLimitExceeded {
+            max_n_bytes,
+            body_n_bytes: n_bytes,
+        };
+        match on_exceeded {
+            LimitExceededPolicy::Error => {
+                tracing::error!(
+                    max_n_bytes = max_n_bytes,
+                    body_n_bytes = n_bytes,
+                    "The response body is larger than the configured size limit. \
+                    It will be discarded."
+                );
+                Err(error)
+            }
+            LimitExceededPolicy::Truncate => {
+                tracing::warn!(
+                    max_n_bytes = max_n_bytes,
+                    body_n_bytes = n_bytes,
+                    "The response body is larger than the configured size limit. \
+                    It will be truncated."
+                );
+                let mut response = response;
+                response.headers_mut().remove(CONTENT_LENGTH);
+                let (head, body) = response.into_parts();
+                let bytes = match body.collect().await {
+                    Ok(collected) => collected.to_bytes(),
+                    // Bodies with an exact size hint have already been buffered in memory,
+                    // polling them to completion shouldn't fail in practice.
+                    Err(_) => return Err(error),
+                };
+                let body = ResponseBody::new(Full::new(bytes.slice(..max_n_bytes)));
+                Ok(Response::from_parts(head, body))
+            }
+        }
+    }
+}
+
+impl Default for ResponseBodySizeLimit {
+    fn default() -> Self {
+        Self::Enabled {
+            max_n_bytes: 10_485_760, // 10 MBs
+            on_exceeded: LimitExceededPolicy::Error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use crate::middleware::Next;
+    use crate::response::Response;
+
+    use super::{LimitExceededPolicy, ResponseBodySizeLimit};
+
+    fn limit(max_n_bytes: usize, on_exceeded: LimitExceededPolicy) -> ResponseBodySizeLimit {
+        ResponseBodySizeLimit::Enabled {
+            max_n_bytes,
+            on_exceeded,
+        }
+    }
+
+    #[tokio::test]
+    async fn error_if_buffered_body_above_size_limit() {
+        let next = Next::new(async { Response::ok().set_typed_body(vec![0u8; 1000]) });
+        let Err(err) =
+            ResponseBodySizeLimit::enforce(next, limit(100, LimitExceededPolicy::Error)).await
+        else {
+            panic!("Expected the response body size limit to be enforced");
+        };
+        insta::assert_display_snapshot!(err, @"The response body is larger than the maximum size limit enforced by this server.");
+        insta::assert_debug_snapshot!(err, @r###"
+        ResponseBodySizeLimitExceeded {
+            max_n_bytes: 100,
+            body_n_bytes: 1000,
+        }
+        "###);
+        assert_eq!(
+            err.into_response().status(),
+            crate::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[tokio::test]
+    async fn truncate_if_buffered_body_above_size_limit() {
+        let next = Next::new(async { Response::ok().set_typed_body(vec![0u8; 1000]) });
+        let response =
+            ResponseBodySizeLimit::enforce(next, limit(100, LimitExceededPolicy::Truncate))
+                .await
+                .unwrap();
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        assert_eq!(bytes.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn bodies_within_the_limit_are_left_untouched() {
+        let next = Next::new(async { Response::ok().set_typed_body(vec![0u8; 100]) });
+        let response = ResponseBodySizeLimit::enforce(next, limit(100, LimitExceededPolicy::Error))
+            .await
+            .unwrap();
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        assert_eq!(bytes.len(), 100);
+    }
+}
@@ -5,14 +5,17 @@
 //! [`Response::set_typed_body`]: crate::response::Response::set_typed_body
 pub use html::Html;
 pub use json::Json;
+pub use limit::{LimitExceededPolicy, ResponseBodySizeLimit};
 pub use typed_body::TypedBody;
 
 pub(super) mod body_;
 mod bytes;
 mod html;
 mod json;
+mod limit;
 mod plain_text;
 pub mod raw;
+pub(crate) mod trailers;
 
 pub mod errors;
 
@@ -19,6 +19,8 @@ impl TypedBody for String {
     }
 }
 
+/// The body points directly at the static string: converting a `&'static str` into [`Bytes`]
+/// doesn't copy or allocate.
 impl TypedBody for &'static str {
     type Body = Full<Bytes>;
 
@@ -31,6 +33,7 @@ impl TypedBody for &'static str {
     }
 }
 
+/// Borrowed strings are not copied: see the implementation for `&'static str`.
 impl TypedBody for Cow<'static, str> {
     type Body = Full<Bytes>;
 
@@ -45,3 +48,42 @@ impl TypedBody for Cow<'static, str> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use bytes::Bytes;
+    use http_body_util::BodyExt;
+
+    use super::TypedBody;
+
+    async fn into_bytes<T: TypedBody<Body = http_body_util::Full<Bytes>>>(body: T) -> Bytes {
+        body.body()
+            .frame()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_data()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn static_str_bodies_are_not_copied() {
+        let s: &'static str = "Hello, world!";
+        let bytes = into_bytes(s).await;
+        assert_eq!(bytes, s.as_bytes());
+        // The body points at the static string itself.
+        assert_eq!(bytes.as_ptr(), s.as_ptr());
+    }
+
+    #[tokio::test]
+    async fn borrowed_cow_bodies_are_not_copied() {
+        let s: &'static str = "Hello, world!";
+        let bytes = into_bytes(Cow::Borrowed(s)).await;
+        assert_eq!(bytes.as_ptr(), s.as_ptr());
+
+        let owned: Cow<'static, str> = Cow::Owned("Hello, world!".to_string());
+        assert_eq!(into_bytes(owned).await, s.as_bytes());
+    }
+}
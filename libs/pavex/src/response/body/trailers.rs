@@ -0,0 +1,66 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http_body::{Frame, SizeHint};
+
+use crate::http::HeaderMap;
+use crate::response::body::raw::{Bytes, RawBody};
+use crate::response::ResponseBody;
+
+/// A body that emits a set of trailers once the wrapped body has been fully
+/// streamed.
+///
+/// If the wrapped body emits its own trailers, the two sets are merged—the values
+/// provided to [`WithTrailers::new`] take precedence.
+pub(crate) struct WithTrailers {
+    inner: ResponseBody,
+    trailers: Option<HeaderMap>,
+}
+
+impl WithTrailers {
+    pub(crate) fn new(inner: ResponseBody, trailers: HeaderMap) -> Self {
+        Self {
+            inner,
+            trailers: Some(trailers),
+        }
+    }
+}
+
+impl RawBody for WithTrailers {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        match Pin::new(&mut this.inner).poll_frame(cx) {
+            Poll::Ready(Some(Ok(frame))) => {
+                let frame = match frame.into_trailers() {
+                    Ok(mut inner_trailers) => {
+                        if let Some(trailers) = this.trailers.take() {
+                            for name in trailers.keys() {
+                                inner_trailers.remove(name);
+                            }
+                            inner_trailers.extend(trailers);
+                        }
+                        Frame::trailers(inner_trailers)
+                    }
+                    Err(frame) => frame,
+                };
+                Poll::Ready(Some(Ok(frame)))
+            }
+            Poll::Ready(None) => Poll::Ready(this.trailers.take().map(|t| Ok(Frame::trailers(t)))),
+            other => other,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream() && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
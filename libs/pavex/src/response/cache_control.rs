@@ -0,0 +1,234 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::http::header::CACHE_CONTROL;
+use crate::http::HeaderValue;
+use crate::response::Response;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A set of caching directives, rendered as the value of a `Cache-Control` header.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use pavex::response::CacheControl;
+///
+/// let directives = CacheControl::public()
+///     .max_age(Duration::from_secs(3600))
+///     .immutable();
+/// assert_eq!(directives.to_string(), "public, max-age=3600, immutable");
+/// ```
+///
+/// # Routes
+///
+/// You can attach caching directives to a route via
+/// [`Route::cache_control`](crate::blueprint::router::Route::cache_control): Pavex will set the
+/// `Cache-Control` header on all the responses returned by that route, unless
+/// they already include one.
+///
+/// # Durations
+///
+/// `max-age` and `s-maxage` are expressed in seconds: sub-second precision is discarded.
+///
+/// # `Expires`
+///
+/// There is no support for the `Expires` header.
+/// Caches ignore it when the response includes a `max-age` or `s-maxage` directive,
+/// and a relative freshness lifetime is easier to get right than an absolute date.
+/// Set the header in your request handler if you need to support clients that
+/// predate `Cache-Control`.
+pub struct CacheControl {
+    visibility: Option<Visibility>,
+    no_cache: bool,
+    no_store: bool,
+    max_age: Option<Duration>,
+    s_maxage: Option<Duration>,
+    immutable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visibility {
+    Public,
+    Private,
+}
+
+impl CacheControl {
+    fn empty() -> Self {
+        Self {
+            visibility: None,
+            no_cache: false,
+            no_store: false,
+            max_age: None,
+            s_maxage: None,
+            immutable: false,
+        }
+    }
+
+    /// The response can be stored by any cache, including shared caches (e.g. a CDN).
+    ///
+    /// It renders as the `public` directive.
+    pub fn public() -> Self {
+        Self {
+            visibility: Some(Visibility::Public),
+            ..Self::empty()
+        }
+    }
+
+    /// The response is meant for a single user and must not be stored by shared caches.
+    ///
+    /// It renders as the `private` directive.
+    pub fn private() -> Self {
+        Self {
+            visibility: Some(Visibility::Private),
+            ..Self::empty()
+        }
+    }
+
+    /// The response must not be stored by any cache.
+    ///
+    /// It renders as the `no-store` directive.
+    pub fn no_store() -> Self {
+        Self {
+            no_store: true,
+            ..Self::empty()
+        }
+    }
+
+    /// The response can be stored, but caches must validate it with the origin server
+    /// before each reuse.
+    ///
+    /// It renders as the `no-cache` directive.
+    pub fn no_cache() -> Self {
+        Self {
+            no_cache: true,
+            ..Self::empty()
+        }
+    }
+
+    /// Set how long the response remains fresh after it has been generated.
+    ///
+    /// It renders as the `max-age=<seconds>` directive.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Set how long the response remains fresh in shared caches, overriding
+    /// [`max_age`](Self::max_age) for them.
+    ///
+    /// It renders as the `s-maxage=<seconds>` directive.
+    pub fn s_maxage(mut self, s_maxage: Duration) -> Self {
+        self.s_maxage = Some(s_maxage);
+        self
+    }
+
+    /// The response will not change while it's fresh—caches don't need to revalidate it,
+    /// even if the user explicitly asks for a reload.
+    ///
+    /// It renders as the `immutable` directive.
+    pub fn immutable(mut self) -> Self {
+        self.immutable = true;
+        self
+    }
+
+    /// Render the directives as a `Cache-Control` header value.
+    pub fn header_value(&self) -> HeaderValue {
+        // The rendered directives only contain visible ASCII characters.
+        HeaderValue::from_str(&self.to_string()).expect("Invalid `Cache-Control` header value")
+    }
+}
+
+impl Display for CacheControl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut directives = Vec::new();
+        match self.visibility {
+            Some(Visibility::Public) => directives.push("public".to_string()),
+            Some(Visibility::Private) => directives.push("private".to_string()),
+            None => {}
+        }
+        if self.no_store {
+            directives.push("no-store".to_string());
+        }
+        if self.no_cache {
+            directives.push("no-cache".to_string());
+        }
+        if let Some(max_age) = self.max_age {
+            directives.push(format!("max-age={}", max_age.as_secs()));
+        }
+        if let Some(s_maxage) = self.s_maxage {
+            directives.push(format!("s-maxage={}", s_maxage.as_secs()));
+        }
+        if self.immutable {
+            directives.push("immutable".to_string());
+        }
+        write!(f, "{}", directives.join(", "))
+    }
+}
+
+/// Set the `Cache-Control` header on a response returned by a route with
+/// [caching directives](crate::blueprint::router::Route::cache_control),
+/// unless the response already includes one.
+///
+/// If the response already includes a `Cache-Control` header, it is returned unchanged:
+/// your request handler (or one of your middlewares) has the final say.
+///
+/// You'll never have to invoke this function yourself: Pavex's generated code calls it
+/// right after the processing pipeline of the route returns.
+#[doc(hidden)]
+pub fn ensure_cache_control(mut response: Response, value: HeaderValue) -> Response {
+    if !response.headers().contains_key(CACHE_CONTROL) {
+        response.headers_mut().insert(CACHE_CONTROL, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::http::header::{HeaderValue, CACHE_CONTROL};
+    use crate::response::Response;
+
+    use super::{ensure_cache_control, CacheControl};
+
+    #[test]
+    fn directives_are_rendered_in_a_stable_order() {
+        let directives = CacheControl::public()
+            .immutable()
+            .s_maxage(Duration::from_secs(60))
+            .max_age(Duration::from_secs(3600));
+        assert_eq!(
+            directives.to_string(),
+            "public, max-age=3600, s-maxage=60, immutable"
+        );
+    }
+
+    #[test]
+    fn standalone_directives() {
+        assert_eq!(CacheControl::private().to_string(), "private");
+        assert_eq!(CacheControl::no_store().to_string(), "no-store");
+        assert_eq!(
+            CacheControl::no_cache()
+                .max_age(Duration::from_millis(1500))
+                .to_string(),
+            "no-cache, max-age=1"
+        );
+    }
+
+    #[test]
+    fn the_header_is_set_if_missing() {
+        let value = CacheControl::no_store().header_value();
+        let response = ensure_cache_control(Response::ok(), value);
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+    }
+
+    #[test]
+    fn an_existing_header_is_left_untouched() {
+        let response =
+            Response::ok().insert_header(CACHE_CONTROL, HeaderValue::from_static("private"));
+        let response = ensure_cache_control(response, CacheControl::public().header_value());
+        assert_eq!(response.headers().get_all(CACHE_CONTROL).iter().count(), 1);
+        assert_eq!(response.headers()[CACHE_CONTROL], "private");
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! Check out the [`Response`] type for more details.
 pub use body::body_::ResponseBody;
+pub use cache_control::{ensure_cache_control, CacheControl};
 pub use into_response::IntoResponse;
 pub use response_::{Response, ResponseHead};
+pub use sender::{ResponseSender, ResponseSenderUnavailable};
 
 pub mod body;
+mod cache_control;
 mod into_response;
 mod response_;
+pub(crate) mod sender;
@@ -1,11 +1,12 @@
 use bytes::Bytes;
-use http::header::CONTENT_TYPE;
+use http::header::{CONTENT_TYPE, VARY};
 use http_body_util::Empty;
 
 use crate::http::StatusCode;
-use crate::http::{HeaderMap, Version};
+use crate::http::{HeaderMap, HeaderName, HeaderValue, Version};
 
 use super::body::raw::RawBody;
+use super::body::trailers::WithTrailers;
 use super::body::TypedBody;
 use super::ResponseBody;
 
@@ -221,6 +222,65 @@ impl Response {
         self
     }
 
+    /// Add a header name to the `Vary` header of the [`Response`].
+    ///
+    /// Use it whenever the response content depends on the value of a request header
+    /// (e.g. `Accept-Encoding` for compression, `Accept-Language` for localization):
+    /// caches rely on `Vary` to avoid serving a response to clients that should have
+    /// received a different representation.
+    ///
+    /// Unlike [`append_header`](Response::append_header) and
+    /// [`insert_header`](Response::insert_header), it merges the new header name with the ones
+    /// that are already listed:
+    ///
+    /// - all values are collapsed into a single comma-separated `Vary` header;
+    /// - a header name is never listed twice (header names are compared case-insensitively);
+    /// - if the `Vary` header is set to `*`, it is left untouched. Adding `*` replaces all
+    ///   other header names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::http::header::{ACCEPT_ENCODING, ACCEPT_LANGUAGE, VARY};
+    /// use pavex::response::Response;
+    ///
+    /// let response = Response::ok()
+    ///     .append_vary(ACCEPT_ENCODING)
+    ///     .append_vary(ACCEPT_LANGUAGE)
+    ///     // Already listed, it won't be added again.
+    ///     .append_vary(ACCEPT_ENCODING);
+    ///
+    /// let values: Vec<_> = response.headers().get_all(VARY).iter().collect();
+    /// assert_eq!(values.len(), 1);
+    /// assert_eq!(values[0], "accept-encoding, accept-language");
+    /// ```
+    pub fn append_vary(mut self, header_name: HeaderName) -> Self {
+        let headers = self.inner.headers_mut();
+        // We work on raw bytes: values that aren't valid UTF-8 must be preserved as well.
+        let mut names: Vec<Vec<u8>> = Vec::new();
+        let new_names = headers
+            .get_all(VARY)
+            .iter()
+            .flat_map(|value| value.as_bytes().split(|b| *b == b','))
+            .chain(std::iter::once(header_name.as_str().as_bytes()));
+        for name in new_names {
+            let name = trim_ascii_whitespace(name).to_ascii_lowercase();
+            if name.is_empty() || names.contains(&name) {
+                continue;
+            }
+            names.push(name);
+        }
+        let value = if names.iter().any(|name| name == b"*") {
+            HeaderValue::from_static("*")
+        } else {
+            // All the names are either valid header names or fragments of
+            // a valid header value, their concatenation is a valid header value.
+            HeaderValue::from_bytes(&names.join(&b", "[..])).expect("Invalid `Vary` header value")
+        };
+        headers.insert(VARY, value);
+        self
+    }
+
     /// Set the [`Response`] body.
     ///
     /// The provided body must implement the [`TypedBody`] trait.  
@@ -302,6 +362,51 @@ impl Response {
         http::Response::from_parts(head, ResponseBody::new(body)).into()
     }
 
+    /// Attach trailers to the [`Response`]—a set of headers sent after the body.
+    ///
+    /// Trailers are useful to transmit metadata that is only known once the body has been
+    /// fully generated (e.g. a checksum or the status of a gRPC call).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pavex::response::Response;
+    /// use pavex::http::{HeaderMap, HeaderName, HeaderValue};
+    ///
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert(
+    ///     HeaderName::from_static("grpc-status"),
+    ///     HeaderValue::from_static("0"),
+    /// );
+    /// let response = Response::ok()
+    ///     .set_typed_body("Hello, world!")
+    ///     // Trailers are attached to the current body:
+    ///     // set the body first!
+    ///     .set_trailers(trailers);
+    /// ```
+    ///
+    /// # Ordering
+    ///
+    /// Trailers are attached to the body that is currently set on the [`Response`].
+    /// If you replace the body afterwards (e.g. via [`set_typed_body`](Response::set_typed_body)
+    /// or [`set_raw_body`](Response::set_raw_body)), the trailers are discarded as well.
+    ///
+    /// If the body already emits trailers of its own, the two sets are merged.
+    /// The values passed to this method take precedence.
+    ///
+    /// # HTTP/1.1
+    ///
+    /// Trailers are a first-class citizen in HTTP/2: they are always sent to the caller.
+    /// That's not the case for HTTP/1.1: trailers can only be sent alongside a body
+    /// that uses chunked transfer encoding, and most clients ignore them.
+    /// Pavex doesn't send trailers on HTTP/1.1 connections: the response body is sent as usual,
+    /// the trailers are silently discarded.
+    pub fn set_trailers(self, trailers: HeaderMap) -> Response {
+        let (head, body) = self.inner.into_parts();
+        let body = ResponseBody::new(WithTrailers::new(body, trailers));
+        http::Response::from_parts(head, body).into()
+    }
+
     /// Get a mutable reference to the [`Response`] body.
     pub fn body_mut(&mut self) -> &mut ResponseBody {
         self.inner.body_mut()
@@ -588,3 +693,86 @@ impl Response {
     shorthand!(INSUFFICIENT_STORAGE);
     shorthand!(LOOP_DETECTED);
 }
+
+/// Strip leading and trailing ASCII whitespace from a header value fragment.
+fn trim_ascii_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::IntoFuture;
+
+    use crate::http::header::{ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, VARY};
+    use crate::http::HeaderValue;
+    use crate::middleware::Next;
+    use crate::response::Response;
+
+    async fn compression<C: IntoFuture<Output = Response>>(next: Next<C>) -> Response {
+        next.into_future().await.append_vary(ACCEPT_ENCODING)
+    }
+
+    async fn localization<C: IntoFuture<Output = Response>>(next: Next<C>) -> Response {
+        next.into_future().await.append_vary(ACCEPT_LANGUAGE)
+    }
+
+    fn vary(response: &Response) -> Vec<&str> {
+        response
+            .headers()
+            .get_all(VARY)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn middlewares_merge_vary_headers() {
+        let handler = async { Response::ok().append_vary(ACCEPT) };
+        let response = compression(Next::new(localization(Next::new(compression(Next::new(
+            handler,
+        ))))))
+        .await;
+        assert_eq!(
+            vary(&response),
+            ["accept, accept-encoding, accept-language"]
+        );
+    }
+
+    #[test]
+    fn pre_existing_vary_values_are_merged() {
+        let response = Response::ok()
+            .append_header(VARY, "Accept-Encoding".parse().unwrap())
+            .append_header(VARY, "origin, Accept".parse().unwrap())
+            .append_vary(ACCEPT);
+        assert_eq!(vary(&response), ["accept-encoding, origin, accept"]);
+    }
+
+    #[test]
+    fn non_utf8_vary_values_are_preserved() {
+        let response = Response::ok()
+            .append_header(VARY, HeaderValue::from_bytes(b"x-caf\xe9").unwrap())
+            .append_vary(ACCEPT);
+        assert_eq!(response.headers()[VARY].as_bytes(), b"x-caf\xe9, accept");
+    }
+
+    #[test]
+    fn wildcard_takes_precedence() {
+        let response = Response::ok()
+            .append_header(VARY, "*".parse().unwrap())
+            .append_vary(ACCEPT);
+        assert_eq!(vary(&response), ["*"]);
+
+        let response = Response::ok()
+            .append_vary(ACCEPT)
+            .append_vary("*".parse().unwrap());
+        assert_eq!(vary(&response), ["*"]);
+    }
+}
@@ -0,0 +1,308 @@
+use std::cell::Cell;
+
+use tokio::sync::oneshot;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::response::Response;
+
+tokio::task_local! {
+    /// The sender for the request that is currently being processed by the worker.
+    ///
+    /// It's `None` if it has already been taken.
+    static RESPONSE_SENDER: Cell<Option<oneshot::Sender<Response>>>;
+}
+
+/// Take over the response flow for the current request.
+///
+/// `ResponseSender` is a low-level escape hatch for protocols that Pavex doesn't model.
+/// It lets a request handler send the response to the client **directly**, bypassing the
+/// usual flow: the response is written to the connection as soon as it's sent, rather than
+/// when the handler (and every middleware wrapping it) returns.
+///
+/// Combined with [`RawIncomingBody`], it gives you full control over the exchange: you own
+/// the incoming byte stream as well as the outgoing response.
+///
+/// **You should rarely need this.** Returning a [`Response`] from your handler is the right
+/// choice in the overwhelming majority of cases—it's the only way to benefit from the
+/// guarantees listed in the [contract](#contract) section below.
+///
+/// # Sections
+///
+/// - [Example](#example)
+/// - [Installation](#installation)
+/// - [Contract](#contract)
+/// - [Interaction with middlewares](#interaction-with-middlewares)
+/// - [Limitations](#limitations)
+///
+/// # Example
+///
+/// A handler that streams the incoming body back to the caller, chunk by chunk:
+///
+/// ```rust
+/// use pavex::request::body::RawIncomingBody;
+/// use pavex::response::{Response, ResponseSender};
+///
+/// pub async fn echo(body: RawIncomingBody, sender: ResponseSender) -> Response {
+///     // The response is written to the connection right away:
+///     // the incoming body is streamed back as it comes in.
+///     // `send` fails if the client went away—there is nobody to talk to.
+///     let _ = sender.send(Response::ok().set_raw_body(body));
+///     // The handler must still return a response to satisfy the type checker,
+///     // but it's discarded.
+///     Response::no_content()
+/// }
+/// ```
+///
+/// # Installation
+///
+/// Register the [default constructor](ResponseSender::extract) and
+/// [error handler](ResponseSenderUnavailable::into_response) for `ResponseSender`
+/// in your `Blueprint`:
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{router::POST, Blueprint};
+/// use pavex::response::ResponseSender;
+/// # pub async fn echo() -> pavex::response::Response { todo!() }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     ResponseSender::register(&mut bp);
+///     bp.route(POST, "/echo", f!(crate::echo));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// # Contract
+///
+/// There is a single `ResponseSender` for each incoming request.
+/// The [constructor](ResponseSender::extract) hands it out the first time it's invoked
+/// for a request and fails afterwards.
+///
+/// The sender can be used at most once, since [`ResponseSender::send`] consumes it.
+/// Once a response has been sent:
+///
+/// - Pavex writes it to the connection, as is;
+/// - the rest of the request processing pipeline keeps running, as a detached task on the
+///   same worker thread. It runs to completion, unless the worker is shut down;
+/// - the response returned by the pipeline is discarded.
+///
+/// If the sender is dropped without sending a response, Pavex falls back to the usual
+/// flow: the response returned by the pipeline is sent to the client.
+///
+/// Taking over the response flow means taking over its responsibilities:
+///
+/// - the [`RawIncomingBody`] has no safeguards—enforce your own size limits if you're buffering it;
+/// - the response hook is applied to the discarded response, not to the one you sent;
+/// - the status code recorded by your telemetry middlewares is the one of the discarded response.
+///
+/// # Interaction with middlewares
+///
+/// Wrapping middlewares (see [`Blueprint::wrap`]) don't see the response you sent.
+///
+/// - They wrap the future of your handler, as usual.
+///   If they don't invoke [`Next`]—e.g. they return an error response early—your handler is
+///   never invoked and the sender is never used.
+/// - If they drop the future of your handler after the response has been sent (e.g. a timeout),
+///   the response is not affected but its body is truncated if your handler was still
+///   producing it (e.g. via a channel).
+/// - They act on the response returned by your handler, which is then discarded.
+///   Headers they would normally add (e.g. CORS headers or a request id) are **not** added to
+///   the response you sent: add them yourself if you need them.
+///
+/// # Limitations
+///
+/// The sender is bound to the task that processes the incoming request.
+/// [`ResponseSender::extract`] fails if invoked from a task spawned by your handler,
+/// or if the request isn't being served by Pavex's [`Server`]
+/// (e.g. when invoking your handler directly in a test).
+/// Once extracted, the sender can be moved freely—e.g. to another task.
+///
+/// Internal requests issued via a [`Dispatcher`] can't take over the response flow:
+/// [`ResponseSender::extract`] fails while processing them, even if the sender of the
+/// request that spawned them hasn't been extracted yet.
+///
+/// `ResponseSender` doesn't give you access to the underlying connection: protocol
+/// upgrades are not supported.
+///
+/// [`RawIncomingBody`]: crate::request::body::RawIncomingBody
+/// [`Server`]: crate::server::Server
+/// [`Dispatcher`]: crate::router::Dispatcher
+/// [`Blueprint::wrap`]: crate::blueprint::Blueprint::wrap
+/// [`Next`]: crate::middleware::Next
+#[doc(alias = "Takeover")]
+pub struct ResponseSender {
+    inner: oneshot::Sender<Response>,
+}
+
+impl std::fmt::Debug for ResponseSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseSender").finish_non_exhaustive()
+    }
+}
+
+impl ResponseSender {
+    /// The default constructor for [`ResponseSender`].
+    ///
+    /// It fails if the sender for the current request has already been extracted,
+    /// or if the request isn't being served by Pavex's [`Server`](crate::server::Server).
+    pub fn extract() -> Result<Self, ResponseSenderUnavailable> {
+        RESPONSE_SENDER
+            .try_with(|slot| slot.take())
+            .ok()
+            .flatten()
+            .map(|inner| Self { inner })
+            .ok_or(ResponseSenderUnavailable)
+    }
+
+    /// Send `response` to the client, bypassing the rest of the response flow.
+    ///
+    /// It returns the response back if the client is no longer waiting for it—e.g.
+    /// the connection has been closed.
+    ///
+    /// Check out [`ResponseSender`]'s documentation for the full contract.
+    pub fn send(self, response: Response) -> Result<(), Response> {
+        self.inner.send(response)
+    }
+
+    /// Register the [default constructor](ResponseSender::extract)
+    /// and [error handler](ResponseSenderUnavailable::into_response)
+    /// for [`ResponseSender`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::response::ResponseSender::extract),
+            Lifecycle::RequestScoped,
+        )
+        .error_handler(f!(
+            pavex::response::ResponseSenderUnavailable::into_response
+        ))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The response sender is not available: it has already been extracted for this request, \
+    or the request is not being served by Pavex's `Server`"
+)]
+#[non_exhaustive]
+/// The error returned by [`ResponseSender::extract`] when the sender is not available.
+pub struct ResponseSenderUnavailable;
+
+impl ResponseSenderUnavailable {
+    /// Convert a [`ResponseSenderUnavailable`] into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error` to the caller.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
+
+/// Drive `future` to completion without a [`ResponseSender`] in scope.
+///
+/// Internal requests are processed within this scope, to prevent them from taking over
+/// the response flow of the request that spawned them.
+pub(crate) async fn without_sender<F>(future: F) -> F::Output
+where
+    F: std::future::Future,
+{
+    RESPONSE_SENDER.scope(Cell::new(None), future).await
+}
+
+#[cfg(feature = "server")]
+/// Drive the request processing pipeline to completion, honouring the [`ResponseSender`]
+/// of the current request if the pipeline decides to use it.
+///
+/// It must be invoked from within a `LocalSet`.
+pub(crate) async fn run_pipeline<F>(pipeline: F) -> Response
+where
+    F: std::future::Future<Output = Response> + 'static,
+{
+    use std::future::{poll_fn, Future};
+    use std::task::Poll;
+
+    enum Outcome {
+        Sent(Response),
+        Returned(Response),
+    }
+
+    let (sender, receiver) = oneshot::channel();
+    let mut pipeline = Box::pin(RESPONSE_SENDER.scope(Cell::new(Some(sender)), pipeline));
+    let mut receiver = Some(receiver);
+    let outcome = poll_fn(|cx| {
+        if let Some(r) = receiver.as_mut() {
+            match std::pin::Pin::new(r).poll(cx) {
+                Poll::Ready(Ok(response)) => return Poll::Ready(Outcome::Sent(response)),
+                // The sender was dropped without being used.
+                Poll::Ready(Err(_)) => receiver = None,
+                Poll::Pending => {}
+            }
+        }
+        pipeline.as_mut().poll(cx).map(Outcome::Returned)
+    })
+    .await;
+    match outcome {
+        Outcome::Sent(response) => {
+            // The rest of the pipeline keeps running, but nobody is waiting for its response.
+            tokio::task::spawn_local(async move {
+                let _ = pipeline.await;
+            });
+            response
+        }
+        Outcome::Returned(response) => {
+            // The pipeline might have sent a response right before returning.
+            match receiver.map(|mut r| r.try_recv()) {
+                Some(Ok(sent)) => sent,
+                _ => response,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::future::Future;
+    use std::pin::Pin;
+
+    use tokio::sync::oneshot;
+
+    use super::{ResponseSender, RESPONSE_SENDER};
+    use crate::http::StatusCode;
+    use crate::request::body::RawIncomingBody;
+    use crate::request::RequestHead;
+    use crate::response::Response;
+    use crate::router::Dispatcher;
+
+    fn take_over(
+        _request_head: RequestHead,
+        _body: RawIncomingBody,
+        _depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Response>>> {
+        Box::pin(async {
+            match ResponseSender::extract() {
+                Ok(_) => Response::ok(),
+                Err(e) => e.into_response(),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn internal_requests_cannot_take_over_the_response_flow() {
+        let dispatcher = Dispatcher::new(0, take_over);
+        let (sender, _receiver) = oneshot::channel();
+        RESPONSE_SENDER
+            .scope(Cell::new(Some(sender)), async {
+                let response = dispatcher
+                    .dispatch(RequestHead::for_tests(), "")
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+                // The sender of the outer request is still there.
+                assert!(ResponseSender::extract().is_ok());
+            })
+            .await;
+    }
+}
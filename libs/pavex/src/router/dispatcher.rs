@@ -0,0 +1,205 @@
+use std::fmt::Formatter;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use bytes::Bytes;
+
+use crate::request::body::RawIncomingBody;
+use crate::request::RequestHead;
+use crate::response::sender::without_sender;
+use crate::response::Response;
+
+use super::errors::DispatchDepthExceeded;
+
+type DispatchFn =
+    dyn Fn(RequestHead, RawIncomingBody, usize) -> Pin<Box<dyn Future<Output = Response>>>;
+
+/// Dispatch a request to another route of the same application, without
+/// going through the network.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::http::{HeaderMap, Method, Version};
+/// use pavex::request::RequestHead;
+/// use pavex::response::Response;
+/// use pavex::router::Dispatcher;
+///
+/// /// Aggregate the responses of two other routes into a single payload.
+/// pub async fn dashboard(dispatcher: &Dispatcher) -> Response {
+///     let mut body = String::new();
+///     for path in ["/profile", "/notifications"] {
+///         let request_head = RequestHead {
+///             method: Method::GET,
+///             target: path.parse().unwrap(),
+///             version: Version::HTTP_11,
+///             headers: HeaderMap::new(),
+///         };
+///         let Ok(response) = dispatcher.dispatch(request_head, "").await else {
+///             return Response::internal_server_error();
+///         };
+///         // [...] Merge `response` into `body`
+///     }
+///     Response::ok().set_typed_body(body)
+/// }
+/// ```
+///
+/// # Framework primitive
+///
+/// `Dispatcher` is a framework primitive—you don't need to register any constructor
+/// with [`Blueprint`] to use it in your application.
+///
+/// # Middlewares
+///
+/// An internal request goes through the same processing pipeline as a request received
+/// from the network: it is routed according to its method and path, and it is processed
+/// by all the middlewares that apply to the route it matches, as well as the route's
+/// request handler.
+/// Request-scoped components are built from scratch for the internal request—they are **not**
+/// shared with the request that triggered the dispatch.
+///
+/// # Recursion
+///
+/// A route can dispatch to another route which, in turn, dispatches to a third route, and so on.
+/// To protect your application against infinite recursion (e.g. a route dispatching to itself),
+/// the nesting depth is capped at [`Dispatcher::MAX_DEPTH`].
+/// [`Dispatcher::dispatch`] returns a [`DispatchDepthExceeded`] error if the limit is breached.
+///
+/// [`Blueprint`]: crate::blueprint::Blueprint
+#[derive(Clone)]
+pub struct Dispatcher {
+    depth: usize,
+    dispatch: Rc<DispatchFn>,
+}
+
+impl Dispatcher {
+    /// The maximum number of nested internal dispatches allowed
+    /// while processing a single request received from the network.
+    pub const MAX_DEPTH: usize = 8;
+
+    /// Create a new [`Dispatcher`].
+    ///
+    /// You'll never have to invoke this method yourself: Pavex's generated code takes care
+    /// of it, passing its own routing function as `dispatch`.
+    #[doc(hidden)]
+    pub fn new<F>(depth: usize, dispatch: F) -> Self
+    where
+        F: Fn(RequestHead, RawIncomingBody, usize) -> Pin<Box<dyn Future<Output = Response>>>
+            + 'static,
+    {
+        Self {
+            depth,
+            dispatch: Rc::new(dispatch),
+        }
+    }
+
+    /// The number of internal dispatches that led to the request currently being processed.
+    ///
+    /// It's `0` for requests received from the network.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Process an internal request and return the response computed by the application.
+    ///
+    /// It fails if the recursion limit has been reached—see [`Dispatcher::MAX_DEPTH`].
+    pub async fn dispatch(
+        &self,
+        request_head: RequestHead,
+        body: impl Into<Bytes>,
+    ) -> Result<Response, DispatchDepthExceeded> {
+        let depth = self.depth + 1;
+        if depth > Self::MAX_DEPTH {
+            return Err(DispatchDepthExceeded {
+                max_depth: Self::MAX_DEPTH,
+            });
+        }
+        let body = RawIncomingBody::from(body.into());
+        Ok(without_sender((self.dispatch)(request_head, body, depth)).await)
+    }
+}
+
+impl std::fmt::Debug for Dispatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field("depth", &self.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    use http_body_util::BodyExt;
+
+    use crate::request::body::RawIncomingBody;
+    use crate::request::RequestHead;
+    use crate::response::Response;
+
+    use super::Dispatcher;
+
+    fn get(path: &str) -> RequestHead {
+        RequestHead::for_tests().with_target(path)
+    }
+
+    async fn body_text(response: Response) -> String {
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    /// A hand-rolled version of the routing function that Pavex generates.
+    fn route(
+        request_head: RequestHead,
+        _body: RawIncomingBody,
+        depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Response>>> {
+        Box::pin(async move {
+            let dispatcher = Dispatcher::new(depth, route);
+            match request_head.target.path() {
+                "/hello" => Response::ok().set_typed_body("Hello"),
+                "/world" => Response::ok().set_typed_body("world"),
+                "/merged" => {
+                    let mut merged = Vec::new();
+                    for path in ["/hello", "/world"] {
+                        let response = dispatcher.dispatch(get(path), "").await.unwrap();
+                        merged.push(body_text(response).await);
+                    }
+                    Response::ok().set_typed_body(merged.join(", "))
+                }
+                "/loop" => match dispatcher.dispatch(get("/loop"), "").await {
+                    Ok(response) => response,
+                    Err(e) => e.into_response(),
+                },
+                _ => Response::not_found(),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn a_handler_can_merge_the_responses_of_other_routes() {
+        let dispatcher = Dispatcher::new(0, route);
+        let response = dispatcher.dispatch(get("/merged"), "").await.unwrap();
+        assert_eq!(response.status(), crate::http::StatusCode::OK);
+        assert_eq!(body_text(response).await, "Hello, world");
+    }
+
+    #[tokio::test]
+    async fn infinite_recursion_is_stopped() {
+        let dispatcher = Dispatcher::new(0, route);
+        let response = dispatcher.dispatch(get("/loop"), "").await.unwrap();
+        assert_eq!(
+            response.status(),
+            crate::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        let dispatcher = Dispatcher::new(Dispatcher::MAX_DEPTH, route);
+        let Err(err) = dispatcher.dispatch(get("/hello"), "").await else {
+            panic!("Expected the maximum dispatch depth to be enforced");
+        };
+        insta::assert_display_snapshot!(err, @"The maximum depth for internal request dispatching (8) has been exceeded.");
+    }
+}
@@ -0,0 +1,25 @@
+//! Errors that can arise when dispatching a request internally.
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The maximum depth for internal request dispatching ({max_depth}) has been exceeded.")]
+#[non_exhaustive]
+/// The error returned by [`Dispatcher::dispatch`] when the maximum nesting depth for internal
+/// requests has been exceeded.
+/// It usually points at a route that (directly or indirectly) dispatches to itself.
+///
+/// [`Dispatcher::dispatch`]: super::Dispatcher::dispatch
+pub struct DispatchDepthExceeded {
+    /// The maximum nesting depth allowed for internal requests.
+    pub max_depth: usize,
+}
+
+impl DispatchDepthExceeded {
+    /// Convert a [`DispatchDepthExceeded`] error into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error`: it's a bug in the server,
+    /// the client has no way to fix it.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
@@ -21,3 +21,60 @@ pub async fn default_fallback(allowed_methods: &AllowedMethods) -> Response {
         Response::not_found()
     }
 }
+
+/// Set the `Allow` header on a response returned by a
+/// [`405 Method Not Allowed` handler](crate::blueprint::Blueprint::method_not_allowed),
+/// unless the handler has already set it.
+///
+/// The header is set to the list of methods allowed for the matched route path—see
+/// [`AllowedMethods::allow_header_value`].  
+/// If the response already includes an `Allow` header, it is returned unchanged: the handler
+/// can override the value that Pavex would have set.
+///
+/// You'll never have to invoke this function yourself: Pavex's generated code calls it
+/// right after your `405 Method Not Allowed` handler returns.
+#[doc(hidden)]
+pub fn ensure_allow_header(mut response: Response, allowed_methods: &AllowedMethods) -> Response {
+    if response.headers().contains_key(ALLOW) {
+        return response;
+    }
+    if let Some(header_value) = allowed_methods.allow_header_value() {
+        response.headers_mut().insert(ALLOW, header_value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::http::header::{HeaderValue, ALLOW};
+    use crate::http::Method;
+    use crate::response::Response;
+    use crate::router::{AllowedMethods, MethodAllowList};
+
+    use super::ensure_allow_header;
+
+    fn get_and_post() -> AllowedMethods {
+        MethodAllowList::from_iter([Method::GET, Method::POST]).into()
+    }
+
+    #[test]
+    fn the_allow_header_is_set_if_missing() {
+        let response = ensure_allow_header(Response::method_not_allowed(), &get_and_post());
+        assert_eq!(response.headers()[ALLOW], "GET,POST");
+    }
+
+    #[test]
+    fn the_handler_can_override_the_allow_header() {
+        let response =
+            Response::method_not_allowed().insert_header(ALLOW, HeaderValue::from_static("GET"));
+        let response = ensure_allow_header(response, &get_and_post());
+        assert_eq!(response.headers().get_all(ALLOW).iter().count(), 1);
+        assert_eq!(response.headers()[ALLOW], "GET");
+    }
+
+    #[test]
+    fn nothing_is_set_if_all_methods_are_allowed() {
+        let response = ensure_allow_header(Response::not_found(), &AllowedMethods::All);
+        assert!(!response.headers().contains_key(ALLOW));
+    }
+}
@@ -1,6 +1,9 @@
 //! Dispatch requests to the appropriate handler.
 pub use allowed_methods::{AllowedMethods, MethodAllowList};
-pub use fallback::default_fallback;
+pub use dispatcher::Dispatcher;
+pub use fallback::{default_fallback, ensure_allow_header};
 
 mod allowed_methods;
+mod dispatcher;
+pub mod errors;
 mod fallback;
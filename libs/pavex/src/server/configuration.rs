@@ -8,6 +8,10 @@ use std::num::NonZeroUsize;
 pub struct ServerConfiguration {
     /// Number of worker threads to spawn.
     pub(crate) n_workers: NonZeroUsize,
+    /// The maximum size of the header section of an incoming request, in bytes.
+    pub(crate) max_header_bytes: NonZeroUsize,
+    /// The maximum number of header fields in an incoming request.
+    pub(crate) max_header_count: NonZeroUsize,
 }
 
 impl Default for ServerConfiguration {
@@ -31,7 +35,11 @@ impl ServerConfiguration {
                 fallback
             }
         };
-        Self { n_workers }
+        Self {
+            n_workers,
+            max_header_bytes: NonZeroUsize::new(64 * 1024).unwrap(),
+            max_header_count: NonZeroUsize::new(100).unwrap(),
+        }
     }
 
     /// Set the number of worker threads to be spawned.
@@ -60,4 +68,66 @@ impl ServerConfiguration {
     pub fn get_n_workers(&self) -> NonZeroUsize {
         self.n_workers
     }
+
+    /// Set the maximum size of the header section of an incoming request, in bytes.
+    /// It must be greater than 0.
+    ///
+    /// The size is computed as the sum of the lengths of all header names and values.
+    /// Requests that exceed the limit are rejected with a `431 Request Header Fields Too Large`
+    /// response, before your routing logic is invoked.
+    ///
+    /// # Default
+    ///
+    /// 64 KiB.
+    ///
+    /// # Header-read timeout
+    ///
+    /// The limit is enforced while the request head is being read: Pavex stops reading an
+    /// oversized header section shortly after the limit is crossed, without waiting for the
+    /// rest of it.
+    /// A client that sends a small header section very slowly is not affected by this limit—it
+    /// can only be stopped by a timeout on reading the request head, which Pavex's
+    /// [`Server`](super::Server) doesn't enforce at the moment.
+    #[track_caller]
+    pub fn set_max_header_bytes(mut self, n: usize) -> Self {
+        assert!(
+            n > 0,
+            "The maximum size of the header section must be greater than 0"
+        );
+        self.max_header_bytes = NonZeroUsize::new(n).unwrap();
+        self
+    }
+
+    /// Get the maximum size of the header section of an incoming request, in bytes.
+    pub fn get_max_header_bytes(&self) -> NonZeroUsize {
+        self.max_header_bytes
+    }
+
+    /// Set the maximum number of header fields in an incoming request.
+    /// It must be greater than 0.
+    ///
+    /// Requests that exceed the limit are rejected with a `431 Request Header Fields Too Large`
+    /// response, before your routing logic is invoked.
+    ///
+    /// # Default
+    ///
+    /// 100.
+    ///
+    /// HTTP/1.1 connections can't carry more than 100 header fields per request, regardless of
+    /// this setting: `hyper`, the HTTP implementation used by Pavex, rejects them with a `431`
+    /// while parsing. Raising the limit above 100 only has an effect on HTTP/2 connections.
+    #[track_caller]
+    pub fn set_max_header_count(mut self, n: usize) -> Self {
+        assert!(
+            n > 0,
+            "The maximum number of header fields must be greater than 0"
+        );
+        self.max_header_count = NonZeroUsize::new(n).unwrap();
+        self
+    }
+
+    /// Get the maximum number of header fields in an incoming request.
+    pub fn get_max_header_count(&self) -> NonZeroUsize {
+        self.max_header_count
+    }
 }
@@ -103,7 +103,6 @@ struct Acceptor<HandlerFuture, ApplicationState> {
     command_inbox: tokio::sync::mpsc::Receiver<ServerCommand>,
     incoming: Vec<IncomingStream>,
     worker_handles: Vec<WorkerHandle>,
-    #[allow(dead_code)]
     config: ServerConfiguration,
     next_worker: usize,
     max_queue_length: usize,
@@ -137,8 +136,13 @@ where
         let n_workers = config.n_workers.get();
         let mut worker_handles = Vec::with_capacity(n_workers);
         for i in 0..n_workers {
-            let (worker, handle) =
-                Worker::new(i, max_queue_length, handler, application_state.clone());
+            let (worker, handle) = Worker::new(
+                i,
+                max_queue_length,
+                &config,
+                handler,
+                application_state.clone(),
+            );
             worker_handles.push(handle);
             // TODO: should we panic here?
             worker.spawn().expect("Failed to spawn worker thread");
@@ -181,7 +185,7 @@ where
             mut next_worker,
             mut worker_handles,
             incoming,
-            config: _,
+            config,
             max_queue_length,
             handler,
             application_state,
@@ -274,6 +278,7 @@ where
                             let (worker, worker_handle) = Worker::new(
                                 worker_id,
                                 max_queue_length,
+                                &config,
                                 handler,
                                 application_state.clone(),
                             );
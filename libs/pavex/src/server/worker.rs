@@ -7,7 +7,10 @@ use hyper_util::rt::TokioIo;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc::error::TrySendError;
 
-use crate::server::ShutdownMode;
+use crate::connection::LocalAddr;
+use crate::response::sender::run_pipeline;
+use crate::response::Response;
+use crate::server::{ServerConfiguration, ShutdownMode};
 
 /// A handle to dispatch incoming connections to a worker thread.
 pub(super) struct WorkerHandle {
@@ -98,6 +101,7 @@ pub(super) struct Worker<HandlerFuture, ApplicationState> {
     shutdown_inbox: tokio::sync::mpsc::UnboundedReceiver<ShutdownWorkerCommand>,
     handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
     application_state: ApplicationState,
+    header_limits: HeaderLimits,
     id: usize,
 }
 
@@ -113,6 +117,7 @@ where
     pub(super) fn new(
         id: usize,
         max_queue_length: usize,
+        config: &ServerConfiguration,
         handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
         application_state: ApplicationState,
     ) -> (Self, WorkerHandle) {
@@ -123,6 +128,7 @@ where
             shutdown_inbox,
             handler,
             application_state,
+            header_limits: HeaderLimits::new(config),
             id,
         };
         let handle = WorkerHandle {
@@ -158,6 +164,7 @@ where
             mut shutdown_inbox,
             handler,
             application_state,
+            header_limits,
             id,
         } = self;
         'event_loop: loop {
@@ -166,7 +173,12 @@ where
                     .await;
             match message {
                 WorkerInboxMessage::Connection(connection) => {
-                    Self::handle_connection(connection, handler, application_state.clone());
+                    Self::handle_connection(
+                        connection,
+                        handler,
+                        application_state.clone(),
+                        header_limits,
+                    );
                 }
                 WorkerInboxMessage::Shutdown(shutdown) => {
                     let ShutdownWorkerCommand {
@@ -184,6 +196,7 @@ where
                                     connection,
                                     handler,
                                     application_state.clone(),
+                                    header_limits,
                                 );
                             }
 
@@ -220,13 +233,32 @@ where
         connection: TcpStream,
         handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
         application_state: ApplicationState,
+        header_limits: HeaderLimits,
     ) {
+        let local_addr = match connection.local_addr() {
+            Ok(addr) => LocalAddr::new(addr),
+            Err(e) => {
+                tracing::error!(
+                    error.msg = %e,
+                    error.details = ?e,
+                    "Failed to determine the local address of an incoming connection. Dropping it."
+                );
+                return;
+            }
+        };
         // A tiny bit of glue to adapt our handler to hyper's service interface.
-        let handler = hyper::service::service_fn(move |request| {
+        let handler = hyper::service::service_fn(move |mut request: http::Request<_>| {
             let state = application_state.clone();
+            // The local address is the same for all requests received over this connection.
+            request.extensions_mut().insert(local_addr);
+            // Abusive requests are rejected before any routing logic is invoked.
+            let rejection = header_limits.check(request.headers());
             async move {
-                let handler = (handler)(request, state);
-                let response = handler.await;
+                let response = match rejection {
+                    Some(response) => response,
+                    // The handler might take over the response flow via a `ResponseSender`.
+                    None => run_pipeline((handler)(request, state)).await,
+                };
                 let response = hyper::Response::from(response);
                 Ok::<_, hyper::Error>(response)
             }
@@ -238,7 +270,8 @@ where
             let _guard = connection_counter_guard;
             // TODO: expose all the config options for `auto::Builder` through the top-level
             //   `ServerConfiguration` object.
-            let builder = hyper_util::server::conn::auto::Builder::new(LocalExec);
+            let mut builder = hyper_util::server::conn::auto::Builder::new(LocalExec);
+            header_limits.configure(&mut builder);
             let connection = TokioIo::new(connection);
             builder
                 .serve_connection(connection, handler)
@@ -281,6 +314,77 @@ impl From<ShutdownWorkerCommand> for WorkerInboxMessage {
     }
 }
 
+/// The limits on the header section of incoming requests, as specified in [`ServerConfiguration`].
+#[derive(Clone, Copy, Debug)]
+struct HeaderLimits {
+    max_bytes: usize,
+    max_count: usize,
+}
+
+impl HeaderLimits {
+    /// Extra room, on top of the header limits, granted at the protocol level to account for
+    /// the request line (HTTP/1.1) or the pseudo-headers (HTTP/2).
+    const REQUEST_LINE_ALLOWANCE: usize = 16 * 1024;
+    /// The smallest read buffer that `hyper` accepts for HTTP/1.1 connections.
+    const MIN_HTTP1_BUFFER_SIZE: usize = 8 * 1024;
+
+    fn new(config: &ServerConfiguration) -> Self {
+        Self {
+            max_bytes: config.max_header_bytes.get(),
+            max_count: config.max_header_count.get(),
+        }
+    }
+
+    /// Configure `hyper` to stop reading an oversized header section early on, instead of
+    /// buffering it in its entirety.
+    ///
+    /// The protocol-level limits are a coarse upper bound: the precise limits are enforced by
+    /// [`HeaderLimits::check`] once the header section has been parsed.
+    fn configure(&self, builder: &mut hyper_util::server::conn::auto::Builder<LocalExec>) {
+        // HTTP/1.1 frames each header field as `name: value\r\n`.
+        let http1_buffer_size = self
+            .max_bytes
+            .saturating_add(self.max_count.saturating_mul(4))
+            .saturating_add(Self::REQUEST_LINE_ALLOWANCE)
+            .max(Self::MIN_HTTP1_BUFFER_SIZE);
+        builder.http1().max_buf_size(http1_buffer_size);
+        // HTTP/2 accounts for a 32 bytes overhead per header field.
+        let http2_header_list_size = self
+            .max_bytes
+            .saturating_add(self.max_count.saturating_mul(32))
+            .saturating_add(Self::REQUEST_LINE_ALLOWANCE);
+        builder
+            .http2()
+            .max_header_list_size(u32::try_from(http2_header_list_size).unwrap_or(u32::MAX));
+    }
+
+    /// Return a `431 Request Header Fields Too Large` response if the header section
+    /// exceeds the configured limits.
+    fn check(&self, headers: &http::HeaderMap) -> Option<Response> {
+        if headers.len() > self.max_count {
+            tracing::debug!(
+                n_headers = headers.len(),
+                max_header_count = self.max_count,
+                "Rejecting a request with too many header fields"
+            );
+            return Some(Response::request_header_fields_too_large());
+        }
+        let size: usize = headers
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.len())
+            .sum();
+        if size > self.max_bytes {
+            tracing::debug!(
+                header_bytes = size,
+                max_header_bytes = self.max_bytes,
+                "Rejecting a request with an oversized header section"
+            );
+            return Some(Response::request_header_fields_too_large());
+        }
+        None
+    }
+}
+
 /// HTTP2 requires `hyper` to be able to spawn tasks, therefore we need to pass to `hyper`'s
 /// `Server` an executor and a way to perform the spawning.
 ///
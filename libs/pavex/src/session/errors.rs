@@ -0,0 +1,27 @@
+//! Errors that can arise when extracting a [`Session`](super::Session) from an incoming request.
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The session cookie is invalid: it has been tampered with, it was issued using a different key \
+    or its payload doesn't match the expected format."
+)]
+#[non_exhaustive]
+/// The error returned by [`Session::extract`](super::Session::extract) when the session cookie
+/// can't be verified (or decrypted) or its payload can't be deserialized.
+///
+/// It is only returned if [`SessionConfig::on_invalid`] is set to
+/// [`InvalidSessionPolicy::Reject`].
+///
+/// [`SessionConfig::on_invalid`]: super::SessionConfig::on_invalid
+/// [`InvalidSessionPolicy::Reject`]: super::InvalidSessionPolicy::Reject
+pub struct InvalidSession;
+
+impl InvalidSession {
+    /// Convert an [`InvalidSession`] error into an HTTP response.
+    ///
+    /// It returns a `400 Bad Request` to the caller.
+    pub fn into_response(&self) -> Response {
+        Response::bad_request().set_typed_body(format!("{self}"))
+    }
+}
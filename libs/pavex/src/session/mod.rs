@@ -0,0 +1,530 @@
+//! Store per-client state in a signed (or encrypted) cookie.
+//!
+//! Check out [`Session`] for more details.
+//! This module is only available if the `session` feature is enabled, which it is by default.
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::future::IntoFuture;
+use std::rc::Rc;
+
+use cookie::{Cookie, CookieJar, SameSite};
+use http::header::{COOKIE, SET_COOKIE};
+use http::{HeaderMap, HeaderValue};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::middleware::Next;
+use crate::request::RequestHead;
+use crate::response::Response;
+
+use errors::InvalidSession;
+
+pub use cookie::Key;
+
+pub mod errors;
+
+/// A typed session, stored client-side in a signed (or encrypted) cookie.
+///
+/// The session payload, `T`, is serialized as JSON and then signed (or encrypted) using the
+/// [`Key`] in [`SessionConfig`]: clients can't tamper with it without being detected.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Usage](#usage)
+/// - [Missing or invalid sessions](#missing-or-invalid-sessions)
+/// - [When is the cookie written?](#when-is-the-cookie-written)
+///
+/// # Installation
+///
+/// You need to register:
+///
+/// - a constructor for [`SessionConfig`], usually as a singleton;
+/// - [`Session::extract`] as a request-scoped constructor;
+/// - [`Session::flush`] as a wrapping middleware.
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{Blueprint, constructor::{CloningStrategy, Lifecycle}};
+/// use pavex::session::{Key, SessionConfig};
+///
+/// #[derive(Default, serde::Serialize, serde::Deserialize)]
+/// pub struct Cart {
+///     pub items: Vec<String>,
+/// }
+///
+/// pub fn session_config() -> SessionConfig {
+///     // Load the key from your configuration, rather than generating a new one
+///     // every time the application starts.
+///     SessionConfig::new(Key::generate())
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(f!(crate::session_config), Lifecycle::Singleton);
+///     bp.constructor(
+///         f!(pavex::session::Session::<crate::Cart>::extract),
+///         Lifecycle::RequestScoped,
+///     )
+///     // `Session` is a cheap handle: the middleware and the request handler
+///     // share the same underlying state.
+///     .cloning(CloningStrategy::CloneIfNecessary)
+///     .error_handler(f!(pavex::session::errors::InvalidSession::into_response));
+///     bp.wrap(f!(pavex::session::Session::<crate::Cart>::flush));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// The middleware should be registered **before** any route that needs access to the session.
+///
+/// # Usage
+///
+/// Inject `&Session<T>` in your request handlers (or constructors).
+/// Use [`Session::get`] to read the session state and [`Session::get_mut`] to modify it:
+///
+/// ```rust
+/// use pavex::response::Response;
+/// use pavex::session::Session;
+/// # #[derive(Default, serde::Serialize, serde::Deserialize)]
+/// # pub struct Cart { pub items: Vec<String> }
+///
+/// pub fn add_to_cart(session: &Session<Cart>) -> Response {
+///     session.get_mut().items.push("book".into());
+///     Response::ok()
+/// }
+/// ```
+///
+/// # Missing or invalid sessions
+///
+/// If the request doesn't carry a session cookie, the session starts from `T::default()`.
+///
+/// If the cookie is there but it can't be verified (or decrypted), or its payload can't be
+/// deserialized into `T` (e.g. because you changed the definition of `T`), the outcome
+/// depends on [`SessionConfig::on_invalid`]:
+///
+/// - [`InvalidSessionPolicy::Reset`] (the default): the session starts from `T::default()`
+///   and the invalid cookie is overwritten when the response is sent back;
+/// - [`InvalidSessionPolicy::Reject`]: [`Session::extract`] fails with an [`InvalidSession`]
+///   error.
+///
+/// # When is the cookie written?
+///
+/// [`Session::flush`] only attaches a `Set-Cookie` header to the response if the session
+/// state has changed:
+///
+/// - if [`Session::get_mut`] (or [`Session::set`]) was never called, there's nothing to do;
+/// - otherwise, the state is serialized and compared to the payload of the incoming cookie.
+///   The cookie is only written if they differ.
+///
+/// Keep the session state small: browsers usually reject cookies larger than 4 KB.
+pub struct Session<T> {
+    state: Rc<SessionState<T>>,
+}
+
+struct SessionState<T> {
+    value: RefCell<T>,
+    /// Set to `true` when the state might have been modified.
+    touched: Cell<bool>,
+    /// The serialized state, as received from the client.
+    /// `None` if the cookie must be written regardless of the current state.
+    original: Option<String>,
+}
+
+impl<T> Clone for Session<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Session<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session").finish_non_exhaustive()
+    }
+}
+
+impl<T> Session<T>
+where
+    T: Serialize + DeserializeOwned + Default + 'static,
+{
+    /// Extract the session from the cookie attached to the incoming request.
+    ///
+    /// It should be registered as a request-scoped constructor—check out
+    /// [`Session`]'s documentation for more details, including how missing and invalid
+    /// sessions are handled.
+    pub fn extract(
+        request_head: &RequestHead,
+        config: &SessionConfig,
+    ) -> Result<Self, InvalidSession> {
+        let Some(cookie) = find_cookie(&request_head.headers, &config.cookie_name) else {
+            // We serialize the default state to avoid sending back a cookie if the
+            // request handler doesn't actually change it.
+            let value = T::default();
+            let original = serde_json::to_string(&value).ok();
+            return Ok(Self::new(value, original, false));
+        };
+        let decoded = config.open(cookie).and_then(|payload| {
+            let value = serde_json::from_str(&payload).ok()?;
+            Some((value, payload))
+        });
+        match decoded {
+            Some((value, payload)) => Ok(Self::new(value, Some(payload), false)),
+            None => match config.on_invalid {
+                InvalidSessionPolicy::Reset => Ok(Self::new(T::default(), None, true)),
+                InvalidSessionPolicy::Reject => Err(InvalidSession),
+            },
+        }
+    }
+
+    /// A wrapping middleware that writes the session state back to the client, via a
+    /// `Set-Cookie` header, if it has changed.
+    ///
+    /// Check out [`Session`]'s documentation for more details.
+    pub async fn flush<C>(next: Next<C>, config: &SessionConfig, session: Session<T>) -> Response
+    where
+        C: IntoFuture<Output = Response>,
+    {
+        let response = next.into_future().await;
+        let Some(payload) = session.changed_payload() else {
+            return response;
+        };
+        let cookie = config.seal(payload);
+        match HeaderValue::try_from(cookie.to_string()) {
+            Ok(value) => response.append_header(SET_COOKIE, value),
+            Err(e) => {
+                tracing::error!(
+                    error.msg = %e,
+                    error.details = ?e,
+                    "Failed to encode the session cookie as a header value"
+                );
+                response
+            }
+        }
+    }
+
+    fn new(value: T, original: Option<String>, touched: bool) -> Self {
+        Self {
+            state: Rc::new(SessionState {
+                value: RefCell::new(value),
+                touched: Cell::new(touched),
+                original,
+            }),
+        }
+    }
+
+    /// Return the serialized state if it must be sent back to the client.
+    fn changed_payload(&self) -> Option<String> {
+        if !self.state.touched.get() {
+            return None;
+        }
+        let payload = match serde_json::to_string(&*self.state.value.borrow()) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!(
+                    error.msg = %e,
+                    error.details = ?e,
+                    "Failed to serialize the session state"
+                );
+                return None;
+            }
+        };
+        if self.state.original.as_ref() == Some(&payload) {
+            return None;
+        }
+        Some(payload)
+    }
+}
+
+impl<T> Session<T> {
+    /// Get a shared reference to the session state.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the state is currently borrowed via [`Session::get_mut`].
+    pub fn get(&self) -> Ref<'_, T> {
+        self.state.value.borrow()
+    }
+
+    /// Get a mutable reference to the session state.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the state is currently borrowed, via [`Session::get`] or
+    /// [`Session::get_mut`].
+    pub fn get_mut(&self) -> RefMut<'_, T> {
+        self.state.touched.set(true);
+        self.state.value.borrow_mut()
+    }
+
+    /// Replace the session state, returning the previous one.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the state is currently borrowed, via [`Session::get`] or
+    /// [`Session::get_mut`].
+    pub fn set(&self, value: T) -> T {
+        self.state.touched.set(true);
+        self.state.value.replace(value)
+    }
+}
+
+/// Look for a cookie named `name` in the `Cookie` headers of the incoming request.
+fn find_cookie(headers: &HeaderMap, name: &str) -> Option<Cookie<'static>> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(Cookie::split_parse)
+        .filter_map(Result::ok)
+        .find(|cookie| cookie.name() == name)
+        .map(Cookie::into_owned)
+}
+
+#[derive(Clone)]
+/// Determine how [`Session`]s are stored in cookies.
+///
+/// Check out [`Session`]'s documentation for more details.
+pub struct SessionConfig {
+    key: Key,
+    cookie_name: String,
+    encrypted: bool,
+    secure: bool,
+    on_invalid: InvalidSessionPolicy,
+}
+
+impl SessionConfig {
+    /// Store sessions in cookies signed with the provided [`Key`].
+    ///
+    /// All cookies issued with a key are rejected once you switch to a different one:
+    /// load it from your configuration rather than generating a new one on startup.
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            cookie_name: "session".to_owned(),
+            encrypted: false,
+            secure: true,
+            on_invalid: InvalidSessionPolicy::Reset,
+        }
+    }
+
+    /// Set the name of the session cookie.
+    ///
+    /// It defaults to `session`.
+    pub fn cookie_name(mut self, cookie_name: impl Into<String>) -> Self {
+        self.cookie_name = cookie_name.into();
+        self
+    }
+
+    /// Encrypt the session cookie, rather than just signing it.
+    ///
+    /// Signed cookies can't be tampered with, but clients can read their payload.
+    /// Encrypted cookies are both tamper-proof and opaque to clients.
+    /// It defaults to `false`.
+    pub fn encrypted(mut self, encrypted: bool) -> Self {
+        self.encrypted = encrypted;
+        self
+    }
+
+    /// Set the `Secure` attribute on the session cookie, instructing clients to only send it
+    /// back over HTTPS.
+    ///
+    /// It defaults to `true`. You may want to disable it for local development.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Determine what happens when the incoming session cookie is invalid.
+    ///
+    /// It defaults to [`InvalidSessionPolicy::Reset`].
+    pub fn on_invalid(mut self, policy: InvalidSessionPolicy) -> Self {
+        self.on_invalid = policy;
+        self
+    }
+
+    /// Verify (or decrypt) the session cookie, returning its payload.
+    fn open(&self, cookie: Cookie<'static>) -> Option<String> {
+        let jar = CookieJar::new();
+        let cookie = if self.encrypted {
+            jar.private(&self.key).decrypt(cookie)
+        } else {
+            jar.signed(&self.key).verify(cookie)
+        }?;
+        let payload = percent_decode_str(cookie.value()).decode_utf8().ok()?;
+        Some(payload.into_owned())
+    }
+
+    /// Build a signed (or encrypted) session cookie with the provided payload.
+    fn seal(&self, payload: String) -> Cookie<'static> {
+        // Percent-encode the payload, since JSON uses characters that are not allowed
+        // in cookie values (e.g. `"` or `,`).
+        let value = utf8_percent_encode(&payload, NON_ALPHANUMERIC).to_string();
+        let cookie = Cookie::build((self.cookie_name.clone(), value))
+            .path("/")
+            .http_only(true)
+            .secure(self.secure)
+            .same_site(SameSite::Lax)
+            .build();
+        let mut jar = CookieJar::new();
+        if self.encrypted {
+            jar.private_mut(&self.key).add(cookie);
+        } else {
+            jar.signed_mut(&self.key).add(cookie);
+        }
+        jar.get(&self.cookie_name)
+            .expect("The session cookie was just added to the jar")
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for SessionConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The key must never end up in logs.
+        f.debug_struct("SessionConfig")
+            .field("cookie_name", &self.cookie_name)
+            .field("encrypted", &self.encrypted)
+            .field("secure", &self.secure)
+            .field("on_invalid", &self.on_invalid)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What to do when the incoming session cookie is invalid.
+///
+/// Check out [`SessionConfig::on_invalid`] for more details.
+pub enum InvalidSessionPolicy {
+    /// Start from a fresh session, `T::default()`.
+    /// The invalid cookie is overwritten when the response is sent back.
+    Reset,
+    /// Reject the request with an [`InvalidSession`] error.
+    Reject,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::ready;
+
+    use http::header::SET_COOKIE;
+    use serde::{Deserialize, Serialize};
+
+    use crate::middleware::Next;
+    use crate::request::RequestHead;
+    use crate::response::Response;
+
+    use super::{InvalidSessionPolicy, Key, Session, SessionConfig};
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    struct Cart {
+        items: Vec<String>,
+    }
+
+    /// Run the flushing middleware and return the `Set-Cookie` header it attached, if any.
+    async fn flush(session: &Session<Cart>, config: &SessionConfig) -> Option<String> {
+        let next = Next::new(ready(Response::ok()));
+        let response = Session::flush(next, config, session.clone()).await;
+        response
+            .headers()
+            .get(SET_COOKIE)
+            .map(|v| v.to_str().unwrap().to_owned())
+    }
+
+    /// Turn a `Set-Cookie` header into the corresponding `Cookie` header.
+    fn cookie_header(set_cookie: &str) -> &str {
+        set_cookie.split(';').next().unwrap()
+    }
+
+    fn cart(items: &[&str]) -> Cart {
+        Cart {
+            items: items.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_session_can_be_read_back() {
+        for encrypted in [false, true] {
+            let config = SessionConfig::new(Key::generate()).encrypted(encrypted);
+            let session = Session::<Cart>::extract(&RequestHead::for_tests(), &config).unwrap();
+            session.set(cart(&["book"]));
+            let set_cookie = flush(&session, &config).await.unwrap();
+            // Encrypted payloads are opaque to the client.
+            assert_eq!(set_cookie.contains("book"), !encrypted);
+
+            let head = RequestHead::for_tests().with_header("cookie", cookie_header(&set_cookie));
+            let session = Session::<Cart>::extract(&head, &config).unwrap();
+            assert_eq!(*session.get(), cart(&["book"]));
+        }
+    }
+
+    #[tokio::test]
+    async fn mutations_are_written_back() {
+        let config = SessionConfig::new(Key::generate());
+        let session = Session::<Cart>::extract(&RequestHead::for_tests(), &config).unwrap();
+        session.set(cart(&["book"]));
+        let set_cookie = flush(&session, &config).await.unwrap();
+
+        let head = RequestHead::for_tests().with_header("cookie", cookie_header(&set_cookie));
+        let session = Session::<Cart>::extract(&head, &config).unwrap();
+        session.get_mut().items.push("pen".into());
+        let set_cookie = flush(&session, &config).await.unwrap();
+        assert!(set_cookie.starts_with("session="));
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(set_cookie.contains("Secure"));
+
+        let head = RequestHead::for_tests().with_header("cookie", cookie_header(&set_cookie));
+        let session = Session::<Cart>::extract(&head, &config).unwrap();
+        assert_eq!(*session.get(), cart(&["book", "pen"]));
+    }
+
+    #[tokio::test]
+    async fn unchanged_sessions_are_not_written_back() {
+        let config = SessionConfig::new(Key::generate());
+        // A fresh session that is never modified.
+        let session = Session::<Cart>::extract(&RequestHead::for_tests(), &config).unwrap();
+        assert_eq!(flush(&session, &config).await, None);
+
+        // An existing session that is only read.
+        session.set(cart(&["book"]));
+        let set_cookie = flush(&session, &config).await.unwrap();
+        let head = RequestHead::for_tests().with_header("cookie", cookie_header(&set_cookie));
+        let session = Session::<Cart>::extract(&head, &config).unwrap();
+        assert_eq!(session.get().items.len(), 1);
+        assert_eq!(flush(&session, &config).await, None);
+
+        // An existing session that is borrowed mutably, but left as it was.
+        drop(session.get_mut());
+        assert_eq!(flush(&session, &config).await, None);
+    }
+
+    #[tokio::test]
+    async fn invalid_sessions_are_reset_by_default() {
+        let config = SessionConfig::new(Key::generate());
+        let other_config = SessionConfig::new(Key::generate());
+        let session = Session::<Cart>::extract(&RequestHead::for_tests(), &other_config).unwrap();
+        session.set(cart(&["book"]));
+        let set_cookie = flush(&session, &other_config).await.unwrap();
+
+        let head = RequestHead::for_tests().with_header("cookie", cookie_header(&set_cookie));
+        let session = Session::<Cart>::extract(&head, &config).unwrap();
+        assert_eq!(*session.get(), Cart::default());
+        // The invalid cookie is overwritten.
+        assert!(flush(&session, &config).await.is_some());
+
+        let head = RequestHead::for_tests().with_header("cookie", "session=tampered");
+        let session = Session::<Cart>::extract(&head, &config).unwrap();
+        assert_eq!(*session.get(), Cart::default());
+    }
+
+    #[test]
+    fn invalid_sessions_can_be_rejected() {
+        let config = SessionConfig::new(Key::generate()).on_invalid(InvalidSessionPolicy::Reject);
+        let head = RequestHead::for_tests().with_header("cookie", "session=tampered");
+        assert!(Session::<Cart>::extract(&head, &config).is_err());
+
+        // Missing sessions are not invalid.
+        let session = Session::<Cart>::extract(&RequestHead::for_tests(), &config).unwrap();
+        assert_eq!(*session.get(), Cart::default());
+    }
+}
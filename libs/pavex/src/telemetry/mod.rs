@@ -0,0 +1,154 @@
+//! Utilities to observe the behaviour of your application at runtime.
+pub use root_span::{logger, RootSpan};
+pub use trace_context::{TraceContext, TraceContextPropagation};
+
+#[cfg(test)]
+pub(crate) mod recorder;
+mod root_span;
+mod trace_context;
+
+/// Log the failure of a request-scoped constructor.
+///
+/// You'll never have to invoke this function yourself: Pavex's generated code calls it
+/// when failure logs have been enabled via
+/// [`Blueprint::log_constructor_failures`](crate::blueprint::Blueprint::log_constructor_failures).
+///
+/// # Log format
+///
+/// An `ERROR`-level event is emitted, with two fields:
+///
+/// - `constructor`, the path of the constructor that failed;
+/// - `call_path`, the chain of components that (transitively) depend on the failed
+///   constructor, starting from the constructor itself and ending with the request handler
+///   (or the middleware) that triggered its invocation. Components are separated by ` -> `.
+///
+/// E.g. `call_path = "app::db_connection -> app::user_repository -> app::get_user"`.
+pub fn log_constructor_failure(constructor: &str, call_path: &[&str]) {
+    tracing::error!(
+        constructor = constructor,
+        call_path = %call_path.join(" -> "),
+        "A constructor failed. The response will be built from its error."
+    );
+}
+
+/// Convert the output of an [optional](crate::blueprint::constructor::Constructor::optional)
+/// singleton constructor into an `Option`, logging its failure.
+///
+/// You'll never have to invoke this function yourself: Pavex's generated code calls it
+/// when building the application state.
+///
+/// # Log format
+///
+/// If `result` is an error, a `WARN`-level event is emitted, with two fields:
+///
+/// - `singleton`, the type of the singleton that couldn't be built;
+/// - `error_type`, the type of the error returned by its constructor.
+///
+/// The error itself is not logged: the error type of an optional constructor is not required
+/// to implement `Debug` or `Display`.
+pub fn log_optional_singleton_failure<T, E>(result: Result<T, E>) -> Option<T> {
+    if result.is_err() {
+        tracing::warn!(
+            singleton = std::any::type_name::<T>(),
+            error_type = std::any::type_name::<E>(),
+            "An optional singleton couldn't be built. Its dependents will receive `None`."
+        );
+    }
+    result.ok()
+}
+
+/// Spawn a blocking task without losing the current `tracing` span.
+///
+/// `tracing`'s span context is thread-local: the current span is lost when a blocking task
+/// is moved to a different thread.
+/// This function captures the current span and re-attaches it to the workload on the
+/// thread that ends up running it.
+///
+/// It must be called from within a `tokio` runtime.
+/// Use [`BlockingPool`](crate::blocking::BlockingPool) if you want to bound the number of
+/// blocking tasks running concurrently.
+pub fn spawn_blocking_with_tracing<F, R>(f: F) -> tokio::task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let current_span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || current_span.in_scope(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::recorder::Recorder;
+    use super::{log_constructor_failure, log_optional_singleton_failure};
+
+    #[test]
+    fn the_log_includes_the_failed_constructor_and_its_call_path() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            log_constructor_failure(
+                "app::db_connection",
+                &[
+                    "app::db_connection",
+                    "app::user_repository",
+                    "app::get_user",
+                ],
+            );
+        });
+
+        let fields = recorder.event_fields();
+        insta::assert_debug_snapshot!(fields, @r###"
+        [
+            (
+                "message",
+                "A constructor failed. The response will be built from its error.",
+            ),
+            (
+                "constructor",
+                "app::db_connection",
+            ),
+            (
+                "call_path",
+                "app::db_connection -> app::user_repository -> app::get_user",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn optional_singleton_failures_are_logged() {
+        let recorder = Recorder::default();
+        let outcome = tracing::subscriber::with_default(recorder.clone(), || {
+            log_optional_singleton_failure::<u8, String>(Err("unreachable".into()))
+        });
+
+        assert_eq!(outcome, None);
+        let fields = recorder.event_fields();
+        insta::assert_debug_snapshot!(fields, @r###"
+        [
+            (
+                "message",
+                "An optional singleton couldn't be built. Its dependents will receive `None`.",
+            ),
+            (
+                "singleton",
+                "u8",
+            ),
+            (
+                "error_type",
+                "alloc::string::String",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn optional_singletons_that_were_built_are_not_logged() {
+        let recorder = Recorder::default();
+        let outcome = tracing::subscriber::with_default(recorder.clone(), || {
+            log_optional_singleton_failure::<u8, String>(Ok(1))
+        });
+
+        assert_eq!(outcome, Some(1));
+        assert!(recorder.event_fields().is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! A bare-bones `tracing` subscriber for our tests.
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Current, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// A subscriber that records:
+///
+/// - the fields of every event;
+/// - the values attached to spans, either at creation time or afterwards;
+/// - on which thread each span is entered.
+///
+/// It keeps track of the current span of each thread, so that spans can be
+/// propagated across threads (e.g. via `Span::current`).
+#[derive(Default, Clone)]
+pub(crate) struct Recorder(Arc<Mutex<State>>);
+
+#[derive(Default)]
+struct State {
+    spans: Vec<&'static Metadata<'static>>,
+    stacks: HashMap<ThreadId, Vec<u64>>,
+    event_fields: Vec<(String, String)>,
+    span_fields: Vec<(String, String)>,
+    entered: Vec<(u64, ThreadId)>,
+}
+
+impl Recorder {
+    /// The fields of all the events that have been emitted, in order.
+    pub(crate) fn event_fields(&self) -> Vec<(String, String)> {
+        self.0.lock().unwrap().event_fields.clone()
+    }
+
+    /// The values recorded on spans, in order.
+    pub(crate) fn span_fields(&self) -> Vec<(String, String)> {
+        self.0.lock().unwrap().span_fields.clone()
+    }
+
+    /// The (span id, thread id) pair for every time a span has been entered.
+    pub(crate) fn entered(&self) -> Vec<(u64, ThreadId)> {
+        self.0.lock().unwrap().entered.clone()
+    }
+}
+
+/// Collect the fields of a span or an event into a list of `(name, value)` pairs.
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name().to_owned(), format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_owned(), value.to_owned()));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut state = self.0.lock().unwrap();
+        span.record(&mut FieldVisitor(&mut state.span_fields));
+        state.spans.push(span.metadata());
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, values: &Record<'_>) {
+        let mut state = self.0.lock().unwrap();
+        values.record(&mut FieldVisitor(&mut state.span_fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut state = self.0.lock().unwrap();
+        event.record(&mut FieldVisitor(&mut state.event_fields));
+    }
+
+    fn enter(&self, span: &Id) {
+        let thread_id = std::thread::current().id();
+        let mut state = self.0.lock().unwrap();
+        state.entered.push((span.into_u64(), thread_id));
+        state
+            .stacks
+            .entry(thread_id)
+            .or_default()
+            .push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        let thread_id = std::thread::current().id();
+        if let Some(stack) = self.0.lock().unwrap().stacks.get_mut(&thread_id) {
+            stack.pop();
+        }
+    }
+
+    fn current_span(&self) -> Current {
+        let thread_id = std::thread::current().id();
+        let state = self.0.lock().unwrap();
+        match state.stacks.get(&thread_id).and_then(|stack| stack.last()) {
+            Some(&id) => Current::new(Id::from_u64(id), state.spans[id as usize - 1]),
+            None => Current::none(),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+use tracing::Instrument;
+
+use crate::blueprint::constructor::{CloningStrategy, Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::http::Version;
+use crate::middleware::Next;
+use crate::request::path::MatchedPathPattern;
+use crate::request::RequestHead;
+use crate::response::Response;
+use crate::telemetry::TraceContext;
+
+#[derive(Debug, Clone)]
+/// The top-level *logical* span for an incoming request.
+///
+/// It is not necessarily the top-level *physical* span, as it may be a child of
+/// another span (e.g. a span representing the underlying HTTP connection).
+///
+/// The root span is used to attach as much information as possible about the
+/// incoming request, and to record the final outcome of the request (success or
+/// failure).
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Custom root spans](#custom-root-spans)
+///
+/// # Installation
+///
+/// Register the [default constructor](RootSpan::register) for [`RootSpan`] alongside the
+/// [`logger`] middleware.
+/// The default constructor links the root span to the caller's trace, if any: you must
+/// register the constructors for [`TraceContext`] and its
+/// [propagation toggle](crate::telemetry::TraceContextPropagation) as well.
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::Blueprint;
+/// use pavex::telemetry::{RootSpan, TraceContext, TraceContextPropagation};
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     TraceContext::register(&mut bp);
+///     TraceContextPropagation::register(&mut bp);
+///     RootSpan::register(&mut bp);
+///     bp.wrap(f!(pavex::telemetry::logger));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// # Custom root spans
+///
+/// The [default constructor](RootSpan::from_request) may not capture all the information
+/// you care about.
+/// You can register your own constructor for [`RootSpan`] instead: the [`logger`] middleware
+/// doesn't care about how the root span was built, as long as it honors the following contract:
+///
+/// - the span must be built via [`RootSpan::new`];
+/// - the span must declare an empty [`STATUS_CODE_FIELD`](RootSpan::STATUS_CODE_FIELD) field,
+///   which will be populated with the status code of the outgoing response.
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{constructor::{CloningStrategy, Lifecycle}, Blueprint};
+/// use pavex::request::RequestHead;
+/// use pavex::telemetry::RootSpan;
+///
+/// pub fn root_span(request_head: &RequestHead) -> RootSpan {
+///     let tenant_id = request_head
+///         .headers
+///         .get("X-Tenant-Id")
+///         .and_then(|h| h.to_str().ok())
+///         .unwrap_or_default();
+///     let span = tracing::info_span!(
+///         "HTTP request",
+///         http.method = %request_head.method,
+///         // Your own app-specific fields...
+///         tenant_id = %tenant_id,
+///         // ...alongside the one required by the `logger` middleware.
+///         http.response.status_code = tracing::field::Empty,
+///     );
+///     RootSpan::new(span)
+/// }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(f!(crate::root_span), Lifecycle::RequestScoped)
+///         .cloning(CloningStrategy::CloneIfNecessary);
+///     bp.wrap(f!(pavex::telemetry::logger));
+///     // [...]
+///     bp
+/// }
+/// ```
+pub struct RootSpan(tracing::Span);
+
+impl RootSpan {
+    /// The name of the field that [`logger`] uses to record the status code of the response.
+    ///
+    /// It follows OpenTelemetry's HTTP semantic conventions.
+    pub const STATUS_CODE_FIELD: &'static str = "http.response.status_code";
+
+    /// Use the provided [`tracing::Span`] as the root span for the incoming request.
+    ///
+    /// Check out the [type-level documentation](RootSpan#custom-root-spans) for the
+    /// fields that the span is expected to declare.
+    pub fn new(span: tracing::Span) -> Self {
+        Self(span)
+    }
+
+    /// Register the [default constructor](RootSpan::from_request)
+    /// for [`RootSpan`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::telemetry::RootSpan::from_request),
+            Lifecycle::RequestScoped,
+        )
+        .cloning(CloningStrategy::CloneIfNecessary)
+    }
+
+    /// The default constructor for [`RootSpan`].
+    ///
+    /// It follows OpenTelemetry's HTTP semantic conventions as closely as
+    /// possible for field naming.
+    /// If the caller propagated a [`TraceContext`], it's recorded on the span via
+    /// [`TraceContext::record_fields`].
+    pub fn from_request(
+        request_head: &RequestHead,
+        matched_route: MatchedPathPattern,
+        trace_context: &Option<TraceContext>,
+    ) -> Self {
+        let user_agent = request_head
+            .headers
+            .get("User-Agent")
+            .map(|h| h.to_str().unwrap_or_default())
+            .unwrap_or_default();
+
+        let span = tracing::info_span!(
+            "HTTP request",
+            http.method = %request_head.method,
+            http.flavor = %http_flavor(request_head.version),
+            user_agent.original = %user_agent,
+            http.response.status_code = tracing::field::Empty,
+            http.route = %matched_route,
+            http.target = %request_head.target.path_and_query().map(|p| p.as_str()).unwrap_or(""),
+            trace_id = tracing::field::Empty,
+            parent_span_id = tracing::field::Empty,
+        );
+        if let Some(trace_context) = trace_context {
+            trace_context.record_fields(&span);
+        }
+        Self::new(span)
+    }
+
+    /// Record the status code of the outgoing response on the root span.
+    pub fn record_response_data(&self, response: &Response) {
+        self.0
+            .record(Self::STATUS_CODE_FIELD, response.status().as_u16());
+    }
+
+    /// Get a reference to the underlying [`tracing::Span`].
+    pub fn inner(&self) -> &tracing::Span {
+        &self.0
+    }
+
+    /// Deconstruct the root span into its underlying [`tracing::Span`].
+    pub fn into_inner(self) -> tracing::Span {
+        self.0
+    }
+}
+
+/// A logging middleware that wraps the request pipeline in the [`RootSpan`].
+/// It records the status code of the response on the root span.
+///
+/// It works with any [`RootSpan`] constructor, as long as it follows the
+/// [contract](RootSpan#custom-root-spans).
+pub async fn logger<C>(next: Next<C>, root_span: RootSpan) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    let response = next
+        .into_future()
+        .instrument(root_span.clone().into_inner())
+        .await;
+    root_span.record_response_data(&response);
+    response
+}
+
+fn http_flavor(version: Version) -> Cow<'static, str> {
+    match version {
+        Version::HTTP_09 => "0.9".into(),
+        Version::HTTP_10 => "1.0".into(),
+        Version::HTTP_11 => "1.1".into(),
+        Version::HTTP_2 => "2.0".into(),
+        Version::HTTP_3 => "3.0".into(),
+        other => format!("{other:?}").into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{logger, RootSpan};
+    use crate::middleware::Next;
+    use crate::request::path::MatchedPathPattern;
+    use crate::request::RequestHead;
+    use crate::response::Response;
+    use crate::telemetry::recorder::Recorder;
+    use crate::telemetry::{TraceContext, TraceContextPropagation};
+
+    #[test]
+    fn the_default_root_span_records_the_incoming_trace_context() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let request_head = RequestHead::for_tests().with_target("/home").with_header(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        );
+        let trace_context = TraceContext::extract(&request_head, TraceContextPropagation::Enabled);
+        assert!(trace_context.is_some());
+        let _root_span = RootSpan::from_request(
+            &request_head,
+            MatchedPathPattern::new("/home"),
+            &trace_context,
+        );
+
+        let fields = recorder.span_fields();
+        assert!(fields.contains(&("trace_id".into(), "4bf92f3577b34da6a3ce929d0e0e4736".into())));
+        assert!(fields.contains(&("parent_span_id".into(), "00f067aa0ba902b7".into())));
+    }
+
+    #[tokio::test]
+    async fn logger_works_with_a_custom_root_span() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let root_span = RootSpan::new(tracing::info_span!(
+            "HTTP request",
+            tenant_id = "acme",
+            http.response.status_code = tracing::field::Empty,
+        ));
+        let next = Next::new(async { Response::not_found() });
+        let response = logger(next, root_span).await;
+        assert_eq!(response.status().as_u16(), 404);
+
+        let fields = recorder.span_fields();
+        insta::assert_debug_snapshot!(fields, @r###"
+        [
+            (
+                "tenant_id",
+                "acme",
+            ),
+            (
+                "http.response.status_code",
+                "404",
+            ),
+        ]
+        "###);
+    }
+}
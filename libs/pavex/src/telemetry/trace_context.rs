@@ -0,0 +1,434 @@
+use http::header::HeaderValue;
+use http::HeaderMap;
+
+use crate::blueprint::constructor::{Constructor, Lifecycle};
+use crate::blueprint::Blueprint;
+use crate::f;
+use crate::request::RequestHead;
+
+const TRACEPARENT: &str = "traceparent";
+const TRACESTATE: &str = "tracestate";
+
+/// The bit of `trace-flags` that signals whether the caller has recorded (sampled) its trace.
+const SAMPLED_FLAG: u8 = 0x01;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The distributed tracing context that has been propagated to your application by
+/// the caller, via the [W3C Trace Context](https://www.w3.org/TR/trace-context/) headers
+/// (`traceparent` and `tracestate`).
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Linking to the root span](#linking-to-the-root-span)
+/// - [Outgoing requests](#outgoing-requests)
+///
+/// # Installation
+///
+/// Register the [constructor](TraceContext::register) for `Option<TraceContext>` alongside the
+/// [default constructor](TraceContextPropagation::register) for [`TraceContextPropagation`]:
+///
+/// ```rust
+/// use pavex::blueprint::Blueprint;
+/// use pavex::telemetry::{TraceContext, TraceContextPropagation};
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     TraceContext::register(&mut bp);
+///     TraceContextPropagation::register(&mut bp);
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// You can then inject `Option<TraceContext>` (or a reference to it) into your components.
+/// It's set to `None` if the incoming request doesn't carry a valid `traceparent` header,
+/// or if propagation has been [disabled](TraceContextPropagation::Disabled).
+///
+/// # Linking to the root span
+///
+/// The trace context is meant to be used as the parent of the root span for the incoming
+/// request, so that your application shows up as part of the caller's trace.
+/// `tracing` has no notion of remote parents: the best you can do without an OpenTelemetry
+/// integration is to record [`trace_id`](TraceContext::trace_id) and
+/// [`parent_id`](TraceContext::parent_id) as fields when building the root span:
+///
+/// ```rust
+/// use pavex::request::RequestHead;
+/// use pavex::telemetry::TraceContext;
+///
+/// pub fn root_span(request_head: &RequestHead, trace_context: &Option<TraceContext>) -> tracing::Span {
+///     let span = tracing::info_span!(
+///         "HTTP request",
+///         http.method = %request_head.method,
+///         trace_id = tracing::field::Empty,
+///         parent_span_id = tracing::field::Empty,
+///     );
+///     if let Some(trace_context) = trace_context {
+///         trace_context.record_fields(&span);
+///     }
+///     span
+/// }
+/// ```
+///
+/// If you are exporting your spans via OpenTelemetry, use the same information to actually set the
+/// parent of the root span (e.g. via `tracing-opentelemetry`'s `OpenTelemetrySpanExt::set_parent`).
+///
+/// # Outgoing requests
+///
+/// Pavex doesn't ship an HTTP client. You can still propagate the trace context to
+/// the services you call by [injecting](TraceContext::inject) it into the headers of
+/// your outgoing requests.
+/// Use [`TraceContext::with_parent_id`] to set the identifier of the span that represents the
+/// outgoing call before injecting it.
+pub struct TraceContext {
+    trace_id: u128,
+    parent_id: u64,
+    trace_flags: u8,
+    trace_state: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Determine if [`TraceContext`] should be extracted from incoming requests.
+///
+/// Propagation is disabled by default: the trace context is chosen by the caller and it
+/// should not be trusted unless you control the caller.
+/// Enable it if your application only receives traffic from services you trust
+/// (e.g. it sits behind a gateway that overwrites the `traceparent` and `tracestate` headers).
+pub enum TraceContextPropagation {
+    /// Extract the trace context from the `traceparent` and `tracestate` headers.
+    Enabled,
+    /// Ignore the `traceparent` and `tracestate` headers of incoming requests.
+    #[default]
+    Disabled,
+}
+
+impl TraceContextPropagation {
+    /// Register the [default constructor](TraceContextPropagation::default)
+    /// for [`TraceContextPropagation`] with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(<pavex::telemetry::TraceContextPropagation as std::default::Default>::default),
+            Lifecycle::Singleton,
+        )
+    }
+}
+
+impl TraceContext {
+    /// Register the [default constructor](TraceContext::extract)
+    /// for `Option<TraceContext>` with a [`Blueprint`].
+    pub fn register(bp: &mut Blueprint) -> Constructor {
+        bp.constructor(
+            f!(pavex::telemetry::TraceContext::extract),
+            Lifecycle::RequestScoped,
+        )
+    }
+
+    /// Extract the trace context from the headers of an incoming request.
+    ///
+    /// It returns `None` if:
+    ///
+    /// - propagation is [disabled](TraceContextPropagation::Disabled);
+    /// - there is no `traceparent` header;
+    /// - the `traceparent` header is malformed, according to the rules laid out in the
+    ///   [W3C Trace Context specification](https://www.w3.org/TR/trace-context/#traceparent-header).
+    ///
+    /// The `tracestate` header is only taken into account if there is a valid `traceparent` header.
+    pub fn extract(
+        request_head: &RequestHead,
+        propagation: TraceContextPropagation,
+    ) -> Option<TraceContext> {
+        if propagation == TraceContextPropagation::Disabled {
+            return None;
+        }
+        Self::from_headers(&request_head.headers)
+    }
+
+    fn from_headers(headers: &HeaderMap) -> Option<TraceContext> {
+        // The specification forbids multiple `traceparent` headers.
+        let mut traceparents = headers.get_all(TRACEPARENT).iter();
+        let traceparent = traceparents.next()?;
+        if traceparents.next().is_some() {
+            return None;
+        }
+        let mut context = parse_traceparent(traceparent.to_str().ok()?)?;
+
+        // Multiple `tracestate` headers must be combined, in order, into a single list.
+        let trace_state = headers
+            .get_all(TRACESTATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>()
+            .join(",");
+        if !trace_state.is_empty() {
+            context.trace_state = Some(trace_state);
+        }
+        Some(context)
+    }
+
+    /// The identifier of the distributed trace that the incoming request belongs to.
+    pub fn trace_id(&self) -> u128 {
+        self.trace_id
+    }
+
+    /// The identifier of the caller's span—i.e. the parent of the span that represents
+    /// the processing of the incoming request.
+    pub fn parent_id(&self) -> u64 {
+        self.parent_id
+    }
+
+    /// `true` if the caller may have recorded its trace.
+    pub fn is_sampled(&self) -> bool {
+        self.trace_flags & SAMPLED_FLAG != 0
+    }
+
+    /// The vendor-specific trace information propagated via the `tracestate` header, if any.
+    pub fn trace_state(&self) -> Option<&str> {
+        self.trace_state.as_deref()
+    }
+
+    /// Set a different parent identifier.
+    ///
+    /// Use it, before [injecting](TraceContext::inject) the context into an outgoing request,
+    /// to specify the span that represents the outgoing call.
+    pub fn with_parent_id(mut self, parent_id: u64) -> Self {
+        self.parent_id = parent_id;
+        self
+    }
+
+    /// Record [`trace_id`](TraceContext::trace_id) and [`parent_id`](TraceContext::parent_id)
+    /// as the `trace_id` and `parent_span_id` fields on the provided span, using their
+    /// hex-encoded representation.
+    ///
+    /// The span must have been created with those two fields, otherwise they'll be silently
+    /// ignored (see [`tracing::Span::record`]).
+    ///
+    /// It doesn't change the parent of the span, as far as `tracing` is concerned.
+    pub fn record_fields(&self, span: &tracing::Span) {
+        span.record(
+            "trace_id",
+            tracing::field::display(format_args!("{:032x}", self.trace_id)),
+        );
+        span.record(
+            "parent_span_id",
+            tracing::field::display(format_args!("{:016x}", self.parent_id)),
+        );
+    }
+
+    /// The value of the `traceparent` header that represents this context.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.parent_id, self.trace_flags
+        )
+    }
+
+    /// Set the `traceparent` (and, if present, `tracestate`) headers on an outgoing request.
+    /// Pre-existing values are overwritten.
+    pub fn inject(&self, headers: &mut HeaderMap) {
+        // The value only contains hex digits and dashes, therefore it's always a valid header value.
+        headers.insert(
+            TRACEPARENT,
+            HeaderValue::from_str(&self.traceparent()).unwrap(),
+        );
+        match self
+            .trace_state
+            .as_deref()
+            .and_then(|s| HeaderValue::from_str(s).ok())
+        {
+            Some(trace_state) => {
+                headers.insert(TRACESTATE, trace_state);
+            }
+            None => {
+                headers.remove(TRACESTATE);
+            }
+        }
+    }
+}
+
+/// Parse the value of a `traceparent` header.
+///
+/// Format: `{version}-{trace-id}-{parent-id}-{trace-flags}`, all lowercase hex.
+fn parse_traceparent(value: &str) -> Option<TraceContext> {
+    let value = value.trim();
+    let mut parts = value.split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let parent_id = parts.next()?;
+    let trace_flags = parts.next()?;
+
+    if !is_lowercase_hex(version, 2) || version == "ff" {
+        return None;
+    }
+    // Version 00 has exactly four fields. Future versions may append more fields:
+    // we must ignore them, as long as the fields we know about are well-formed.
+    if version == "00" && parts.next().is_some() {
+        return None;
+    }
+    if !is_lowercase_hex(trace_id, 32)
+        || !is_lowercase_hex(parent_id, 16)
+        || !is_lowercase_hex(trace_flags, 2)
+    {
+        return None;
+    }
+    let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+    let parent_id = u64::from_str_radix(parent_id, 16).ok()?;
+    let trace_flags = u8::from_str_radix(trace_flags, 16).ok()?;
+    // All-zero identifiers are explicitly forbidden.
+    if trace_id == 0 || parent_id == 0 {
+        return None;
+    }
+    Some(TraceContext {
+        trace_id,
+        parent_id,
+        trace_flags,
+        trace_state: None,
+    })
+}
+
+fn is_lowercase_hex(s: &str, expected_len: usize) -> bool {
+    s.len() == expected_len && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::{TraceContext, TraceContextPropagation};
+    use crate::request::RequestHead;
+    use crate::telemetry::recorder::Recorder;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    fn extract(headers: &[(&'static str, &'static str)]) -> Option<TraceContext> {
+        TraceContext::extract(
+            &RequestHead::for_tests().with_headers(headers),
+            TraceContextPropagation::Enabled,
+        )
+    }
+
+    #[test]
+    fn a_valid_traceparent_is_extracted() {
+        let context = extract(&[("traceparent", TRACEPARENT)]).unwrap();
+        assert_eq!(context.trace_id(), 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(context.parent_id(), 0x00f067aa0ba902b7);
+        assert!(context.is_sampled());
+        assert_eq!(context.trace_state(), None);
+        assert_eq!(context.traceparent(), TRACEPARENT);
+    }
+
+    #[test]
+    fn tracestate_headers_are_combined() {
+        let context = extract(&[
+            ("traceparent", TRACEPARENT),
+            ("tracestate", "congo=t61rcWkgMzE"),
+            ("tracestate", "rojo=00f067aa0ba902b7"),
+        ])
+        .unwrap();
+        assert_eq!(
+            context.trace_state(),
+            Some("congo=t61rcWkgMzE,rojo=00f067aa0ba902b7")
+        );
+    }
+
+    #[test]
+    fn malformed_traceparents_are_ignored() {
+        let invalid = [
+            // Invalid version
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            // Uppercase hex digits
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            // All-zero trace id
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            // All-zero parent id
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            // Trace id is too short
+            "00-4bf92f3577b34da6a3ce929d0e0e47-00f067aa0ba902b7-01",
+            // Version 00 doesn't allow additional fields
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            // Missing fields
+            "00-4bf92f3577b34da6a3ce929d0e0e4736",
+        ];
+        for traceparent in invalid {
+            assert_eq!(
+                extract(&[
+                    ("traceparent", traceparent),
+                    ("tracestate", "congo=t61rcWkgMzE")
+                ]),
+                None,
+                "`{traceparent}` should have been rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn future_versions_can_have_additional_fields() {
+        let context = extract(&[(
+            "traceparent",
+            "cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-what-the-future-holds",
+        )])
+        .unwrap();
+        assert_eq!(context.trace_id(), 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert!(!context.is_sampled());
+    }
+
+    #[test]
+    fn propagation_is_disabled_by_default() {
+        let head = RequestHead::for_tests().with_header("traceparent", TRACEPARENT);
+        assert_eq!(
+            TraceContextPropagation::default(),
+            TraceContextPropagation::Disabled
+        );
+        assert_eq!(
+            TraceContext::extract(&head, TraceContextPropagation::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn the_incoming_context_is_recorded_on_the_span() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let context = extract(&[("traceparent", TRACEPARENT)]).unwrap();
+            let span = tracing::info_span!(
+                "HTTP request",
+                trace_id = tracing::field::Empty,
+                parent_span_id = tracing::field::Empty,
+            );
+            context.record_parent(&span);
+        });
+
+        let fields = recorder.span_fields();
+        insta::assert_debug_snapshot!(fields, @r###"
+        [
+            (
+                "trace_id",
+                "4bf92f3577b34da6a3ce929d0e0e4736",
+            ),
+            (
+                "parent_span_id",
+                "00f067aa0ba902b7",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn the_context_can_be_injected_into_outgoing_requests() {
+        let context = extract(&[
+            ("traceparent", TRACEPARENT),
+            ("tracestate", "congo=t61rcWkgMzE"),
+        ])
+        .unwrap()
+        .with_parent_id(0xb7ad6b7169203331);
+        let mut headers = HeaderMap::new();
+        context.inject(&mut headers);
+        assert_eq!(
+            headers["traceparent"],
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-b7ad6b7169203331-01"
+        );
+        assert_eq!(headers["tracestate"], "congo=t61rcWkgMzE");
+    }
+}
@@ -0,0 +1,47 @@
+//! Errors that can arise when managing a request-scoped [`Transaction`](super::Transaction).
+use crate::response::Response;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to begin a new transaction.")]
+#[non_exhaustive]
+/// The error returned by [`Transaction::begin`](super::Transaction::begin) when the
+/// backend fails to open a new transaction.
+pub struct BeginTransactionError {
+    #[source]
+    /// The error reported by the transaction backend.
+    pub source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl BeginTransactionError {
+    /// Convert a [`BeginTransactionError`] into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error`: the failure is on the server side,
+    /// the client has no way to fix it.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to commit the transaction.")]
+#[non_exhaustive]
+/// The error returned by [`Transaction::finalize`](super::Transaction::finalize) when the
+/// backend fails to commit the transaction.
+///
+/// The response returned by the request handler is discarded: the changes it reports on have
+/// not been persisted.
+pub struct CommitTransactionError {
+    #[source]
+    /// The error reported by the transaction backend.
+    pub source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl CommitTransactionError {
+    /// Convert a [`CommitTransactionError`] into an HTTP response.
+    ///
+    /// It returns a `500 Internal Server Error`: the failure is on the server side,
+    /// the client has no way to fix it.
+    pub fn into_response(&self) -> Response {
+        Response::internal_server_error()
+    }
+}
@@ -0,0 +1,330 @@
+//! Wrap the processing of each incoming request in a database transaction.
+//!
+//! Check out [`Transaction`] for more details.
+use std::future::IntoFuture;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use futures_util::future::LocalBoxFuture;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::middleware::Next;
+use crate::response::Response;
+
+use errors::{BeginTransactionError, CommitTransactionError};
+
+pub mod errors;
+
+/// The interface between [`Transaction`] and your database driver of choice.
+///
+/// Implement it for a type (e.g. a connection pool) that can open, commit and roll back
+/// transactions—see [`Transaction`] for an example.
+pub trait TransactionBackend: 'static {
+    /// The type of a transaction handle (e.g. `sqlx::Transaction<'static, Postgres>`).
+    type Transaction: 'static;
+    /// The error returned by the backend when an operation fails.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Open a new transaction.
+    fn begin(&self) -> LocalBoxFuture<'_, Result<Self::Transaction, Self::Error>>;
+    /// Commit the transaction, persisting all the changes that were made as part of it.
+    fn commit(&self, transaction: Self::Transaction)
+        -> LocalBoxFuture<'_, Result<(), Self::Error>>;
+    /// Roll back the transaction, discarding all the changes that were made as part of it.
+    fn rollback(
+        &self,
+        transaction: Self::Transaction,
+    ) -> LocalBoxFuture<'_, Result<(), Self::Error>>;
+}
+
+/// A database transaction scoped to the incoming request.
+///
+/// # Sections
+///
+/// - [Installation](#installation)
+/// - [Usage](#usage)
+/// - [Commit or rollback?](#commit-or-rollback)
+///
+/// # Installation
+///
+/// You need to register:
+///
+/// - a [`TransactionBackend`] for your database (e.g. your connection pool), usually as a singleton;
+/// - [`Transaction::begin`] as a request-scoped constructor;
+/// - [`Transaction::finalize`] as a wrapping middleware.
+///
+/// ```rust
+/// use pavex::f;
+/// use pavex::blueprint::{Blueprint, constructor::{CloningStrategy, Lifecycle}};
+/// # pub struct Pool;
+/// # pub fn pool() -> Pool { todo!() }
+///
+/// fn blueprint() -> Blueprint {
+///     let mut bp = Blueprint::new();
+///     bp.constructor(f!(crate::pool), Lifecycle::Singleton);
+///     bp.constructor(
+///         f!(pavex::transaction::Transaction::<crate::Pool>::begin),
+///         Lifecycle::RequestScoped,
+///     )
+///     // `Transaction` is a cheap handle: the middleware and the request handler
+///     // share the same underlying transaction.
+///     .cloning(CloningStrategy::CloneIfNecessary)
+///     .error_handler(f!(pavex::transaction::errors::BeginTransactionError::into_response));
+///     bp.wrap(f!(pavex::transaction::Transaction::<crate::Pool>::finalize))
+///         .error_handler(f!(pavex::transaction::errors::CommitTransactionError::into_response));
+///     // [...]
+///     bp
+/// }
+/// ```
+///
+/// The middleware should be registered **before** any route that needs access to the
+/// transaction.
+///
+/// # Usage
+///
+/// Inject `&Transaction<B>` in your request handlers (or constructors) and use
+/// [`Transaction::lock`] to get access to the underlying transaction handle:
+///
+/// ```rust
+/// use pavex::response::Response;
+/// use pavex::transaction::{Transaction, TransactionBackend};
+///
+/// pub async fn create_user<B: TransactionBackend>(transaction: &Transaction<B>) -> Response {
+///     let mut tx = transaction.lock().await;
+///     // Use `tx` (i.e. `&mut B::Transaction`) to issue queries against the database.
+///     // [...]
+///     Response::created()
+/// }
+/// ```
+///
+/// # Commit or rollback?
+///
+/// [`Transaction::finalize`] looks at the status code of the response returned by the
+/// rest of the request processing pipeline:
+///
+/// - if it's a success status code (`2xx`), the transaction is committed.
+///   If the commit fails, the response is discarded and a [`CommitTransactionError`] is
+///   returned instead;
+/// - otherwise, the transaction is rolled back.
+///   If the rollback fails, the error is logged and the original response is returned: the
+///   changes have not been persisted either way, since the transaction was never committed.
+pub struct Transaction<B: TransactionBackend> {
+    backend_transaction: Rc<Mutex<Option<B::Transaction>>>,
+}
+
+impl<B: TransactionBackend> Clone for Transaction<B> {
+    fn clone(&self) -> Self {
+        Self {
+            backend_transaction: self.backend_transaction.clone(),
+        }
+    }
+}
+
+impl<B: TransactionBackend> std::fmt::Debug for Transaction<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transaction").finish_non_exhaustive()
+    }
+}
+
+impl<B: TransactionBackend> Transaction<B> {
+    /// Open a new transaction using the provided backend.
+    ///
+    /// It should be registered as a request-scoped constructor—check out
+    /// [`Transaction`]'s documentation for more details.
+    pub async fn begin(backend: &B) -> Result<Self, BeginTransactionError> {
+        let transaction = backend
+            .begin()
+            .await
+            .map_err(|e| BeginTransactionError { source: e.into() })?;
+        Ok(Self {
+            backend_transaction: Rc::new(Mutex::new(Some(transaction))),
+        })
+    }
+
+    /// Get exclusive access to the underlying transaction handle.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the transaction has already been committed or rolled back
+    /// by [`Transaction::finalize`].
+    pub async fn lock(&self) -> TransactionGuard<'_, B> {
+        let guard = self.backend_transaction.lock().await;
+        assert!(
+            guard.is_some(),
+            "The transaction has already been committed or rolled back"
+        );
+        TransactionGuard { guard }
+    }
+
+    /// A wrapping middleware that commits or rolls back the transaction based on the
+    /// status code of the response.
+    ///
+    /// Check out [`Transaction`]'s documentation for more details.
+    pub async fn finalize<C>(
+        next: Next<C>,
+        backend: &B,
+        transaction: Transaction<B>,
+    ) -> Result<Response, CommitTransactionError>
+    where
+        C: IntoFuture<Output = Response>,
+    {
+        let response = next.into_future().await;
+        let Some(backend_transaction) = transaction.backend_transaction.lock().await.take() else {
+            // It has already been finalized, e.g. by an outer instance of this middleware.
+            return Ok(response);
+        };
+        if response.status().is_success() {
+            backend
+                .commit(backend_transaction)
+                .await
+                .map_err(|e| CommitTransactionError { source: e.into() })?;
+        } else if let Err(e) = backend.rollback(backend_transaction).await {
+            tracing::error!(
+                error.msg = %e,
+                error.details = ?e,
+                "Failed to roll back the transaction"
+            );
+        }
+        Ok(response)
+    }
+}
+
+/// Exclusive access to the transaction handle wrapped by a [`Transaction`].
+///
+/// It is returned by [`Transaction::lock`].
+pub struct TransactionGuard<'a, B: TransactionBackend> {
+    guard: MutexGuard<'a, Option<B::Transaction>>,
+}
+
+impl<B: TransactionBackend> Deref for TransactionGuard<'_, B> {
+    type Target = B::Transaction;
+
+    fn deref(&self) -> &Self::Target {
+        // `Transaction::lock` checks that the transaction is still there.
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<B: TransactionBackend> DerefMut for TransactionGuard<'_, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use futures_util::future::LocalBoxFuture;
+    use futures_util::FutureExt;
+
+    use crate::middleware::Next;
+    use crate::response::Response;
+
+    use super::{Transaction, TransactionBackend};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Outcome {
+        Committed,
+        RolledBack,
+    }
+
+    /// An in-memory backend that keeps track of what happened to its transactions.
+    #[derive(Default)]
+    struct MockBackend {
+        outcomes: RefCell<Vec<(Vec<&'static str>, Outcome)>>,
+        fail_commit: bool,
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("The mock backend failed")]
+    struct MockError;
+
+    impl TransactionBackend for MockBackend {
+        type Transaction = Vec<&'static str>;
+        type Error = MockError;
+
+        fn begin(&self) -> LocalBoxFuture<'_, Result<Self::Transaction, Self::Error>> {
+            async { Ok(Vec::new()) }.boxed_local()
+        }
+
+        fn commit(&self, tx: Self::Transaction) -> LocalBoxFuture<'_, Result<(), Self::Error>> {
+            async move {
+                if self.fail_commit {
+                    return Err(MockError);
+                }
+                self.outcomes.borrow_mut().push((tx, Outcome::Committed));
+                Ok(())
+            }
+            .boxed_local()
+        }
+
+        fn rollback(&self, tx: Self::Transaction) -> LocalBoxFuture<'_, Result<(), Self::Error>> {
+            async move {
+                self.outcomes.borrow_mut().push((tx, Outcome::RolledBack));
+                Ok(())
+            }
+            .boxed_local()
+        }
+    }
+
+    /// A request handler that writes to the transaction and then returns `response`.
+    async fn handler(transaction: Transaction<MockBackend>, response: Response) -> Response {
+        transaction.lock().await.push("INSERT INTO users");
+        response
+    }
+
+    #[tokio::test]
+    async fn transaction_is_rolled_back_on_500() {
+        let backend = MockBackend::default();
+        let transaction = Transaction::begin(&backend).await.unwrap();
+        let next = Next::new(handler(
+            transaction.clone(),
+            Response::internal_server_error(),
+        ));
+        let response = Transaction::finalize(next, &backend, transaction)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            crate::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            backend.outcomes.into_inner(),
+            vec![(vec!["INSERT INTO users"], Outcome::RolledBack)]
+        );
+    }
+
+    #[tokio::test]
+    async fn transaction_is_committed_on_2xx() {
+        let backend = MockBackend::default();
+        let transaction = Transaction::begin(&backend).await.unwrap();
+        let next = Next::new(handler(transaction.clone(), Response::created()));
+        let response = Transaction::finalize(next, &backend, transaction)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), crate::http::StatusCode::CREATED);
+        assert_eq!(
+            backend.outcomes.into_inner(),
+            vec![(vec!["INSERT INTO users"], Outcome::Committed)]
+        );
+    }
+
+    #[tokio::test]
+    async fn commit_failures_are_reported() {
+        let backend = MockBackend {
+            fail_commit: true,
+            ..Default::default()
+        };
+        let transaction = Transaction::begin(&backend).await.unwrap();
+        let next = Next::new(handler(transaction.clone(), Response::ok()));
+        let Err(err) = Transaction::finalize(next, &backend, transaction).await else {
+            panic!("Expected the commit failure to be reported");
+        };
+        insta::assert_display_snapshot!(err, @"Failed to commit the transaction.");
+        assert_eq!(
+            err.into_response().status(),
+            crate::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
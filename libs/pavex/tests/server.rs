@@ -1,10 +1,17 @@
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::time::Duration;
 
-use http::Request;
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Request};
+use http_body::Frame;
+use http_body_util::{BodyExt, StreamBody};
 use hyper::body::Incoming;
 
-use pavex::response::Response;
+use pavex::connection::LocalAddr;
+use pavex::request::body::{BodySizeLimit, BufferedBody, RawIncomingBody};
+use pavex::request::RequestHead;
+use pavex::response::{Response, ResponseSender};
 use pavex::server::{IncomingStream, Server, ServerConfiguration, ShutdownMode};
 
 // A dummy handler for our server tests.
@@ -217,3 +224,187 @@ async fn graceful_but_too_fast() {
         _ = shutdown_future => {}
     }
 }
+
+// Echo the request body and its trailers back to the caller.
+async fn echo_trailers(request: Request<Incoming>, _state: ()) -> Response {
+    let (head, body) = request.into_parts();
+    let head: RequestHead = head.into();
+    let body = BufferedBody::extract(&head, RawIncomingBody::from(body), BodySizeLimit::Disabled)
+        .await
+        .unwrap();
+    let trailers = body.trailers.unwrap_or_default();
+    Response::ok()
+        .set_typed_body(body.bytes)
+        .set_trailers(trailers)
+}
+
+#[tokio::test]
+async fn trailers_round_trip_over_http2() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(echo_trailers, ());
+
+    // HTTP/2 with prior knowledge, no TLS.
+    let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (mut sender, connection) = hyper::client::conn::http2::handshake(
+        hyper_util::rt::TokioExecutor::new(),
+        hyper_util::rt::TokioIo::new(stream),
+    )
+    .await
+    .unwrap();
+    tokio::task::spawn(connection);
+
+    let mut trailers = HeaderMap::new();
+    trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+    let frames = vec![
+        Ok::<_, Infallible>(Frame::data(Bytes::from_static(b"hello"))),
+        Ok(Frame::trailers(trailers)),
+    ];
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("http://{addr}/"))
+        .body(StreamBody::new(futures_util::stream::iter(frames)))
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body = response.into_body().collect().await.unwrap();
+    assert_eq!(body.trailers().unwrap()["x-checksum"], "abc");
+    assert_eq!(body.to_bytes(), "hello");
+}
+
+// Return the local address of the connection, as seen by the server.
+async fn echo_local_addr(request: Request<Incoming>, _state: ()) -> Response {
+    let local_addr = request.extensions().get::<LocalAddr>().unwrap();
+    Response::ok().set_typed_body(local_addr.to_string())
+}
+
+#[tokio::test]
+async fn local_addr_is_attached_to_incoming_requests() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(echo_local_addr, ());
+
+    let url = format!("http://{addr}");
+    let client = reqwest::Client::new();
+    // Send two requests over the same connection: they must both see
+    // the address of the listener the connection was accepted on.
+    for _ in 0..2 {
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), addr.to_string());
+    }
+}
+
+#[tokio::test]
+async fn requests_with_too_many_headers_are_rejected() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config().set_max_header_count(10))
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let url = format!("http://{addr}");
+    let client = reqwest::Client::new();
+    let send_with_n_headers = |n: usize| {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for i in 0..n {
+            headers.insert(
+                reqwest::header::HeaderName::try_from(format!("x-custom-{i}")).unwrap(),
+                reqwest::header::HeaderValue::from_static("value"),
+            );
+        }
+        client.get(&url).headers(headers).send()
+    };
+
+    let response = send_with_n_headers(2).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = send_with_n_headers(20).await.unwrap();
+    assert_eq!(response.status(), 431);
+}
+
+#[tokio::test]
+async fn requests_with_oversized_headers_are_rejected() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config().set_max_header_bytes(1024))
+        .listen(incoming)
+        .serve(test_handler, ());
+
+    let url = format!("http://{addr}");
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(&url)
+        .header("x-small", "a")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .get(&url)
+        .header("x-large", "a".repeat(2048))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 431);
+}
+
+// Take over the response flow and stream the request body back to the caller.
+async fn raw_echo(request: Request<Incoming>, _state: ()) -> Response {
+    let body = RawIncomingBody::from(request.into_body());
+    let sender = ResponseSender::extract().unwrap();
+    // There is a single sender per request.
+    assert!(ResponseSender::extract().is_err());
+    let _ = sender.send(Response::ok().set_raw_body(body));
+    // The response flow has been taken over: this response is discarded.
+    Response::internal_server_error()
+}
+
+#[tokio::test]
+async fn handlers_can_take_over_the_response_flow() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(raw_echo, ());
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}"))
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "hello");
+}
+
+// Extract the response sender, but never use it.
+async fn unused_sender(_request: Request<Incoming>, _state: ()) -> Response {
+    let sender = ResponseSender::extract().unwrap();
+    drop(sender);
+    Response::accepted()
+}
+
+#[tokio::test]
+async fn unused_response_senders_fall_back_to_the_returned_response() {
+    let (incoming, addr) = test_incoming().await;
+    Server::new()
+        .set_config(test_server_config())
+        .listen(incoming)
+        .serve(unused_sender, ());
+
+    let response = reqwest::get(format!("http://{addr}")).await.unwrap();
+    assert_eq!(response.status(), 202);
+}
+
+#[test]
+fn response_sender_is_unavailable_outside_of_the_server() {
+    assert!(ResponseSender::extract().is_err());
+}
@@ -76,6 +76,23 @@ enum Commands {
         /// the application to the specified path.
         #[clap(long, value_parser)]
         diagnostics: Option<PathBuf>,
+        /// Optional. If provided, pavex will write a report to the specified path, summarizing
+        /// how framework extractors (e.g. `BufferedBody`) are configured for each route.
+        #[clap(long, value_parser)]
+        extractor_report: Option<PathBuf>,
+        /// Optional. If provided, pavex will write a report to the specified path, listing
+        /// the error handler that is invoked for each fallible component, for each route.
+        #[clap(long, value_parser)]
+        error_handler_report: Option<PathBuf>,
+        /// Optional. If set, the code generated for each route is written to its own file
+        /// (`src/route_<n>.rs`) rather than being inlined in `src/lib.rs`.
+        #[clap(long)]
+        split_modules: bool,
+        /// Optional. If set, pavex runs the full analysis and prints a summary of the
+        /// application (routes, constructors, diagnostics) alongside the list of files it
+        /// would write, without touching the filesystem.
+        #[clap(long, conflicts_with_all = ["diagnostics", "extractor_report", "error_handler_report"])]
+        dry_run: bool,
         /// The path to the directory that will contain the manifest and the source code for the generated application crate.  
         /// If the provided path is relative, it is interpreted as relative to the root of the current workspace.
         #[clap(short, long, value_parser)]
@@ -148,8 +165,21 @@ fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
         Commands::Generate {
             blueprint,
             diagnostics,
+            extractor_report,
+            error_handler_report,
+            split_modules,
+            dry_run,
             output,
-        } => generate(blueprint, diagnostics, output, cli.color),
+        } => generate(
+            blueprint,
+            diagnostics,
+            extractor_report,
+            error_handler_report,
+            split_modules,
+            dry_run,
+            output,
+            cli.color,
+        ),
         Commands::New { path } => scaffold_project(path),
     }
 }
@@ -158,6 +188,10 @@ fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
 fn generate(
     blueprint: PathBuf,
     diagnostics: Option<PathBuf>,
+    extractor_report: Option<PathBuf>,
+    error_handler_report: Option<PathBuf>,
+    split_modules: bool,
+    dry_run: bool,
     output: PathBuf,
     color_profile: Color,
 ) -> Result<ExitCode, Box<dyn std::error::Error>> {
@@ -169,24 +203,51 @@ fn generate(
         Ok(a) => a,
         Err(errors) => {
             for e in errors {
-                if color_on_stderr {
-                    eprintln!("{}: {e:?}", "ERROR".bold().red());
-                } else {
-                    eprintln!("ERROR: {e:?}");
-                };
+                print_diagnostic(&e, color_on_stderr);
             }
             return Ok(ExitCode::FAILURE);
         }
     };
+    for warning in app.warning_reports() {
+        print_diagnostic(warning, color_on_stderr);
+    }
     if let Some(diagnostic_path) = diagnostics {
         app.diagnostic_representation()
             .persist_flat(&diagnostic_path)?;
     }
-    let generated_app = app.codegen()?;
+    if let Some(report_path) = extractor_report {
+        app.extractor_report().persist(&report_path)?;
+    }
+    if let Some(report_path) = error_handler_report {
+        app.error_handler_report().persist(&report_path)?;
+    }
+    let mut generated_app = app.codegen()?;
+    if split_modules {
+        generated_app = generated_app.split_modules();
+    }
+    if dry_run {
+        let files = generated_app.dry_run(&output)?;
+        print!("{}", app.summary().render());
+        println!("Files that would be written ({}):", files.len());
+        for file in files {
+            println!("    {}", file.display());
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
     generated_app.persist(&output)?;
     Ok(ExitCode::SUCCESS)
 }
 
+fn print_diagnostic(diagnostic: &miette::Error, color_on_stderr: bool) {
+    let is_warning = diagnostic.severity() == Some(miette::Severity::Warning);
+    match (is_warning, color_on_stderr) {
+        (true, true) => eprintln!("{}: {diagnostic:?}", "WARNING".bold().yellow()),
+        (true, false) => eprintln!("WARNING: {diagnostic:?}"),
+        (false, true) => eprintln!("{}: {diagnostic:?}", "ERROR".bold().red()),
+        (false, false) => eprintln!("ERROR: {diagnostic:?}"),
+    }
+}
+
 fn use_color_on_stderr(color_profile: Color) -> bool {
     match color_profile {
         Color::Auto => supports_color::on(Stream::Stderr).is_some(),
@@ -0,0 +1,40 @@
+use pavex::blueprint::{
+    constructor::Lifecycle,
+    router::{ANY, GET, POST},
+    Blueprint,
+};
+use pavex::f;
+
+pub struct Config;
+
+pub fn config() -> Config {
+    todo!()
+}
+
+pub struct Session;
+
+pub fn session(_c: &Config) -> Session {
+    todo!()
+}
+
+pub fn index(_s: &Session) -> pavex::response::Response {
+    todo!()
+}
+
+pub fn login(_s: Session) -> pavex::response::Response {
+    todo!()
+}
+
+pub fn health() -> pavex::response::Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::config), Lifecycle::Singleton);
+    bp.constructor(f!(crate::session), Lifecycle::RequestScoped);
+    bp.route(GET, "/", f!(crate::index));
+    bp.route(POST, "/login", f!(crate::login));
+    bp.route(ANY, "/health", f!(crate::health));
+    bp
+}
@@ -11,8 +11,8 @@ pub struct ApplicationState {
 }
 #[derive(Debug, thiserror::Error)]
 pub enum ApplicationStateError {
-    #[error(transparent)]
-    HttpClient(app::HttpClientError),
+    #[error("Failed to build a singleton: `app::http_client` returned an error")]
+    HttpClient(#[source] app::HttpClientError),
 }
 pub async fn build_application_state(
     v0: app::Config,
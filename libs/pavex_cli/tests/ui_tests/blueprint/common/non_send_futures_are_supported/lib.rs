@@ -0,0 +1,21 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub async fn handler() -> Response {
+    // `Rc` is not `Send`, therefore the future returned by this
+    // handler is not `Send` either.
+    let n_calls = Rc::new(Cell::new(0));
+    std::future::ready(()).await;
+    n_calls.set(n_calls.get() + 1);
+    Response::ok().set_typed_body(n_calls.get().to_string())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,43 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::http::{HeaderName, HeaderValue};
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+pub fn index() -> Response {
+    Response::ok()
+}
+
+#[derive(Debug)]
+pub struct ApiError;
+
+pub fn fallible() -> Result<Response, ApiError> {
+    Err(ApiError)
+}
+
+pub fn api_error2response(_e: &ApiError) -> Response {
+    Response::internal_server_error()
+}
+
+pub fn security_headers(response: Response, request_head: &RequestHead) -> Response {
+    let path = HeaderValue::from_str(request_head.target.path()).unwrap();
+    response
+        .insert_header(
+            HeaderName::from_static("x-content-type-options"),
+            HeaderValue::from_static("nosniff"),
+        )
+        .insert_header(
+            HeaderName::from_static("x-frame-options"),
+            HeaderValue::from_static("DENY"),
+        )
+        .insert_header(HeaderName::from_static("x-request-path"), path)
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/", f!(crate::index));
+    bp.route(GET, "/fallible", f!(crate::fallible))
+        .error_handler(f!(crate::api_error2response));
+    bp.response_hook(f!(crate::security_headers));
+    bp
+}
@@ -0,0 +1,59 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str) -> reqwest::Response {
+    reqwest::get(&format!("http://localhost:{}{}", port, path))
+        .await
+        .expect("Failed to make request")
+}
+
+fn assert_security_headers(response: &reqwest::Response, path: &str) {
+    let headers = response.headers();
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert_eq!(headers["x-request-path"], path);
+}
+
+#[tokio::test]
+async fn the_hook_is_applied_to_successful_responses() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/").await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_security_headers(&response, "/");
+}
+
+#[tokio::test]
+async fn the_hook_is_applied_to_error_responses() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/fallible").await;
+    assert_eq!(
+        StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+        response.status().as_u16()
+    );
+    assert_security_headers(&response, "/fallible");
+}
+
+#[tokio::test]
+async fn the_hook_is_applied_to_fallback_responses() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/unknown").await;
+    assert_eq!(StatusCode::NOT_FOUND.as_u16(), response.status().as_u16());
+    assert_security_headers(&response, "/unknown");
+}
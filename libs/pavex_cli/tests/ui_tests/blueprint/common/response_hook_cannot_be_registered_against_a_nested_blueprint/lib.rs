@@ -0,0 +1,24 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn handler() -> Response {
+    Response::ok()
+}
+
+pub fn hook(response: Response) -> Response {
+    response
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.nest_at("/api", api());
+    bp
+}
+
+fn api() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/", f!(crate::handler));
+    bp.response_hook(f!(crate::hook));
+    bp
+}
@@ -0,0 +1,16 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn handler() -> Response {
+    Response::ok()
+}
+
+pub fn hook(_response: Response) {}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/", f!(crate::handler));
+    bp.response_hook(f!(crate::hook));
+    bp
+}
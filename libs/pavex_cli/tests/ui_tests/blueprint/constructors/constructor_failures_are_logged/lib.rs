@@ -0,0 +1,47 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+#[derive(Debug)]
+pub struct DatabaseError;
+
+pub struct Connection;
+
+pub struct UserRepository {
+    _connection: Connection,
+}
+
+pub fn connection(_request_head: &RequestHead) -> Result<Connection, DatabaseError> {
+    Err(DatabaseError)
+}
+
+pub fn user_repository(connection: Connection) -> UserRepository {
+    UserRepository {
+        _connection: connection,
+    }
+}
+
+pub fn database_error(_e: &DatabaseError) -> Response {
+    Response::internal_server_error()
+}
+
+pub fn get_user(_repository: UserRepository) -> Response {
+    todo!()
+}
+
+pub fn report() -> Result<Response, DatabaseError> {
+    Err(DatabaseError)
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.log_constructor_failures();
+    bp.error_handler(f!(crate::database_error));
+    bp.constructor(f!(crate::connection), Lifecycle::RequestScoped);
+    bp.constructor(f!(crate::user_repository), Lifecycle::RequestScoped);
+    bp.route(GET, "/user", f!(crate::get_user));
+    // Fallible request handlers are not constructors: their failures are not logged.
+    bp.route(GET, "/report", f!(crate::report));
+    bp
+}
@@ -0,0 +1,38 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::http::{header::LOCATION, HeaderValue};
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+pub struct Session {
+    pub user: String,
+}
+
+pub fn session(request_head: &RequestHead) -> Result<Session, Response> {
+    match request_head.headers.get("x-user") {
+        Some(user) => Ok(Session {
+            user: user.to_str().unwrap_or_default().to_owned(),
+        }),
+        None => {
+            let redirect_response =
+                Response::see_other().insert_header(LOCATION, HeaderValue::from_static("/login"));
+            Err(redirect_response)
+        }
+    }
+}
+
+pub fn unauthorized(_redirect_response: &Response) -> Response {
+    Response::unauthorized()
+}
+
+pub fn handler(session: Session) -> Response {
+    Response::ok().set_typed_body(format!("Hello, {}!", session.user))
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::session), Lifecycle::RequestScoped)
+        .error_handler(f!(crate::unauthorized));
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,50 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+#[tokio::test]
+async fn the_error_handler_builds_the_response() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(format!("http://localhost:{port}/home"))
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(
+        StatusCode::UNAUTHORIZED.as_u16(),
+        response.status().as_u16()
+    );
+    assert!(response.headers().get("location").is_none());
+}
+
+#[tokio::test]
+async fn the_handler_is_invoked_if_the_constructor_succeeds() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::new()
+        .get(format!("http://localhost:{port}/home"))
+        .header("x-user", "ursula")
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), "Hello, ursula!");
+}
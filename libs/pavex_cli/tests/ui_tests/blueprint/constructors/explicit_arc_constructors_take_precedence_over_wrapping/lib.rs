@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Config {
+    pub greeting: String,
+}
+
+pub fn config() -> Config {
+    Config {
+        greeting: "Built by `config`".into(),
+    }
+}
+
+pub fn shared_config() -> Arc<Config> {
+    Arc::new(Config {
+        greeting: "Built by `shared_config`".into(),
+    })
+}
+
+pub fn handler(config: Arc<Config>) -> Response {
+    Response::ok().set_typed_body(config.greeting.clone())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::config), Lifecycle::Singleton);
+    bp.constructor(f!(crate::shared_config), Lifecycle::Singleton);
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,33 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct DbConfig {
+    pub reachable: bool,
+}
+
+#[derive(Clone)]
+pub struct DbPool;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The database is unreachable")]
+pub struct DbPoolError;
+
+pub fn db_pool(config: DbConfig) -> Result<DbPool, DbPoolError> {
+    if config.reachable {
+        Ok(DbPool)
+    } else {
+        Err(DbPoolError)
+    }
+}
+
+pub fn handler(_pool: DbPool) -> Response {
+    Response::ok()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::db_pool), Lifecycle::Singleton);
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,27 @@
+use std::error::Error;
+
+use app::DbConfig;
+use application::build_application_state;
+
+#[tokio::test]
+async fn a_failing_singleton_is_reported_at_startup() {
+    let err = match build_application_state(DbConfig { reachable: false }).await {
+        Ok(_) => panic!("Expected the application state to fail to build"),
+        Err(e) => e,
+    };
+    assert_eq!(
+        err.to_string(),
+        "Failed to build a singleton: `app::db_pool` returned an error"
+    );
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "The database is unreachable"
+    );
+}
+
+#[tokio::test]
+async fn the_application_state_is_built_if_all_singletons_succeed() {
+    assert!(build_application_state(DbConfig { reachable: true })
+        .await
+        .is_ok());
+}
@@ -0,0 +1,22 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+#[derive(Clone)]
+pub struct Cache;
+
+pub fn cache() -> Cache {
+    todo!()
+}
+
+pub fn handler(_cache: &Cache) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::cache), Lifecycle::Singleton)
+        .optional();
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,29 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Session;
+
+#[derive(Debug)]
+pub struct SessionError;
+
+pub fn session() -> Result<Session, SessionError> {
+    todo!()
+}
+
+pub fn session_error(_e: &SessionError) -> Response {
+    todo!()
+}
+
+pub fn handler(_session: &Session) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::session), Lifecycle::RequestScoped)
+        .error_handler(f!(crate::session_error))
+        .optional();
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,25 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+#[derive(Clone)]
+pub struct Cache;
+
+#[derive(Debug)]
+pub struct CacheError;
+
+pub fn cache() -> Result<Cache, CacheError> {
+    todo!()
+}
+
+pub fn handler(_cache: &Cache) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::cache), Lifecycle::Singleton)
+        .optional();
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,38 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct CacheConfig {
+    pub reachable: bool,
+}
+
+#[derive(Clone)]
+pub struct Cache;
+
+#[derive(Debug, thiserror::Error)]
+#[error("The cache is unreachable")]
+pub struct CacheError;
+
+pub fn cache(config: CacheConfig) -> Result<Cache, CacheError> {
+    if config.reachable {
+        Ok(Cache)
+    } else {
+        Err(CacheError)
+    }
+}
+
+pub fn handler(cache: &Option<Cache>) -> Response {
+    let body = match cache {
+        Some(_) => "Served from the cache",
+        None => "The cache is unavailable",
+    };
+    Response::ok().set_typed_body(body)
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::cache), Lifecycle::Singleton)
+        .optional();
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,42 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use app::CacheConfig;
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server(config: CacheConfig) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state(config).await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get_home(port: u16) -> reqwest::Response {
+    reqwest::get(&format!("http://localhost:{}/home", port))
+        .await
+        .expect("Failed to make request")
+}
+
+#[tokio::test]
+async fn the_application_starts_if_an_optional_singleton_fails() {
+    let port = spawn_test_server(CacheConfig { reachable: false }).await;
+    let response = get_home(port).await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), "The cache is unavailable");
+}
+
+#[tokio::test]
+async fn dependents_get_the_optional_singleton_if_it_was_built() {
+    let port = spawn_test_server(CacheConfig { reachable: true }).await;
+    let response = get_home(port).await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), "Served from the cache");
+}
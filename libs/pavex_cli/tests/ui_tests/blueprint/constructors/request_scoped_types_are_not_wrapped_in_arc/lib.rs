@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Config;
+
+pub fn config() -> Config {
+    todo!()
+}
+
+pub fn handler(_config: Arc<Config>) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::config), Lifecycle::RequestScoped);
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,29 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Settings {
+    pub config: Config,
+}
+
+pub struct Config;
+
+pub fn settings() -> Settings {
+    todo!()
+}
+
+pub fn config(settings: &Settings) -> &Config {
+    &settings.config
+}
+
+pub fn handler(_config: &Config) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::settings), Lifecycle::Singleton);
+    bp.constructor(f!(crate::config), Lifecycle::Singleton);
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,20 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Config;
+
+pub fn config() -> Result<Config, Response> {
+    todo!()
+}
+
+pub fn handler(_config: &Config) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::config), Lifecycle::Singleton);
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use pavex::blueprint::{
+    constructor::{CloningStrategy, Lifecycle},
+    router::GET,
+    Blueprint,
+};
+use pavex::f;
+use pavex::response::Response;
+
+#[derive(Clone)]
+pub struct Config {
+    pub greeting: String,
+}
+
+pub fn config() -> Config {
+    Config {
+        greeting: "Hello".into(),
+    }
+}
+
+pub fn greet(config: &Config) -> Response {
+    Response::ok().set_typed_body(config.greeting.clone())
+}
+
+pub fn shared(config: Arc<Config>) -> Response {
+    Response::ok().set_typed_body(config.greeting.clone())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    // `Arc::new` consumes `Config`, but `Config` must also be stored as is
+    // in the application state for `greet`: Pavex needs to clone it.
+    bp.constructor(f!(crate::config), Lifecycle::Singleton)
+        .cloning(CloningStrategy::CloneIfNecessary);
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp.route(GET, "/shared", f!(crate::shared));
+    bp
+}
@@ -0,0 +1,47 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+#[derive(Debug)]
+pub struct DatabaseError;
+
+pub fn report() -> Result<Response, DatabaseError> {
+    Err(DatabaseError)
+}
+
+pub fn public_report() -> Result<Response, DatabaseError> {
+    Err(DatabaseError)
+}
+
+pub fn audit() -> Result<Response, DatabaseError> {
+    Err(DatabaseError)
+}
+
+pub fn database_error(_e: &DatabaseError) -> Response {
+    Response::internal_server_error().set_typed_body("database error")
+}
+
+pub fn redacted_database_error(_e: &DatabaseError) -> Response {
+    Response::service_unavailable()
+}
+
+pub fn audit_error(_e: &DatabaseError) -> Response {
+    Response::forbidden()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.error_handler(f!(crate::database_error));
+    bp.route(GET, "/report", f!(crate::report));
+    bp.nest_at("/public", {
+        let mut bp = Blueprint::new();
+        // It overrides the parent's type-wide error handler for `DatabaseError`...
+        bp.error_handler(f!(crate::redacted_database_error));
+        bp.route(GET, "/report", f!(crate::public_report));
+        // ...but an error handler registered against a component always wins.
+        bp.route(GET, "/audit", f!(crate::audit))
+            .error_handler(f!(crate::audit_error));
+        bp
+    });
+    bp
+}
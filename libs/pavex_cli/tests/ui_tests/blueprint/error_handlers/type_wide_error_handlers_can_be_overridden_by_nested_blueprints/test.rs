@@ -0,0 +1,34 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+#[tokio::test]
+async fn the_closest_error_handler_is_invoked() {
+    let port = spawn_test_server().await;
+    for (path, expected) in [
+        ("report", StatusCode::INTERNAL_SERVER_ERROR),
+        ("public/report", StatusCode::SERVICE_UNAVAILABLE),
+        ("public/audit", StatusCode::FORBIDDEN),
+    ] {
+        let response = reqwest::get(format!("http://localhost:{port}/{path}"))
+            .await
+            .expect("Failed to make request");
+        assert_eq!(expected.as_u16(), response.status().as_u16(), "{path}");
+    }
+}
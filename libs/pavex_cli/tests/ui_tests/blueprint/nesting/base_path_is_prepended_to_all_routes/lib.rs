@@ -0,0 +1,24 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn health() -> Response {
+    Response::ok().set_typed_body("health")
+}
+
+pub fn user() -> Response {
+    Response::ok().set_typed_body("user")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.base_path("/service-a");
+    bp.route(GET, "/health", f!(crate::health));
+    // The base path stacks with nesting prefixes.
+    bp.nest_at("/api", {
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/users/:id", f!(crate::user));
+        bp
+    });
+    bp
+}
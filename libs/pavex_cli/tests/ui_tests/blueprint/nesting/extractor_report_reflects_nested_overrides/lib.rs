@@ -0,0 +1,39 @@
+use pavex::blueprint::{constructor::Lifecycle, router::POST, Blueprint};
+use pavex::f;
+use pavex::request::body::{BodySizeLimit, BufferedBody};
+use pavex::response::Response;
+
+pub fn default_body_size_limit() -> BodySizeLimit {
+    BodySizeLimit::Enabled {
+        max_n_bytes: 2_097_152, // 2 MBs
+    }
+}
+
+pub fn upload_size_limit() -> BodySizeLimit {
+    BodySizeLimit::Enabled {
+        max_n_bytes: 1_073_741_824, // 1 GB
+    }
+}
+
+pub fn comment(_body: BufferedBody) -> Response {
+    todo!()
+}
+
+pub fn upload(_body: BufferedBody) -> Response {
+    todo!()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::default_body_size_limit), Lifecycle::RequestScoped);
+    BufferedBody::register(&mut bp);
+    bp.route(POST, "/comment", f!(crate::comment));
+    bp.nest_at("/media", {
+        let mut bp = Blueprint::new();
+        // This limit only applies to the routes registered against the nested blueprint.
+        bp.constructor(f!(crate::upload_size_limit), Lifecycle::RequestScoped);
+        bp.route(POST, "/upload", f!(crate::upload));
+        bp
+    });
+    bp
+}
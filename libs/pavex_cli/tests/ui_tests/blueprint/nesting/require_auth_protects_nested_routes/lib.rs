@@ -0,0 +1,45 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+pub struct CurrentUser {
+    pub name: String,
+}
+
+pub fn authenticate(request_head: &RequestHead) -> Result<CurrentUser, Response> {
+    match request_head.headers.get("x-user") {
+        Some(name) => Ok(CurrentUser {
+            name: name.to_str().unwrap_or_default().to_owned(),
+        }),
+        None => Err(Response::unauthorized()),
+    }
+}
+
+pub fn greet(user: &CurrentUser) -> Response {
+    Response::ok().set_typed_body(format!("Hello, {}!", user.name))
+}
+
+pub fn ping() -> Response {
+    Response::ok()
+}
+
+pub fn public() -> Response {
+    Response::ok()
+}
+
+fn admin() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.require_auth(f!(crate::authenticate));
+    bp.route(GET, "/greet", f!(crate::greet));
+    // It doesn't ask for `CurrentUser`, but it's still protected.
+    bp.route(GET, "/ping", f!(crate::ping));
+    bp
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/public", f!(crate::public));
+    bp.nest_at("/admin", admin());
+    bp
+}
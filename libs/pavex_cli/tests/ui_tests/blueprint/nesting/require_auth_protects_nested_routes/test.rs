@@ -0,0 +1,56 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+#[tokio::test]
+async fn authenticated_requests_reach_the_handler() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::new()
+        .get(format!("http://localhost:{port}/admin/greet"))
+        .header("x-user", "ursula")
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), "Hello, ursula!");
+}
+
+#[tokio::test]
+async fn unauthenticated_requests_are_rejected() {
+    let port = spawn_test_server().await;
+    for path in ["/admin/greet", "/admin/ping"] {
+        let response = reqwest::get(format!("http://localhost:{port}{path}"))
+            .await
+            .expect("Failed to make request");
+        assert_eq!(
+            StatusCode::UNAUTHORIZED.as_u16(),
+            response.status().as_u16(),
+            "{path} should require authentication"
+        );
+    }
+}
+
+#[tokio::test]
+async fn routes_outside_the_protected_subtree_are_unaffected() {
+    let port = spawn_test_server().await;
+    let response = reqwest::get(format!("http://localhost:{port}/public"))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+}
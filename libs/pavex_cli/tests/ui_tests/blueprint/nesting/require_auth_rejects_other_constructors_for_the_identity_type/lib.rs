@@ -0,0 +1,45 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::request::RequestHead;
+use pavex::response::Response;
+
+pub struct CurrentUser {
+    pub name: String,
+}
+
+pub fn authenticate(request_head: &RequestHead) -> Result<CurrentUser, Response> {
+    match request_head.headers.get("x-user") {
+        Some(name) => Ok(CurrentUser {
+            name: name.to_str().unwrap_or_default().to_owned(),
+        }),
+        None => Err(Response::unauthorized()),
+    }
+}
+
+pub fn guest() -> CurrentUser {
+    CurrentUser {
+        name: "guest".into(),
+    }
+}
+
+pub fn greet(user: &CurrentUser) -> Response {
+    Response::ok().set_typed_body(format!("Hello, {}!", user.name))
+}
+
+fn admin() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.require_auth(f!(crate::authenticate));
+    // It would shadow the authenticator.
+    bp.constructor(f!(crate::guest), Lifecycle::RequestScoped);
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    // This one is fine: the authenticator takes precedence in the nested blueprint.
+    bp.constructor(f!(crate::guest), Lifecycle::RequestScoped);
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp.nest_at("/admin", admin());
+    bp
+}
@@ -0,0 +1,51 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::body::{LimitExceededPolicy, ResponseBodySizeLimit};
+use pavex::response::Response;
+
+pub fn small_limit() -> ResponseBodySizeLimit {
+    ResponseBodySizeLimit::Enabled {
+        max_n_bytes: 100,
+        on_exceeded: LimitExceededPolicy::Error,
+    }
+}
+
+pub fn large_limit() -> ResponseBodySizeLimit {
+    ResponseBodySizeLimit::Enabled {
+        max_n_bytes: 10_000,
+        on_exceeded: LimitExceededPolicy::Error,
+    }
+}
+
+pub fn small_report() -> Response {
+    Response::ok().set_typed_body(vec![0u8; 1_000])
+}
+
+pub fn large_report() -> Response {
+    Response::ok().set_typed_body(vec![0u8; 1_000])
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.nest_at("/small", {
+        let mut bp = Blueprint::new();
+        bp.constructor(f!(crate::small_limit), Lifecycle::RequestScoped);
+        bp.wrap(f!(pavex::response::body::ResponseBodySizeLimit::enforce))
+            .error_handler(f!(
+                pavex::response::body::errors::ResponseBodySizeLimitExceeded::into_response
+            ));
+        bp.route(GET, "/report", f!(crate::small_report));
+        bp
+    });
+    bp.nest_at("/large", {
+        let mut bp = Blueprint::new();
+        bp.constructor(f!(crate::large_limit), Lifecycle::RequestScoped);
+        bp.wrap(f!(pavex::response::body::ResponseBodySizeLimit::enforce))
+            .error_handler(f!(
+                pavex::response::body::errors::ResponseBodySizeLimitExceeded::into_response
+            ));
+        bp.route(GET, "/report", f!(crate::large_report));
+        bp
+    });
+    bp
+}
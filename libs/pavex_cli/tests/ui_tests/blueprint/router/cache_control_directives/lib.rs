@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::http::header::{HeaderValue, CACHE_CONTROL};
+use pavex::response::{CacheControl, Response};
+
+pub fn logo() -> Response {
+    Response::ok()
+}
+
+pub fn account() -> Response {
+    Response::ok()
+}
+
+pub fn profile() -> Response {
+    Response::ok().insert_header(CACHE_CONTROL, HeaderValue::from_static("private"))
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/logo", f!(crate::logo))
+        .cache_control(CacheControl::public().max_age(Duration::from_secs(3600)));
+    bp.route(GET, "/account", f!(crate::account))
+        .cache_control(CacheControl::no_store());
+    bp.route(GET, "/profile", f!(crate::profile))
+        .cache_control(CacheControl::public().max_age(Duration::from_secs(60)));
+    bp
+}
@@ -0,0 +1,57 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::header::CACHE_CONTROL;
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str) -> reqwest::Response {
+    let response = reqwest::get(&format!("http://localhost:{}{}", port, path))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    response
+}
+
+#[tokio::test]
+async fn public_max_age_directives_are_set() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/logo").await;
+    assert_eq!(
+        response.headers()[CACHE_CONTROL.as_str()],
+        "public, max-age=3600"
+    );
+}
+
+#[tokio::test]
+async fn no_store_directives_are_set() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/account").await;
+    assert_eq!(response.headers()[CACHE_CONTROL.as_str()], "no-store");
+}
+
+#[tokio::test]
+async fn the_header_set_by_the_handler_is_not_overridden() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/profile").await;
+    let values: Vec<_> = response
+        .headers()
+        .get_all(CACHE_CONTROL.as_str())
+        .iter()
+        .collect();
+    assert_eq!(values, vec!["private"]);
+}
@@ -0,0 +1,14 @@
+use pavex::blueprint::{router::MethodGuard, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn propfind() -> Response {
+    Response::ok().set_typed_body("Listing properties")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    let propfind = MethodGuard::from_bytes(b"PROPFIND").unwrap();
+    bp.route(propfind, "/files", f!(crate::propfind));
+    bp
+}
@@ -0,0 +1,52 @@
+use pavex::blueprint::{
+    router::{GET, POST},
+    Blueprint,
+};
+use pavex::f;
+use pavex::http::header::{HeaderValue, ALLOW};
+use pavex::response::Response;
+use pavex::router::AllowedMethods;
+
+pub fn list_users() -> Response {
+    Response::ok()
+}
+
+pub fn create_user() -> Response {
+    Response::created()
+}
+
+pub fn legacy() -> Response {
+    Response::ok()
+}
+
+pub fn method_not_allowed(allowed_methods: &AllowedMethods) -> Response {
+    let allowed: Vec<&str> = match allowed_methods {
+        AllowedMethods::Some(methods) => methods.iter().map(|m| m.as_str()).collect(),
+        AllowedMethods::All => vec![],
+    };
+    Response::method_not_allowed().set_typed_body(format!(
+        "Use one of the following methods: {}",
+        allowed.join(", ")
+    ))
+}
+
+pub fn legacy_method_not_allowed() -> Response {
+    // Our reverse proxy takes care of `HEAD` requests for this endpoint.
+    Response::method_not_allowed()
+        .insert_header(ALLOW, HeaderValue::from_static("GET, HEAD"))
+        .set_typed_body("This endpoint is read-only")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/users", f!(crate::list_users));
+    bp.route(POST, "/users", f!(crate::create_user));
+    bp.method_not_allowed(f!(crate::method_not_allowed));
+    bp.nest({
+        let mut bp = Blueprint::new();
+        bp.route(GET, "/legacy", f!(crate::legacy));
+        bp.method_not_allowed(f!(crate::legacy_method_not_allowed));
+        bp
+    });
+    bp
+}
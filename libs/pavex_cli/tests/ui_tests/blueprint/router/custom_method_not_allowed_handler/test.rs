@@ -0,0 +1,67 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::header::ALLOW;
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+#[tokio::test]
+async fn the_custom_handler_renders_the_body_and_pavex_sets_the_allow_header() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::new()
+        .delete(&format!("http://localhost:{}/users", port))
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(
+        StatusCode::METHOD_NOT_ALLOWED.as_u16(),
+        response.status().as_u16()
+    );
+    assert_eq!(response.headers()[ALLOW.as_str()], "GET,POST");
+    assert_eq!(
+        response.text().await.unwrap(),
+        "Use one of the following methods: GET, POST"
+    );
+}
+
+#[tokio::test]
+async fn the_custom_handler_can_override_the_allow_header() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::new()
+        .post(&format!("http://localhost:{}/legacy", port))
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(
+        StatusCode::METHOD_NOT_ALLOWED.as_u16(),
+        response.status().as_u16()
+    );
+    let allow_headers: Vec<_> = response.headers().get_all(ALLOW.as_str()).iter().collect();
+    assert_eq!(allow_headers, vec!["GET, HEAD"]);
+    assert_eq!(response.text().await.unwrap(), "This endpoint is read-only");
+}
+
+#[tokio::test]
+async fn unmatched_paths_are_still_handled_by_the_fallback() {
+    let port = spawn_test_server().await;
+    let response = reqwest::Client::new()
+        .delete(&format!("http://localhost:{}/unknown", port))
+        .send()
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::NOT_FOUND.as_u16(), response.status().as_u16());
+}
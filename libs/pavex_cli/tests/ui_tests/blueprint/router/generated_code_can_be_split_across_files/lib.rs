@@ -0,0 +1,18 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn first() -> Response {
+    Response::ok().set_typed_body("first")
+}
+
+pub fn second() -> Response {
+    Response::ok().set_typed_body("second")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/first", f!(crate::first));
+    bp.route(GET, "/second", f!(crate::second));
+    bp
+}
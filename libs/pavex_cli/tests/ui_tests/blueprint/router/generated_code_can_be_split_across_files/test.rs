@@ -0,0 +1,40 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+#[tokio::test]
+async fn requests_are_routed_to_the_right_module() {
+    let port = spawn_test_server().await;
+    for path in ["first", "second"] {
+        let response = reqwest::get(format!("http://localhost:{port}/{path}"))
+            .await
+            .expect("Failed to make request");
+        assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+        assert_eq!(response.text().await.unwrap(), path);
+    }
+}
+
+#[tokio::test]
+async fn the_fallback_is_invoked_for_unknown_paths() {
+    let port = spawn_test_server().await;
+    let response = reqwest::get(format!("http://localhost:{port}/third"))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::NOT_FOUND.as_u16(), response.status().as_u16());
+}
@@ -0,0 +1,54 @@
+use http_body_util::BodyExt;
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::http::{HeaderMap, Method, Version};
+use pavex::request::RequestHead;
+use pavex::response::Response;
+use pavex::router::Dispatcher;
+
+fn get(path: &str) -> RequestHead {
+    RequestHead {
+        method: Method::GET,
+        target: path.parse().unwrap(),
+        version: Version::HTTP_11,
+        headers: HeaderMap::new(),
+    }
+}
+
+pub async fn greet(dispatcher: &Dispatcher) -> Response {
+    let mut greeting = Vec::new();
+    for path in ["/hello", "/world"] {
+        let response = match dispatcher.dispatch(get(path), "").await {
+            Ok(r) => r,
+            Err(e) => return e.into_response(),
+        };
+        let (_, body) = response.into_parts();
+        let bytes = body.collect().await.unwrap().to_bytes();
+        greeting.push(String::from_utf8(bytes.to_vec()).unwrap());
+    }
+    Response::ok().set_typed_body(greeting.join(", "))
+}
+
+pub fn hello() -> Response {
+    Response::ok().set_typed_body("Hello")
+}
+
+pub async fn looping(dispatcher: &Dispatcher) -> Response {
+    match dispatcher.dispatch(get("/loop"), "").await {
+        Ok(r) => r,
+        Err(e) => e.into_response(),
+    }
+}
+
+pub fn world() -> Response {
+    Response::ok().set_typed_body("world")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp.route(GET, "/hello", f!(crate::hello));
+    bp.route(GET, "/loop", f!(crate::looping));
+    bp.route(GET, "/world", f!(crate::world));
+    bp
+}
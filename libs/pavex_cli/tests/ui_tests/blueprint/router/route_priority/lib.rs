@@ -0,0 +1,30 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn post() -> Response {
+    Response::ok().set_typed_body("post")
+}
+
+pub fn latest_post() -> Response {
+    Response::ok().set_typed_body("latest post")
+}
+
+pub fn user() -> Response {
+    Response::ok().set_typed_body("user")
+}
+
+pub fn current_user() -> Response {
+    Response::ok().set_typed_body("current user")
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    // Default precedence: the static route wins.
+    bp.route(GET, "/posts/:id", f!(crate::post));
+    bp.route(GET, "/posts/latest", f!(crate::latest_post));
+    // The parametric route has a higher priority: it wins.
+    bp.route(GET, "/users/:id", f!(crate::user)).priority(1);
+    bp.route(GET, "/users/me", f!(crate::current_user));
+    bp
+}
@@ -0,0 +1,41 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str) -> String {
+    let response = reqwest::get(&format!("http://localhost:{}{}", port, path))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    response.text().await.unwrap()
+}
+
+#[tokio::test]
+async fn static_routes_win_by_default() {
+    let port = spawn_test_server().await;
+    assert_eq!(get(port, "/posts/latest").await, "latest post");
+    assert_eq!(get(port, "/posts/1").await, "post");
+}
+
+#[tokio::test]
+async fn routes_with_a_higher_priority_win() {
+    let port = spawn_test_server().await;
+    assert_eq!(get(port, "/users/me").await, "user");
+    assert_eq!(get(port, "/users/1").await, "user");
+}
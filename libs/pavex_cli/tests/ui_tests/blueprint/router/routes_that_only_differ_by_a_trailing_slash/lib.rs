@@ -0,0 +1,14 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub fn handler() -> Response {
+    Response::ok()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/users", f!(crate::handler));
+    bp.route(GET, "/users/", f!(crate::handler));
+    bp
+}
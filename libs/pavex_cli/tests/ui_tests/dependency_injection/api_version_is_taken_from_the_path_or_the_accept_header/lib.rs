@@ -0,0 +1,25 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::request::version::ApiVersion;
+use pavex::response::Response;
+
+pub fn greet(version: ApiVersion) -> Response {
+    Response::ok().set_typed_body(version.to_string())
+}
+
+fn versioned() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/greet", f!(crate::greet));
+    bp
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    ApiVersion::register(&mut bp);
+    bp.route(GET, "/greet", f!(crate::greet));
+    // The `v3` segment wasn't added by `ApiVersion::nest`: it must be ignored.
+    bp.route(GET, "/archive/v3", f!(crate::greet));
+    ApiVersion::new(1).nest(&mut bp, versioned());
+    ApiVersion::new(2).nest(&mut bp, versioned());
+    bp
+}
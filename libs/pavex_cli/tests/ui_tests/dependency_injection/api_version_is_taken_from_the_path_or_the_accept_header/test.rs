@@ -0,0 +1,64 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str, accept: Option<&str>) -> reqwest::Response {
+    let mut request = reqwest::Client::new().get(format!("http://localhost:{port}{path}"));
+    if let Some(accept) = accept {
+        request = request.header("accept", accept);
+    }
+    request.send().await.expect("Failed to make request")
+}
+
+#[tokio::test]
+async fn nested_routes_get_their_version_from_the_path() {
+    let port = spawn_test_server().await;
+    for (path, expected) in [("/v1/greet", "v1"), ("/v2/greet", "v2")] {
+        let response = get(port, path, None).await;
+        assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+        assert_eq!(response.text().await.unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn the_path_takes_precedence_over_the_accept_header() {
+    let port = spawn_test_server().await;
+    let response = get(port, "/v2/greet", Some("application/vnd.api.v1+json")).await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), "v2");
+}
+
+#[tokio::test]
+async fn other_routes_get_their_version_from_the_accept_header() {
+    let port = spawn_test_server().await;
+    for path in ["/greet", "/archive/v3"] {
+        let response = get(port, path, Some("application/vnd.api.v2+json")).await;
+        assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+        assert_eq!(response.text().await.unwrap(), "v2");
+    }
+}
+
+#[tokio::test]
+async fn other_routes_fail_without_a_versioned_accept_header() {
+    let port = spawn_test_server().await;
+    for path in ["/greet", "/archive/v3"] {
+        let response = get(port, path, None).await;
+        assert_eq!(StatusCode::BAD_REQUEST.as_u16(), response.status().as_u16());
+    }
+}
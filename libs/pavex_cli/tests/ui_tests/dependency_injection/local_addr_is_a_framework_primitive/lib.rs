@@ -0,0 +1,14 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::connection::LocalAddr;
+use pavex::f;
+use pavex::response::Response;
+
+pub fn handler(local_addr: LocalAddr) -> Response {
+    Response::ok().set_typed_body(local_addr.to_string())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
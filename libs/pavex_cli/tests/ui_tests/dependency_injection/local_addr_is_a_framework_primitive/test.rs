@@ -0,0 +1,27 @@
+use std::future::IntoFuture;
+use std::net::{SocketAddr, TcpListener};
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let addr = listener.local_addr().expect("Failed to get local address");
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    addr
+}
+
+#[tokio::test]
+async fn handlers_see_the_local_address_of_the_connection() {
+    let addr = spawn_test_server().await;
+
+    let response = reqwest::get(&format!("http://{addr}/"))
+        .await
+        .expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.text().await.unwrap(), addr.to_string());
+}
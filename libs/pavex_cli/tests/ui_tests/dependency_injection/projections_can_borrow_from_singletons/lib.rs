@@ -0,0 +1,53 @@
+use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::f;
+use pavex::response::Response;
+
+pub struct Settings {
+    pub database: DatabaseSettings,
+    pub redis: RedisSettings,
+}
+
+pub struct DatabaseSettings {
+    pub url: String,
+}
+
+pub struct RedisSettings {
+    pub url: String,
+}
+
+pub fn settings() -> Settings {
+    Settings {
+        database: DatabaseSettings {
+            url: "postgres://localhost:5432".into(),
+        },
+        redis: RedisSettings {
+            url: "redis://localhost:6379".into(),
+        },
+    }
+}
+
+pub fn database(settings: &Settings) -> &DatabaseSettings {
+    &settings.database
+}
+
+pub fn redis(settings: &Settings) -> &RedisSettings {
+    &settings.redis
+}
+
+pub fn database_url(settings: &DatabaseSettings) -> Response {
+    Response::ok().set_typed_body(settings.url.clone())
+}
+
+pub fn redis_url(settings: &RedisSettings) -> Response {
+    Response::ok().set_typed_body(settings.url.clone())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::settings), Lifecycle::Singleton);
+    bp.constructor(f!(crate::database), Lifecycle::RequestScoped);
+    bp.constructor(f!(crate::redis), Lifecycle::RequestScoped);
+    bp.route(GET, "/database", f!(crate::database_url));
+    bp.route(GET, "/redis", f!(crate::redis_url));
+    bp
+}
@@ -0,0 +1,16 @@
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::request::id::{RequestId, RequestIdConfig};
+use pavex::response::Response;
+
+pub fn handler(request_id: &RequestId) -> Response {
+    Response::ok().set_typed_body(request_id.to_string())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    RequestIdConfig::register(&mut bp);
+    RequestId::register(&mut bp);
+    bp.route(GET, "/", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,16 @@
+use pavex::blueprint::{router::POST, Blueprint};
+use pavex::f;
+use pavex::request::body::RawIncomingBody;
+use pavex::response::{Response, ResponseSender};
+
+pub async fn echo(body: RawIncomingBody, sender: ResponseSender) -> Response {
+    let _ = sender.send(Response::ok().set_raw_body(body));
+    Response::no_content()
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    ResponseSender::register(&mut bp);
+    bp.route(POST, "/echo", f!(crate::echo));
+    bp
+}
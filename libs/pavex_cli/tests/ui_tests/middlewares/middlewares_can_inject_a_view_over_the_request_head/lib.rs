@@ -0,0 +1,36 @@
+use std::future::IntoFuture;
+
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::http::{HeaderName, HeaderValue};
+use pavex::middleware::Next;
+use pavex::request::{RequestHead, RequestHeadView};
+use pavex::response::Response;
+
+pub async fn mw<C>(head: RequestHeadView<'_>, next: Next<C>) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    // The view borrows from the request head: we must extract what we need
+    // before handing control over to the rest of the pipeline.
+    let method = HeaderValue::from_str(head.method.as_str()).unwrap();
+    let query = head.query.map(|q| HeaderValue::from_str(q).unwrap());
+    let response = next.await;
+    let response = response.insert_header(HeaderName::from_static("x-method"), method);
+    match query {
+        Some(query) => response.insert_header(HeaderName::from_static("x-query"), query),
+        None => response,
+    }
+}
+
+pub fn handler(head: &RequestHead) -> Response {
+    Response::ok().set_typed_body(head.target.path().to_owned())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    RequestHeadView::register(&mut bp);
+    bp.wrap(f!(crate::mw));
+    bp.route(GET, "/home", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,46 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str) -> reqwest::Response {
+    reqwest::get(&format!("http://localhost:{}{}", port, path))
+        .await
+        .expect("Failed to make request")
+}
+
+#[tokio::test]
+async fn middlewares_can_read_the_request_head_via_a_view() {
+    let port = spawn_test_server().await;
+
+    let response = get(port, "/home?greeting=hi").await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.headers()["x-method"], "GET");
+    assert_eq!(response.headers()["x-query"], "greeting=hi");
+    assert_eq!(response.text().await.unwrap(), "/home");
+}
+
+#[tokio::test]
+async fn the_view_is_available_for_requests_handled_by_the_fallback() {
+    let port = spawn_test_server().await;
+
+    let response = get(port, "/unknown").await;
+    assert_eq!(StatusCode::NOT_FOUND.as_u16(), response.status().as_u16());
+    assert_eq!(response.headers()["x-method"], "GET");
+    assert!(response.headers().get("x-query").is_none());
+}
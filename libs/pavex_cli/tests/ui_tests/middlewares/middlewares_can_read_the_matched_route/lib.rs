@@ -0,0 +1,30 @@
+use std::future::IntoFuture;
+
+use pavex::blueprint::{router::GET, Blueprint};
+use pavex::f;
+use pavex::http::{HeaderName, HeaderValue};
+use pavex::middleware::Next;
+use pavex::request::path::MatchedPathPattern;
+use pavex::response::Response;
+
+pub async fn mw<C>(route: MatchedPathPattern, next: Next<C>) -> Response
+where
+    C: IntoFuture<Output = Response>,
+{
+    let response = next.await;
+    response.insert_header(
+        HeaderName::from_static("x-matched-route"),
+        HeaderValue::from_static(route.inner()),
+    )
+}
+
+pub fn handler(route: MatchedPathPattern) -> Response {
+    Response::ok().set_typed_body(route.to_string())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.wrap(f!(crate::mw));
+    bp.route(GET, "/users/:id", f!(crate::handler));
+    bp
+}
@@ -0,0 +1,44 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+async fn get(port: u16, path: &str) -> reqwest::Response {
+    reqwest::get(&format!("http://localhost:{}{}", port, path))
+        .await
+        .expect("Failed to make request")
+}
+
+#[tokio::test]
+async fn middlewares_see_the_matched_route() {
+    let port = spawn_test_server().await;
+
+    let response = get(port, "/users/42").await;
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    assert_eq!(response.headers()["x-matched-route"], "/users/:id");
+    assert_eq!(response.text().await.unwrap(), "/users/:id");
+}
+
+#[tokio::test]
+async fn middlewares_see_a_wildcard_for_unmatched_requests() {
+    let port = spawn_test_server().await;
+
+    let response = get(port, "/unknown").await;
+    assert_eq!(StatusCode::NOT_FOUND.as_u16(), response.status().as_u16());
+    assert_eq!(response.headers()["x-matched-route"], "*");
+}
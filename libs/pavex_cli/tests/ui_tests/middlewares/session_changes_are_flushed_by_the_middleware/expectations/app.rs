@@ -0,0 +1,183 @@
+//! Do NOT edit this code.
+//! It was automatically generated by Pavex.
+//! All manual edits will be lost next time the code is generated.
+extern crate alloc;
+struct ServerState {
+    router: matchit::Router<u32>,
+    application_state: ApplicationState,
+}
+pub struct ApplicationState {
+    s0: pavex::session::SessionConfig,
+}
+pub async fn build_application_state() -> crate::ApplicationState {
+    let v0 = app::session_config();
+    crate::ApplicationState { s0: v0 }
+}
+pub fn run(
+    server_builder: pavex::server::Server,
+    application_state: ApplicationState,
+) -> pavex::server::ServerHandle {
+    let server_state = std::sync::Arc::new(ServerState {
+        router: build_router(),
+        application_state,
+    });
+    server_builder.serve(route_request, server_state)
+}
+fn build_router() -> matchit::Router<u32> {
+    let mut router = matchit::Router::new();
+    router.insert("/visit", 0u32).unwrap();
+    router
+}
+async fn route_request(
+    request: http::Request<hyper::body::Incoming>,
+    server_state: std::sync::Arc<ServerState>,
+) -> pavex::response::Response {
+    let (request_head, request_body) = request.into_parts();
+    #[allow(unused)]
+    let request_body = pavex::request::body::RawIncomingBody::from(request_body);
+    let request_head: pavex::request::RequestHead = request_head.into();
+    let matched_route = match server_state.router.at(&request_head.target.path()) {
+        Ok(m) => m,
+        Err(_) => {
+            let allowed_methods: pavex::router::AllowedMethods = pavex::router::MethodAllowList::from_iter(
+                    vec![],
+                )
+                .into();
+            return route_1::middleware_0(
+                    &server_state.application_state.s0,
+                    &request_head,
+                    &allowed_methods,
+                )
+                .await;
+        }
+    };
+    let route_id = matched_route.value;
+    #[allow(unused)]
+    let url_params: pavex::request::path::RawPathParams<'_, '_> = matched_route
+        .params
+        .into();
+    match route_id {
+        0u32 => {
+            match &request_head.method {
+                &pavex::http::Method::GET => {
+                    route_0::middleware_0(
+                            &server_state.application_state.s0,
+                            &request_head,
+                        )
+                        .await
+                }
+                _ => {
+                    let allowed_methods: pavex::router::AllowedMethods = pavex::router::MethodAllowList::from_iter([
+                            pavex::http::Method::GET,
+                        ])
+                        .into();
+                    route_1::middleware_0(
+                            &server_state.application_state.s0,
+                            &request_head,
+                            &allowed_methods,
+                        )
+                        .await
+                }
+            }
+        }
+        i => unreachable!("Unknown route id: {}", i),
+    }
+}
+pub mod route_0 {
+    pub async fn middleware_0(
+        v0: &pavex::session::SessionConfig,
+        v1: &pavex::request::RequestHead,
+    ) -> pavex::response::Response {
+        let v2 = pavex::session::Session::<app::Counter>::extract(v1, v0);
+        let v3 = match v2 {
+            Ok(ok) => ok,
+            Err(v3) => {
+                return {
+                    let v4 = pavex::session::errors::InvalidSession::into_response(&v3);
+                    <pavex::response::Response as pavex::response::IntoResponse>::into_response(
+                        v4,
+                    )
+                };
+            }
+        };
+        let v4 = <pavex::session::Session<app::Counter> as core::clone::Clone>::clone(
+            &v3,
+        );
+        let v5 = crate::route_0::Next0 {
+            s_0: &v3,
+            next: handler,
+        };
+        let v6 = pavex::middleware::Next::new(v5);
+        pavex::session::Session::<app::Counter>::flush(v6, v0, v4).await
+    }
+    pub async fn handler(v0: &pavex::session::Session<app::Counter>) -> pavex::response::Response {
+        let v1 = app::visit(v0);
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v1)
+    }
+    pub struct Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        s_0: &'a pavex::session::Session<app::Counter>,
+        next: fn(&'a pavex::session::Session<app::Counter>) -> T,
+    }
+    impl<'a, T> std::future::IntoFuture for Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        type Output = pavex::response::Response;
+        type IntoFuture = T;
+        fn into_future(self) -> Self::IntoFuture {
+            (self.next)(self.s_0)
+        }
+    }
+}
+pub mod route_1 {
+    pub async fn middleware_0(
+        v0: &pavex::session::SessionConfig,
+        v1: &pavex::request::RequestHead,
+        v2: &pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let v3 = pavex::session::Session::<app::Counter>::extract(v1, v0);
+        let v4 = match v3 {
+            Ok(ok) => ok,
+            Err(v4) => {
+                return {
+                    let v5 = pavex::session::errors::InvalidSession::into_response(&v4);
+                    <pavex::response::Response as pavex::response::IntoResponse>::into_response(
+                        v5,
+                    )
+                };
+            }
+        };
+        let v6 = crate::route_1::Next0 {
+            s_0: v2,
+            next: handler,
+        };
+        let v7 = pavex::middleware::Next::new(v6);
+        pavex::session::Session::<app::Counter>::flush(v7, v0, v4).await
+    }
+    pub async fn handler(
+        v0: &pavex::router::AllowedMethods,
+    ) -> pavex::response::Response {
+        let v1 = pavex::router::default_fallback(v0).await;
+        <pavex::response::Response as pavex::response::IntoResponse>::into_response(v1)
+    }
+    pub struct Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        s_0: &'a pavex::router::AllowedMethods,
+        next: fn(&'a pavex::router::AllowedMethods) -> T,
+    }
+    impl<'a, T> std::future::IntoFuture for Next0<'a, T>
+    where
+        T: std::future::Future<Output = pavex::response::Response>,
+    {
+        type Output = pavex::response::Response;
+        type IntoFuture = T;
+        fn into_future(self) -> Self::IntoFuture {
+            (self.next)(self.s_0)
+        }
+    }
+}
\ No newline at end of file
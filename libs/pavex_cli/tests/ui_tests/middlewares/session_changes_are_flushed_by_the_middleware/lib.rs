@@ -0,0 +1,37 @@
+use pavex::blueprint::{
+    constructor::{CloningStrategy, Lifecycle},
+    router::GET,
+    Blueprint,
+};
+use pavex::f;
+use pavex::response::Response;
+use pavex::session::{Key, Session, SessionConfig};
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct Counter {
+    pub visits: u64,
+}
+
+pub fn session_config() -> SessionConfig {
+    SessionConfig::new(Key::from(&[42; 64]))
+}
+
+pub fn visit(session: &Session<Counter>) -> Response {
+    let mut counter = session.get_mut();
+    counter.visits += 1;
+    Response::ok().set_typed_body(counter.visits.to_string())
+}
+
+pub fn blueprint() -> Blueprint {
+    let mut bp = Blueprint::new();
+    bp.constructor(f!(crate::session_config), Lifecycle::Singleton);
+    bp.constructor(
+        f!(pavex::session::Session::<crate::Counter>::extract),
+        Lifecycle::RequestScoped,
+    )
+    .cloning(CloningStrategy::CloneIfNecessary)
+    .error_handler(f!(pavex::session::errors::InvalidSession::into_response));
+    bp.wrap(f!(pavex::session::Session::<crate::Counter>::flush));
+    bp.route(GET, "/visit", f!(crate::visit));
+    bp
+}
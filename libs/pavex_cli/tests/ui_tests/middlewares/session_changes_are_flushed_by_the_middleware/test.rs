@@ -0,0 +1,67 @@
+use std::future::IntoFuture;
+use std::net::TcpListener;
+
+use application::{build_application_state, run};
+use pavex::http::StatusCode;
+
+async fn spawn_test_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to listen on a random port");
+    let port = listener
+        .local_addr()
+        .expect("Failed to get local address")
+        .port();
+    let incoming_stream: pavex::server::IncomingStream =
+        listener.try_into().expect("Failed to convert listener");
+    let server = pavex::server::Server::new().listen(incoming_stream);
+    let application_state = build_application_state().await;
+    tokio::task::spawn(run(server, application_state).into_future());
+    port
+}
+
+/// Visit `/visit`, sending `cookie` if provided.
+///
+/// It returns the response body and the session cookie set by the server, if any.
+async fn visit(port: u16, cookie: Option<&str>) -> (String, Option<String>) {
+    let mut request = reqwest::Client::new().get(format!("http://localhost:{port}/visit"));
+    if let Some(cookie) = cookie {
+        request = request.header("cookie", cookie);
+    }
+    let response = request.send().await.expect("Failed to make request");
+    assert_eq!(StatusCode::OK.as_u16(), response.status().as_u16());
+    // Only keep the `name=value` pair, dropping the cookie attributes.
+    let cookie = response.headers().get("set-cookie").map(|value| {
+        value
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_owned()
+    });
+    (response.text().await.unwrap(), cookie)
+}
+
+#[tokio::test]
+async fn changes_made_by_the_handler_are_written_back_by_the_middleware() {
+    let port = spawn_test_server().await;
+
+    let (body, cookie) = visit(port, None).await;
+    assert_eq!(body, "1");
+    let cookie = cookie.expect("The session cookie wasn't set");
+    assert!(cookie.starts_with("session="));
+
+    let (body, cookie) = visit(port, Some(&cookie)).await;
+    assert_eq!(body, "2");
+    let cookie = cookie.expect("The session cookie wasn't updated");
+
+    let (body, _) = visit(port, Some(&cookie)).await;
+    assert_eq!(body, "3");
+}
+
+#[tokio::test]
+async fn tampered_cookies_are_reset() {
+    let port = spawn_test_server().await;
+    let (body, cookie) = visit(port, Some("session=tampered")).await;
+    assert_eq!(body, "1");
+    assert!(cookie.is_some());
+}
@@ -12,6 +12,10 @@ use pavex::blueprint::Blueprint;
 pub struct GenerateBuilder {
     cmd: Command,
     diagnostics_path: Option<PathBuf>,
+    extractor_report_path: Option<PathBuf>,
+    error_handler_report_path: Option<PathBuf>,
+    split_modules: bool,
+    dry_run: bool,
     blueprint: Blueprint,
     output_directory: PathBuf,
 }
@@ -20,6 +24,10 @@ impl GenerateBuilder {
     pub(crate) fn new(cmd: Command, blueprint: Blueprint, output_directory: PathBuf) -> Self {
         Self {
             diagnostics_path: None,
+            extractor_report_path: None,
+            error_handler_report_path: None,
+            split_modules: false,
+            dry_run: false,
             blueprint,
             cmd,
             output_directory,
@@ -70,6 +78,18 @@ impl GenerateBuilder {
         if let Some(path) = self.diagnostics_path {
             self.cmd.arg("--diagnostics").arg(path);
         }
+        if let Some(path) = self.extractor_report_path {
+            self.cmd.arg("--extractor-report").arg(path);
+        }
+        if let Some(path) = self.error_handler_report_path {
+            self.cmd.arg("--error-handler-report").arg(path);
+        }
+        if self.split_modules {
+            self.cmd.arg("--split-modules");
+        }
+        if self.dry_run {
+            self.cmd.arg("--dry-run");
+        }
         Ok(self.cmd)
     }
 
@@ -83,4 +103,48 @@ impl GenerateBuilder {
         self.diagnostics_path = Some(path);
         self
     }
+
+    /// Set the path to the file that Pavex will use to write a summary of how
+    /// framework extractors (e.g. `BufferedBody`) are configured for each route.
+    ///
+    /// It can be used to audit security-relevant settings, such as body size limits.
+    ///
+    /// If this is not set, Pavex will not produce the report.
+    pub fn extractor_report_path(mut self, path: PathBuf) -> Self {
+        self.extractor_report_path = Some(path);
+        self
+    }
+
+    /// Set the path to the file that Pavex will use to write a summary of the
+    /// error handler that is invoked for each fallible component, for each route.
+    ///
+    /// It can be used to verify which type-wide error handlers apply to a nested blueprint.
+    ///
+    /// If this is not set, Pavex will not produce the report.
+    pub fn error_handler_report_path(mut self, path: PathBuf) -> Self {
+        self.error_handler_report_path = Some(path);
+        self
+    }
+
+    /// Write the code generated for each route to its own file (`src/route_<n>.rs`),
+    /// rather than inlining all of it in `src/lib.rs`.
+    ///
+    /// It's useful for applications with many routes, where a single `lib.rs` can
+    /// become unwieldy to navigate.
+    pub fn split_modules(mut self) -> Self {
+        self.split_modules = true;
+        self
+    }
+
+    /// Run the full analysis without writing the generated application to disk.
+    ///
+    /// Pavex will print a summary of the application (routes, constructors, diagnostics)
+    /// and the list of files it would have written.
+    /// It can't be combined with [`diagnostics_path`](Self::diagnostics_path),
+    /// [`extractor_report_path`](Self::extractor_report_path) or
+    /// [`error_handler_report_path`](Self::error_handler_report_path).
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
 }
@@ -113,6 +113,13 @@ struct TestConfig {
     /// Ignore the test if set to `true`.
     #[serde(default)]
     ignore: bool,
+    /// Generate the code for each route in its own file, rather than inlining it in `lib.rs`.
+    #[serde(default)]
+    split_modules: bool,
+    /// Run code generation in dry-run mode: nothing should be written to disk.
+    /// The summary printed by Pavex is compared against `expectations/dry_run.txt`.
+    #[serde(default)]
+    dry_run: bool,
 }
 
 #[derive(serde::Deserialize)]
@@ -406,6 +413,19 @@ impl TestData {
             toml::to_string(&cargo_config)?.as_bytes(),
         )?;
 
+        let mut generate_options = String::new();
+        if test_config.dry_run {
+            generate_options.push_str("\n        .dry_run()");
+        } else {
+            generate_options.push_str(
+                "\n        .diagnostics_path(\"diagnostics.dot\".into())\
+                \n        .extractor_report_path(\"extractor_report.txt\".into())\
+                \n        .error_handler_report_path(\"error_handler_report.txt\".into())",
+            );
+        }
+        if test_config.split_modules {
+            generate_options.push_str("\n        .split_modules()");
+        }
         let main_rs = format!(
             r#"use app::blueprint;
 use pavex_cli_client::{{Client, client::Color}};
@@ -414,8 +434,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {{
     if Client::new()
         .color(Color::Always)
         .pavex_cli_path("../../../../../libs/target/{cli_profile}/pavex".into())
-        .generate(blueprint(), "generated_app".into())
-        .diagnostics_path("diagnostics.dot".into())
+        .generate(blueprint(), "generated_app".into()){generate_options}
         .execute().is_err() {{
         std::process::exit(1);
     }}
@@ -571,6 +590,41 @@ fn _run_test(
         });
     };
 
+    if test_config.dry_run {
+        let summary_snapshot = SnapshotTest::new(expectations_directory.join("dry_run.txt"));
+        if summary_snapshot.verify(&codegen_output.stdout).is_err() {
+            return Ok(TestOutcome {
+                outcome: Err(
+                    "The summary printed by the dry run doesn't match what we expected".into(),
+                ),
+                codegen_output,
+                compilation_output: None,
+                test_output: None,
+            });
+        }
+        // The generated crate is seeded with an empty `lib.rs` and nothing else.
+        let source_directory = test
+            .test_runtime_directory()
+            .join("generated_app")
+            .join("src");
+        let source_files = fs_err::read_dir(&source_directory)?.count();
+        let lib_rs = fs_err::read_to_string(source_directory.join("lib.rs"))?;
+        if source_files != 1 || !lib_rs.is_empty() {
+            return Ok(TestOutcome {
+                outcome: Err("The dry run wrote the generated application code to disk".into()),
+                codegen_output,
+                compilation_output: None,
+                test_output: None,
+            });
+        }
+        return Ok(TestOutcome {
+            outcome: Ok(()),
+            codegen_output,
+            compilation_output: None,
+            test_output: None,
+        });
+    }
+
     let diagnostics_snapshot = SnapshotTest::new(expectations_directory.join("diagnostics.dot"));
     let actual_diagnostics =
         fs_err::read_to_string(test.test_runtime_directory().join("diagnostics.dot"))?;
@@ -580,6 +634,44 @@ fn _run_test(
     // with a test run in the middle.
     let diagnostics_outcome = diagnostics_snapshot.verify(&actual_diagnostics);
 
+    // The extractor report is only checked for the tests that opted into it, by
+    // providing an expectation file.
+    let extractor_report_outcome = {
+        let expectation_path = expectations_directory.join("extractor_report.txt");
+        if expectation_path.exists() {
+            let actual_report =
+                fs_err::read_to_string(test.test_runtime_directory().join("extractor_report.txt"))?;
+            SnapshotTest::new(expectation_path).verify(&actual_report)
+        } else {
+            Ok(())
+        }
+    };
+
+    // Same for the error handler report.
+    let error_handler_report_outcome = {
+        let expectation_path = expectations_directory.join("error_handler_report.txt");
+        if expectation_path.exists() {
+            let actual_report = fs_err::read_to_string(
+                test.test_runtime_directory()
+                    .join("error_handler_report.txt"),
+            )?;
+            SnapshotTest::new(expectation_path).verify(&actual_report)
+        } else {
+            Ok(())
+        }
+    };
+
+    // Warnings don't stop code generation: they are only checked for the tests that
+    // opted into it, by providing an expectation file.
+    let warnings_outcome = {
+        let expectation_path = expectations_directory.join("warnings.txt");
+        if expectation_path.exists() {
+            SnapshotTest::new(expectation_path).verify(&codegen_output.stderr)
+        } else {
+            Ok(())
+        }
+    };
+
     let app_code_snapshot = SnapshotTest::new(expectations_directory.join("app.rs"));
     let actual_app_code = fs_err::read_to_string(
         test.test_runtime_directory()
@@ -615,6 +707,41 @@ fn _run_test(
         });
     }
 
+    if extractor_report_outcome.is_err() {
+        return Ok(TestOutcome {
+            outcome: Err(
+                "The extractor report for the generated application doesn't match what we expected"
+                    .into(),
+            ),
+            codegen_output,
+            compilation_output: None,
+            test_output: None,
+        });
+    }
+
+    if error_handler_report_outcome.is_err() {
+        return Ok(TestOutcome {
+            outcome: Err(
+                "The error handler report for the generated application doesn't match what we expected"
+                    .into(),
+            ),
+            codegen_output,
+            compilation_output: None,
+            test_output: None,
+        });
+    }
+
+    if warnings_outcome.is_err() {
+        return Ok(TestOutcome {
+            outcome: Err(
+                "The warnings emitted by code generation don't match what we expected".into(),
+            ),
+            codegen_output,
+            compilation_output: None,
+            test_output: None,
+        });
+    }
+
     if codegen_outcome.is_err() {
         return Ok(TestOutcome {
             outcome: Err("The generated application code doesn't match what we expected".into()),
@@ -259,7 +259,13 @@ pub(crate) fn application_state_call_graph(
                 } else {
                     format!("{error_type_name}{n_duplicates}")
                 };
-                error_variants.insert(error_type_name.clone(), error_type.clone());
+                error_variants.insert(
+                    error_type_name.clone(),
+                    ApplicationStateErrorVariant {
+                        error_type: error_type.clone(),
+                        constructor_path: fallible_callable.path.to_string(),
+                    },
+                );
                 *n_duplicates += 1;
                 let error_variant_constructor = Callable {
                     is_async: false,
@@ -337,5 +343,14 @@ pub(crate) fn application_state_call_graph(
 
 pub(crate) struct ApplicationStateCallGraph {
     pub(crate) call_graph: OrderedCallGraph,
-    pub(crate) error_variants: IndexMap<String, ResolvedType>,
+    pub(crate) error_variants: IndexMap<String, ApplicationStateErrorVariant>,
+}
+
+/// A variant of the code-generated `ApplicationStateError` enum.
+pub(crate) struct ApplicationStateErrorVariant {
+    /// The error type returned by the fallible singleton constructor.
+    pub(crate) error_type: ResolvedType,
+    /// The path of the fallible singleton constructor, used to generate
+    /// a meaningful error message.
+    pub(crate) constructor_path: String,
 }
@@ -3,6 +3,7 @@ use bimap::BiHashMap;
 use guppy::PackageId;
 use indexmap::IndexSet;
 use petgraph::stable_graph::NodeIndex;
+use proc_macro2::Ident;
 use syn::ItemFn;
 
 use crate::compiler::analyses::call_graph::codegen::codegen_callable_closure;
@@ -50,8 +51,15 @@ impl OrderedCallGraph {
         package_id2name: &BiHashMap<PackageId, String>,
         component_db: &ComponentDb,
         computation_db: &ComputationDb,
+        failure_logger: Option<&Ident>,
     ) -> Result<ItemFn, anyhow::Error> {
-        codegen_callable_closure(self, package_id2name, component_db, computation_db)
+        codegen_callable_closure(
+            self,
+            package_id2name,
+            component_db,
+            computation_db,
+            failure_logger,
+        )
     }
 
     /// Return the set of types that must be provided as input to (recursively) build the handler's
@@ -24,17 +24,22 @@ use crate::compiler::codegen_utils;
 use crate::compiler::codegen_utils::{Fragment, VariableNameGenerator};
 use crate::compiler::component::Constructor;
 use crate::compiler::computation::{Computation, MatchResultVariant};
-use crate::language::ResolvedType;
+use crate::language::{InvocationStyle, ResolvedType};
 
 /// Generate the dependency closure of the [`OrderedCallGraph`]'s root callable.
 ///
 /// If the generation is successful, it returns a free function (an [`ItemFn`]) that wraps the
 /// underlying root callable.
+///
+/// If `failure_logger` is set, the generated code logs every constructor failure
+/// using `pavex::telemetry::log_constructor_failure`. `failure_logger` is the name
+/// `pavex` has been imported with in the generated code.
 pub(crate) fn codegen_callable_closure(
     call_graph: &OrderedCallGraph,
     package_id2name: &BiHashMap<PackageId, String>,
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
+    failure_logger: Option<&Ident>,
 ) -> Result<ItemFn, anyhow::Error> {
     let input_parameter_types = call_graph.required_input_types();
     let mut variable_generator = VariableNameGenerator::new();
@@ -52,6 +57,7 @@ pub(crate) fn codegen_callable_closure(
         package_id2name,
         component_db,
         computation_db,
+        failure_logger,
         &mut variable_generator,
     )?;
 
@@ -176,6 +182,7 @@ fn codegen_callable_closure_body(
     package_id2name: &BiHashMap<PackageId, String>,
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
+    failure_logger: Option<&Ident>,
     variable_name_generator: &mut VariableNameGenerator,
 ) -> Result<TokenStream, anyhow::Error> {
     let mut at_most_once_constructor_blocks = IndexMap::<NodeIndex, TokenStream>::new();
@@ -189,6 +196,7 @@ fn codegen_callable_closure_body(
         package_id2name,
         component_db,
         computation_db,
+        failure_logger,
         variable_name_generator,
         &mut at_most_once_constructor_blocks,
         &mut blocks,
@@ -204,6 +212,7 @@ fn _codegen_callable_closure_body(
     package_id2name: &BiHashMap<PackageId, String>,
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
+    failure_logger: Option<&Ident>,
     variable_name_generator: &mut VariableNameGenerator,
     at_most_once_constructor_blocks: &mut IndexMap<NodeIndex, TokenStream>,
     blocks: &mut HashMap<NodeIndex, Fragment>,
@@ -314,6 +323,7 @@ fn _codegen_callable_closure_body(
                         package_id2name,
                         component_db,
                         computation_db,
+                        failure_logger,
                         &mut variant_name_generator,
                         &mut at_most_once_constructor_blocks,
                         &mut variant_blocks,
@@ -337,8 +347,18 @@ fn _codegen_callable_closure_body(
                             ok_arm = Some(match_arm_body);
                         }
                         MatchResultVariant::Err => {
+                            let failure_log = failure_logger.and_then(|pavex| {
+                                codegen_failure_log(
+                                    pavex,
+                                    current_index,
+                                    call_graph,
+                                    component_db,
+                                    computation_db,
+                                )
+                            });
                             err_arm = Some(quote! {
                                 Err(#match_binding_parameter_name) => return {
+                                    #failure_log
                                     #match_arm_body
                                 }
                             });
@@ -387,6 +407,87 @@ fn _codegen_callable_closure_body(
     Ok(body)
 }
 
+/// Generate a call to `pavex::telemetry::log_constructor_failure` for the fallible
+/// constructor whose output is matched by `match_branching_index`.
+///
+/// The call path is determined by following the `Ok` branch from the failed constructor all the
+/// way down to a terminal node, collecting the constructors, middlewares and
+/// request handlers we encounter along the way.
+///
+/// It returns `None` if the fallible component is not a user-registered constructor—e.g.
+/// a fallible request handler or middleware—since there is no constructor failure to report.
+fn codegen_failure_log(
+    pavex: &Ident,
+    match_branching_index: NodeIndex,
+    call_graph: &RawCallGraph,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+) -> Option<TokenStream> {
+    let component_path = |node_index: NodeIndex| -> Option<String> {
+        let CallGraphNode::Compute { component_id, .. } = &call_graph[node_index] else {
+            return None;
+        };
+        let component = component_db.hydrated_component(*component_id, computation_db);
+        if !matches!(
+            component,
+            HydratedComponent::Constructor(_)
+                | HydratedComponent::RequestHandler(_)
+                | HydratedComponent::WrappingMiddleware(_)
+        ) {
+            return None;
+        }
+        match component.computation() {
+            // We skip the framework-generated `Next` state constructors.
+            Computation::Callable(c)
+                if !matches!(c.invocation_style, InvocationStyle::StructLiteral { .. }) =>
+            {
+                Some(c.path.to_string())
+            }
+            _ => None,
+        }
+    };
+    let is_err_variant = |node_index: NodeIndex| -> bool {
+        let CallGraphNode::Compute { component_id, .. } = &call_graph[node_index] else {
+            return false;
+        };
+        matches!(
+            component_db.hydrated_component(*component_id, computation_db),
+            HydratedComponent::Transformer(Computation::MatchResult(m))
+                | HydratedComponent::Constructor(Constructor(Computation::MatchResult(m)))
+                if m.variant == MatchResultVariant::Err
+        )
+    };
+
+    let constructor_index = call_graph
+        .neighbors_directed(match_branching_index, Direction::Incoming)
+        .next()
+        .unwrap();
+    let CallGraphNode::Compute { component_id, .. } = &call_graph[constructor_index] else {
+        return None;
+    };
+    if !matches!(
+        component_db.hydrated_component(*component_id, computation_db),
+        HydratedComponent::Constructor(_)
+    ) {
+        return None;
+    }
+    let constructor = component_path(constructor_index)?;
+    let mut call_path = vec![constructor.clone()];
+    let mut current_index = match_branching_index;
+    while let Some(next_index) = call_graph
+        .neighbors_directed(current_index, Direction::Outgoing)
+        .find(|&i| !is_err_variant(i))
+    {
+        if let Some(path) = component_path(next_index) {
+            call_path.push(path);
+        }
+        current_index = next_index;
+    }
+    Some(quote! {
+        #pavex::telemetry::log_constructor_failure(#constructor, &[#(#call_path),*]);
+    })
+}
+
 /// Returns a terminal descendant of the given node—i.e. a node that is reachable from
 /// `start_index` and has no outgoing edges.
 fn find_terminal_descendant(start_index: NodeIndex, call_graph: &RawCallGraph) -> NodeIndex {
@@ -1,4 +1,6 @@
-pub(crate) use application_state::{application_state_call_graph, ApplicationStateCallGraph};
+pub(crate) use application_state::{
+    application_state_call_graph, ApplicationStateCallGraph, ApplicationStateErrorVariant,
+};
 pub(crate) use borrow_checker::OrderedCallGraph;
 pub(crate) use core_graph::{
     CallGraph, CallGraphEdgeMetadata, CallGraphNode, InputParameterSource,
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet};
 use bimap::BiHashMap;
 use guppy::graph::PackageGraph;
 use indexmap::IndexSet;
@@ -16,8 +16,8 @@ use crate::compiler::analyses::user_components::{
 };
 use crate::compiler::component::{
     Constructor, ConstructorValidationError, ErrorHandler, ErrorHandlerValidationError,
-    RequestHandler, RequestHandlerValidationError, WrappingMiddleware,
-    WrappingMiddlewareValidationError,
+    RequestHandler, RequestHandlerValidationError, ResponseHook, ResponseHookValidationError,
+    WrappingMiddleware, WrappingMiddlewareValidationError,
 };
 use crate::compiler::computation::{Computation, MatchResult};
 use crate::compiler::interner::Interner;
@@ -174,6 +174,11 @@ pub(crate) struct ComponentDb {
     ///
     /// Invariants: there is an entry for every constructor.
     constructor_id2cloning_strategy: HashMap<ComponentId, CloningStrategy>,
+    /// The set of (fallible, singleton) constructors that have been marked as optional.
+    ///
+    /// Their errors are discarded: they are converted into an `Option<T>` rather than being
+    /// matched into `T` and `E`.
+    optional_constructor_ids: HashSet<ComponentId>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
@@ -184,6 +189,10 @@ pub(crate) struct ComponentDb {
     transformer_id2when_to_insert: HashMap<ComponentId, InsertTransformer>,
     error_handler_id2error_handler: HashMap<ComponentId, ErrorHandler>,
     into_response: PathType,
+    /// `pavex::telemetry::log_optional_singleton_failure`, with its generic parameters unbound.
+    ///
+    /// It converts the output of optional singleton constructors into an `Option<T>`.
+    optional_singleton_wrapper: Callable,
     /// A mapping from the low-level [`UserComponentId`]s to the high-level [`ComponentId`]s.
     ///
     /// This is used to "lift" mappings that use [`UserComponentId`] into mappings that
@@ -194,6 +203,9 @@ pub(crate) struct ComponentDb {
     /// - match request handlers with the sequence of middlewares that wrap around them.
     /// - convert the ids in the router.
     user_component_id2component_id: HashMap<UserComponentId, ComponentId>,
+    /// The response hook registered against the top-level blueprint, if any
+    /// and if it passed validation.
+    response_hook_id: Option<UserComponentId>,
 }
 
 /// The `build` method and its auxiliary routines.
@@ -218,6 +230,11 @@ impl ComponentDb {
             };
             into_response
         };
+        let optional_singleton_wrapper = process_framework_callable_path(
+            "pavex::telemetry::log_optional_singleton_failure",
+            package_graph,
+            krate_collection,
+        );
 
         let mut self_ = Self {
             user_component_db,
@@ -229,11 +246,14 @@ impl ComponentDb {
             id2transformer_ids: Default::default(),
             id2lifecycle: Default::default(),
             constructor_id2cloning_strategy: Default::default(),
+            optional_constructor_ids: Default::default(),
             handler_id2middleware_ids: Default::default(),
             transformer_id2when_to_insert: Default::default(),
             error_handler_id2error_handler: Default::default(),
             into_response,
+            optional_singleton_wrapper,
             user_component_id2component_id: Default::default(),
+            response_hook_id: None,
         };
 
         {
@@ -265,6 +285,8 @@ impl ComponentDb {
                 diagnostics,
             );
 
+            self_.process_response_hook(computation_db, package_graph, diagnostics);
+
             self_.compute_request2middleware_chain();
             self_.process_error_handlers(
                 &mut needs_error_handler,
@@ -442,6 +464,25 @@ impl ComponentDb {
                 }
                 Ok(c) => {
                     let lifecycle = *self.user_component_db.get_lifecycle(user_component_id);
+                    if lifecycle == Lifecycle::Singleton && c.output_borrows_from_inputs() {
+                        Self::singleton_cannot_borrow_from_its_inputs(
+                            user_component_id,
+                            c.output_type(),
+                            &self.user_component_db,
+                            package_graph,
+                            diagnostics,
+                        );
+                        continue;
+                    }
+                    if lifecycle == Lifecycle::Singleton && c.short_circuits_with_a_response() {
+                        Self::singleton_cannot_short_circuit_with_a_response(
+                            user_component_id,
+                            &self.user_component_db,
+                            package_graph,
+                            diagnostics,
+                        );
+                        continue;
+                    }
                     let constructor_id = self.interner.get_or_intern(Component::Constructor {
                         source_id: user_component_id.into(),
                     });
@@ -456,12 +497,37 @@ impl ComponentDb {
                     );
                     self.id2lifecycle.insert(constructor_id, lifecycle);
 
+                    if self.user_component_db.is_optional(user_component_id) {
+                        if lifecycle != Lifecycle::Singleton {
+                            Self::optional_constructor_must_be_a_singleton(
+                                user_component_id,
+                                &self.user_component_db,
+                                package_graph,
+                                diagnostics,
+                            );
+                        } else if !c.is_fallible() {
+                            Self::optional_constructor_must_be_fallible(
+                                user_component_id,
+                                &self.user_component_db,
+                                package_graph,
+                                diagnostics,
+                            );
+                        } else {
+                            self.optional_constructor_ids.insert(constructor_id);
+                        }
+                    }
+
                     self.register_derived_constructors(constructor_id, computation_db);
-                    if c.is_fallible() && lifecycle != Lifecycle::Singleton {
+                    if c.is_fallible()
+                        && lifecycle != Lifecycle::Singleton
+                        && !c.short_circuits_with_a_response()
+                    {
                         // We'll try to match all fallible constructors with an error handler later.
                         // We skip singletons since we don't "handle" errors when constructing them.
                         // They are just bubbled up to the caller by the function that builds
                         // the application state.
+                        // We also skip constructors that fail with a `Response`: if no error
+                        // handler was registered, the response is returned to the caller as it is.
                         needs_error_handler.insert(user_component_id);
                     }
                 }
@@ -558,10 +624,54 @@ impl ComponentDb {
             .collect::<Vec<_>>();
         for user_component_id in wrapping_middleware_ids {
             let user_component = &self.user_component_db[user_component_id];
-            let callable = &computation_db[user_component_id];
-            let UserComponent::WrappingMiddleware { .. } = user_component else {
+            let UserComponent::WrappingMiddleware { scope_id, .. } = user_component else {
                 unreachable!()
             };
+            let scope_id = *scope_id;
+            // Middlewares registered via `Blueprint::require_auth` are generic over
+            // the type returned by the authenticator: we bind it before validation.
+            let source_id = match self
+                .user_component_db
+                .get_authenticator_id(user_component_id)
+            {
+                None => SourceId::UserComponentId(user_component_id),
+                Some(authenticator_id) => {
+                    let Some(output) = &computation_db[authenticator_id].output else {
+                        // The authenticator is also registered as a constructor:
+                        // its validation will report the issue.
+                        continue;
+                    };
+                    let identity = if output.is_result() {
+                        get_ok_variant(output).to_owned()
+                    } else {
+                        output.to_owned()
+                    };
+                    self.detect_identity_constructor_conflicts(
+                        user_component_id,
+                        authenticator_id,
+                        &identity,
+                        computation_db,
+                        package_graph,
+                        diagnostics,
+                    );
+                    let mut bindings = HashMap::with_capacity(1);
+                    bindings.insert("T".to_string(), identity);
+                    let bound_callable =
+                        computation_db[user_component_id].bind_generic_type_parameters(&bindings);
+                    let computation_id = computation_db
+                        .get_or_intern(Computation::Callable(Cow::Owned(bound_callable)));
+                    SourceId::ComputationId(computation_id, scope_id)
+                }
+            };
+            let callable = match &source_id {
+                SourceId::UserComponentId(id) => &computation_db[*id],
+                SourceId::ComputationId(id, _) => {
+                    let Computation::Callable(callable) = &computation_db[*id] else {
+                        unreachable!()
+                    };
+                    callable.as_ref()
+                }
+            };
             match WrappingMiddleware::new(Cow::Borrowed(callable)) {
                 Err(e) => {
                     Self::invalid_wrapping_middleware(
@@ -575,12 +685,11 @@ impl ComponentDb {
                     );
                 }
                 Ok(mw) => {
-                    let mw_id = self.interner.get_or_intern(Component::WrappingMiddleware {
-                        source_id: user_component_id.into(),
-                    });
+                    let mw_id = self
+                        .interner
+                        .get_or_intern(Component::WrappingMiddleware { source_id });
                     self.user_component_id2component_id
                         .insert(user_component_id, mw_id);
-                    let scope_id = self.scope_id(mw_id);
                     let lifecycle = Lifecycle::RequestScoped;
                     self.id2lifecycle.insert(mw_id, lifecycle);
 
@@ -618,6 +727,108 @@ impl ComponentDb {
         }
     }
 
+    /// The authenticator passed to `Blueprint::require_auth` is the constructor of the identity
+    /// type for the blueprint it was registered against.
+    /// Another constructor for the same type, in the same blueprint or in one of the blueprints
+    /// nested under it, could shadow the authenticator: requests would reach the protected
+    /// routes without being authenticated.
+    ///
+    /// We emit a diagnostic for each of them.
+    fn detect_identity_constructor_conflicts(
+        &self,
+        middleware_id: UserComponentId,
+        authenticator_id: UserComponentId,
+        identity: &ResolvedType,
+        computation_db: &ComputationDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let scope_graph = self.user_component_db.scope_graph();
+        let protected_scope_id = self.user_component_db[middleware_id].scope_id();
+        for (constructor_id, constructor) in self.user_component_db.constructors() {
+            if constructor_id == authenticator_id
+                || !constructor
+                    .scope_id()
+                    .is_descendant_of(protected_scope_id, scope_graph)
+            {
+                continue;
+            }
+            let Some(output) = &computation_db[constructor_id].output else {
+                continue;
+            };
+            let output = if output.is_result() {
+                get_ok_variant(output)
+            } else {
+                output
+            };
+            if output != identity {
+                continue;
+            }
+
+            let location = self.user_component_db.get_location(constructor_id);
+            let source = match location.source_file(package_graph) {
+                Ok(s) => s,
+                Err(e) => {
+                    diagnostics.push(e.into());
+                    continue;
+                }
+            };
+            let label = diagnostic::get_f_macro_invocation_span(&source, location)
+                .map(|s| s.labeled("The conflicting constructor".into()));
+            let authenticator_snippet = {
+                let location = self.user_component_db.get_location(middleware_id);
+                location.source_file(package_graph).ok().map(|source| {
+                    let label = diagnostic::get_f_macro_invocation_span(&source, location)
+                        .map(|s| s.labeled("The authenticator was registered here".into()));
+                    AnnotatedSnippet::new_optional(source, label)
+                })
+            };
+            let error = anyhow::anyhow!(
+                "You can't register a constructor for `{identity:?}`: it's built by the authenticator \
+                you passed to `Blueprint::require_auth`.\n\
+                If I were to use your constructor, requests could reach the protected routes \
+                without being authenticated."
+            );
+            let diagnostic = CompilerDiagnostic::builder(source, error)
+                .code("pavex::require_auth::constructor_conflict")
+                .optional_label(label)
+                .optional_additional_annotated_snippet(authenticator_snippet)
+                .help(
+                    "Remove this constructor, or register it against a blueprint that isn't \
+                    protected by `require_auth`."
+                        .into(),
+                )
+                .build();
+            diagnostics.push(diagnostic.into());
+        }
+    }
+
+    fn process_response_hook(
+        &mut self,
+        computation_db: &ComputationDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let Some(user_component_id) = self.user_component_db.response_hook() else {
+            return;
+        };
+        let callable = &computation_db[user_component_id];
+        match ResponseHook::new(Cow::Borrowed(callable)) {
+            Ok(_) => {
+                self.response_hook_id = Some(user_component_id);
+            }
+            Err(e) => {
+                Self::invalid_response_hook(
+                    e,
+                    user_component_id,
+                    &self.user_component_db,
+                    package_graph,
+                    diagnostics,
+                );
+            }
+        }
+    }
+
     fn process_error_handlers(
         &mut self,
         missing_error_handlers: &mut IndexSet<UserComponentId>,
@@ -636,10 +847,14 @@ impl ComponentDb {
                         fallible_callable_identifiers_id,
                         ..
                     } => Some((id, *fallible_callable_identifiers_id)),
-                    Fallback { .. }
+                    // They have been paired with the matching fallible components
+                    // when building the `UserComponentDb`.
+                    TypeWideErrorHandler { .. }
+                    | Fallback { .. }
                     | RequestHandler { .. }
                     | Constructor { .. }
-                    | WrappingMiddleware { .. } => None,
+                    | WrappingMiddleware { .. }
+                    | ResponseHook { .. } => None,
                 }
             })
             .collect::<Vec<_>>();
@@ -753,7 +968,9 @@ impl ComponentDb {
         error_handler_id
     }
 
-    fn add_synthetic_constructor(
+    /// Register a constructor that was not registered by the user, but that Pavex
+    /// needs to build a type—e.g. framework-provided types.
+    pub(crate) fn add_synthetic_constructor(
         &mut self,
         c: Constructor<'static>,
         l: Lifecycle,
@@ -785,7 +1002,27 @@ impl ComponentDb {
             };
             constructor.into_owned()
         };
-        if let Ok(constructor) = constructor.as_fallible() {
+        if self.optional_constructor_ids.contains(&constructor_id) {
+            // Register a constructor that transforms `Result<T,E>` into `Option<T>`.
+            // There is no `Err` branch: the error is logged and discarded.
+            let output_type = constructor.output_type();
+            let mut bindings = HashMap::with_capacity(2);
+            bindings.insert("T".to_string(), get_ok_variant(output_type).to_owned());
+            bindings.insert("E".to_string(), get_err_variant(output_type).to_owned());
+            let callable = self
+                .optional_singleton_wrapper
+                .bind_generic_type_parameters(&bindings);
+            let cloning_strategy = self.constructor_id2cloning_strategy[&constructor_id];
+            let lifecycle = self.lifecycle(constructor_id).unwrap().to_owned();
+            let scope_id = self.scope_id(constructor_id);
+            self.add_synthetic_constructor(
+                Constructor(Computation::Callable(Cow::Owned(callable))),
+                lifecycle,
+                scope_id,
+                cloning_strategy,
+                computation_db,
+            );
+        } else if let Ok(constructor) = constructor.as_fallible() {
             let cloning_strategy = self.constructor_id2cloning_strategy[&constructor_id];
             let lifecycle = self.lifecycle(constructor_id).unwrap().to_owned();
             let scope_id = self.scope_id(constructor_id);
@@ -914,6 +1151,13 @@ impl ComponentDb {
         &self.user_component_id2component_id
     }
 
+    /// The id of the response hook registered against the top-level blueprint, if any.
+    ///
+    /// Use it to index into the [`ComputationDb`] to retrieve the hook's callable.
+    pub fn response_hook_id(&self) -> Option<UserComponentId> {
+        self.response_hook_id
+    }
+
     /// Iterate over all the components in the database alongside their ids.
     pub fn iter(
         &self,
@@ -1264,8 +1508,13 @@ impl ComponentDb {
             }
         };
 
-        if let Some((_, err_match_id)) = self.fallible_id2match_ids.get(&id) {
-            let err_handler_id = self.match_err_id2error_handler_id[err_match_id];
+        // Constructors that fail with a `Response` may not have an error handler.
+        if let Some(err_handler_id) = self
+            .fallible_id2match_ids
+            .get(&id)
+            .and_then(|(_, err_match_id)| self.match_err_id2error_handler_id.get(err_match_id))
+            .copied()
+        {
             let HydratedComponent::ErrorHandler(error_handler) =
                 self.hydrated_component(err_handler_id, computation_db)
             else {
@@ -1693,6 +1942,35 @@ impl ComponentDb {
         diagnostics.push(diagnostic.into());
     }
 
+    fn invalid_response_hook(
+        e: ResponseHookValidationError,
+        user_component_id: UserComponentId,
+        user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let location = user_component_db.get_location(user_component_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The response hook was registered here".into()));
+        let diagnostic = CompilerDiagnostic::builder(source, e)
+            .optional_label(label)
+            .help(
+                "Response hooks must have one of the following signatures:\n\
+                - `fn(Response) -> Response`\n\
+                - `fn(Response, &RequestHead) -> Response`"
+                    .into(),
+            )
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
     fn invalid_wrapping_middleware(
         e: WrappingMiddlewareValidationError,
         user_component_id: UserComponentId,
@@ -2086,6 +2364,132 @@ impl ComponentDb {
         diagnostics.push(diagnostic.into());
     }
 
+    fn optional_constructor_must_be_a_singleton(
+        constructor_id: UserComponentId,
+        raw_user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let lifecycle = raw_user_component_db.get_lifecycle(constructor_id);
+        let location = raw_user_component_db.get_location(constructor_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The optional constructor was registered here".into()));
+        let error = anyhow::anyhow!(
+            "You marked a constructor with a {lifecycle} lifecycle as optional. \
+            Only singleton constructors can be optional.",
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, error)
+            .optional_label(label)
+            .help(
+                "If the constructor can fail at runtime, register an error handler \
+                via `.error_handler` instead of marking it as optional."
+                    .to_string(),
+            )
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
+    fn singleton_cannot_borrow_from_its_inputs(
+        constructor_id: UserComponentId,
+        output_type: &ResolvedType,
+        raw_user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let location = raw_user_component_db.get_location(constructor_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The singleton constructor was registered here".into()));
+        let error = anyhow::anyhow!(
+            "Singletons can't borrow from their inputs.\n\
+            The output type of this singleton constructor, `{output_type:?}`, has a non-'static \
+            lifetime parameter: it can't be stored in the application state.",
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, error)
+            .optional_label(label)
+            .help(
+                "Register it as a request-scoped constructor instead. \
+                If its inputs are singletons, the borrows are valid for the whole request: \
+                the output is computed once per request, at no cost beyond the function call."
+                    .to_string(),
+            )
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
+    fn singleton_cannot_short_circuit_with_a_response(
+        constructor_id: UserComponentId,
+        raw_user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let location = raw_user_component_db.get_location(constructor_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The singleton constructor was registered here".into()));
+        let error = anyhow::anyhow!(
+            "Singletons can't short-circuit with a response.\n\
+            This singleton constructor fails with a `pavex::response::Response`, but singletons \
+            are built when the application starts: there is no request to respond to.",
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, error)
+            .optional_label(label)
+            .help(
+                "Return an error type that implements `std::error::Error` if the application \
+                shouldn't start when the constructor fails, or register it as a request-scoped \
+                constructor if the response is meant for the caller."
+                    .to_string(),
+            )
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
+    fn optional_constructor_must_be_fallible(
+        constructor_id: UserComponentId,
+        raw_user_component_db: &UserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let location = raw_user_component_db.get_location(constructor_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The optional constructor was registered here".into()));
+        let error = anyhow::anyhow!(
+            "You marked a singleton constructor that doesn't return a `Result` as optional. \
+            An infallible constructor always succeeds: there is nothing to fall back from.",
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, error)
+            .optional_label(label)
+            .help("Remove `.optional()`, it is not needed.".to_string())
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
     fn missing_error_handler(
         fallible_id: UserComponentId,
         raw_user_component_db: &UserComponentDb,
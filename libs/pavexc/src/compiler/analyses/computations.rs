@@ -51,6 +51,15 @@ impl ComputationDb {
         Ok(callable_id)
     }
 
+    /// Associate `user_component_id` with the computation that `source_id` was resolved to.
+    ///
+    /// It panics if `source_id` hasn't been resolved yet.
+    pub(crate) fn alias(&mut self, user_component_id: UserComponentId, source_id: UserComponentId) {
+        let callable_id = self.component_id2callable_id[&source_id];
+        self.component_id2callable_id
+            .insert(user_component_id, callable_id);
+    }
+
     /// Retrieve the id for a computation from the interner, or insert it if it doesn't exist.
     pub(crate) fn get_or_intern(
         &mut self,
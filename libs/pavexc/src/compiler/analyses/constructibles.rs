@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::VecDeque;
 
 use ahash::{HashMap, HashMapExt, HashSet};
@@ -6,7 +7,7 @@ use indexmap::{IndexMap, IndexSet};
 use miette::{NamedSource, SourceSpan};
 use syn::spanned::Spanned;
 
-use pavex::blueprint::constructor::Lifecycle;
+use pavex::blueprint::constructor::{CloningStrategy, Lifecycle};
 
 use crate::compiler::analyses::components::{
     ComponentDb, ComponentId, ConsumptionMode, HydratedComponent,
@@ -15,12 +16,16 @@ use crate::compiler::analyses::computations::ComputationDb;
 use crate::compiler::analyses::user_components::{
     ScopeGraph, ScopeId, UserComponentDb, UserComponentId,
 };
+use crate::compiler::component::Constructor;
+use crate::compiler::computation::Computation;
 use crate::diagnostic::{self, ParsedSourceFile};
 use crate::diagnostic::{
     convert_proc_macro_span, convert_rustdoc_span, read_source_file, AnnotatedSnippet,
     CompilerDiagnostic, HelpWithSnippet, LocationExt, SourceSpanExt,
 };
-use crate::language::{Callable, ResolvedType};
+use crate::language::{
+    Callable, GenericArgument, InvocationStyle, ResolvedPath, ResolvedPathSegment, ResolvedType,
+};
 use crate::rustdoc::CrateCollection;
 
 use super::framework_items::FrameworkItemDb;
@@ -464,7 +469,83 @@ impl ConstructibleDb {
             }
             fifo.extend(scope_id.direct_parent_ids(component_db.scope_graph()));
         }
-        None
+        self.try_wrap_singleton_in_arc(scope_id, type_, component_db, computation_db)
+    }
+
+    /// If `type_` is `Arc<T>` (or `&Arc<T>`) and `T` is a singleton, we add a synthetic
+    /// singleton constructor that wraps `T` in an `Arc`—i.e. it invokes `Arc::new`.
+    ///
+    /// The rules:
+    ///
+    /// - We only bridge singletons. Request-scoped and transient types are built fresh
+    ///   for each request, there is no instance to share.
+    /// - We only wrap, never unwrap: a component asking for `T` will never be handed the
+    ///   inner value of an `Arc<T>` singleton.
+    /// - An explicitly registered constructor for `Arc<T>` always takes precedence.
+    ///
+    /// `Arc::new` consumes `T`: if `T` is needed elsewhere, the borrow checker will
+    /// either clone it or report an error, according to its cloning strategy.
+    fn try_wrap_singleton_in_arc(
+        &mut self,
+        scope_id: ScopeId,
+        type_: &ResolvedType,
+        component_db: &mut ComponentDb,
+        computation_db: &mut ComputationDb,
+    ) -> Option<(ComponentId, ConsumptionMode)> {
+        let arc_type = match type_ {
+            ResolvedType::Reference(ref_) if !ref_.lifetime.is_static() && !ref_.is_mutable => {
+                ref_.inner.as_ref()
+            }
+            _ => type_,
+        };
+        let ResolvedType::ResolvedPath(arc_path) = arc_type else {
+            return None;
+        };
+        if arc_path.base_type != ["alloc", "sync", "Arc"] {
+            return None;
+        }
+        let [GenericArgument::TypeParameter(inner_type)] = arc_path.generic_arguments.as_slice()
+        else {
+            return None;
+        };
+        let (inner_constructor_id, ConsumptionMode::Move) =
+            self.get_or_try_bind(scope_id, inner_type, component_db, computation_db)?
+        else {
+            return None;
+        };
+        if component_db.lifecycle(inner_constructor_id) != Some(&Lifecycle::Singleton) {
+            return None;
+        }
+
+        let callable = Callable {
+            is_async: false,
+            takes_self_as_ref: false,
+            output: Some(arc_type.to_owned()),
+            path: ResolvedPath {
+                segments: ["alloc", "sync", "Arc", "new"]
+                    .into_iter()
+                    .map(|ident| ResolvedPathSegment {
+                        ident: ident.into(),
+                        generic_arguments: vec![],
+                    })
+                    .collect(),
+                qualified_self: None,
+                package_id: arc_path.package_id.clone(),
+            },
+            inputs: vec![inner_type.to_owned()],
+            invocation_style: InvocationStyle::FunctionCall,
+            source_coordinates: None,
+        };
+        // The `Arc` lives in the same scope as the singleton it wraps.
+        let component_id = component_db.add_synthetic_constructor(
+            Constructor(Computation::Callable(Cow::Owned(callable))),
+            Lifecycle::Singleton,
+            component_db.scope_id(inner_constructor_id),
+            CloningStrategy::CloneIfNecessary,
+            computation_db,
+        );
+        self.insert(component_id, component_db, computation_db);
+        self.get(scope_id, type_, component_db.scope_graph())
     }
 
     fn missing_constructor(
@@ -39,9 +39,9 @@ impl FrameworkItemDb {
             package_graph,
             krate_collection,
         );
-        items.insert(request_head, 0);
+        items.insert(request_head, Self::request_head_id());
         id2metadata.insert(
-            0,
+            Self::request_head_id(),
             FrameworkItemMetadata {
                 lifecycle: Lifecycle::RequestScoped,
                 cloning_strategy: CloningStrategy::NeverClone,
@@ -105,9 +105,41 @@ impl FrameworkItemDb {
                 binding: format_ident!("allowed_methods"),
             },
         );
+
+        let dispatcher =
+            process_framework_path("pavex::router::Dispatcher", package_graph, krate_collection);
+        items.insert(dispatcher, Self::dispatcher_id());
+        id2metadata.insert(
+            Self::dispatcher_id(),
+            FrameworkItemMetadata {
+                lifecycle: Lifecycle::RequestScoped,
+                cloning_strategy: CloningStrategy::CloneIfNecessary,
+                binding: format_ident!("dispatcher"),
+            },
+        );
+
+        let local_addr = process_framework_path(
+            "pavex::connection::LocalAddr",
+            package_graph,
+            krate_collection,
+        );
+        items.insert(local_addr, Self::local_addr_id());
+        id2metadata.insert(
+            Self::local_addr_id(),
+            FrameworkItemMetadata {
+                lifecycle: Lifecycle::RequestScoped,
+                cloning_strategy: CloningStrategy::CloneIfNecessary,
+                binding: format_ident!("local_addr"),
+            },
+        );
         Self { items, id2metadata }
     }
 
+    /// Return the id for the `RequestHead` type.
+    pub(crate) fn request_head_id() -> FrameworkItemId {
+        0
+    }
+
     /// Return the id for the `MatchedPathPattern` type.
     pub(crate) fn matched_route_template_id() -> FrameworkItemId {
         3
@@ -118,6 +150,16 @@ impl FrameworkItemDb {
         4
     }
 
+    /// Return the id for the `Dispatcher` type.
+    pub(crate) fn dispatcher_id() -> FrameworkItemId {
+        5
+    }
+
+    /// Return the id for the `LocalAddr` type.
+    pub(crate) fn local_addr_id() -> FrameworkItemId {
+        6
+    }
+
     /// Return the [`Lifecycle`] associated with a framework item.
     pub(crate) fn lifecycle(&self, item_id: FrameworkItemId) -> Lifecycle {
         self.id2metadata[&item_id].lifecycle
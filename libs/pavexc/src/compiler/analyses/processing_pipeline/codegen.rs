@@ -19,19 +19,28 @@ impl RequestHandlerPipeline {
     /// - The closure of each middleware functions
     /// - The `Next` state for each middleware invocation
     ///
-    /// You can wrap the generated code in an inline module by calling the
-    /// [`as_inline_module`](CodegenedRequestHandlerPipeline::as_inline_module) method on
+    /// You can get the items that make up the body of the pipeline module by calling the
+    /// [`as_module_body`](CodegenedRequestHandlerPipeline::as_module_body) method on
     /// the output.
+    ///
+    /// If `failure_logger` is set, the generated code logs constructor failures via
+    /// `pavex::telemetry::log_constructor_failure`.
     pub(crate) fn codegen(
         &self,
         package_id2name: &BiHashMap<PackageId, String>,
         component_db: &ComponentDb,
         computation_db: &ComputationDb,
+        failure_logger: Option<&Ident>,
     ) -> Result<CodegenedRequestHandlerPipeline, anyhow::Error> {
         let n_middlewares = self.middleware_id2stage_data.len();
         let mut stages = Vec::with_capacity(n_middlewares + 1);
         for (i, call_graph) in self.graph_iter().enumerate() {
-            let mut fn_ = call_graph.codegen(package_id2name, component_db, computation_db)?;
+            let mut fn_ = call_graph.codegen(
+                package_id2name,
+                component_db,
+                computation_db,
+                failure_logger,
+            )?;
             fn_.sig.ident = if i < n_middlewares {
                 format_ident!("middleware_{}", i)
             } else {
@@ -210,20 +219,19 @@ pub(crate) struct CodegenedRequestHandlerPipeline {
 }
 
 impl CodegenedRequestHandlerPipeline {
-    /// Generates an inline module containing the code generated for the pipeline
-    /// of this request handler.
-    pub(crate) fn as_inline_module(&self) -> TokenStream {
+    /// Generates the items that make up the module containing the code generated for
+    /// the pipeline of this request handler, without the `mod` declaration itself.
+    ///
+    /// The module is named after [`module_name`](Self::module_name).
+    pub(crate) fn as_module_body(&self) -> TokenStream {
         let Self {
             stages,
             next_states,
-            module_name,
+            module_name: _,
         } = self;
-        let module_name = format_ident!("{}", module_name);
         quote! {
-            pub mod #module_name {
-                #(#stages)*
-                #(#next_states)*
-            }
+            #(#stages)*
+            #(#next_states)*
         }
     }
 
@@ -327,6 +335,20 @@ impl CodegenedRequestHandlerPipeline {
             .unwrap();
         self.needs_input_type(matched_route_type)
     }
+
+    pub(crate) fn needs_local_addr(&self, framework_item_db: &FrameworkItemDb) -> bool {
+        let local_addr_type = framework_item_db
+            .get_type(FrameworkItemDb::local_addr_id())
+            .unwrap();
+        self.needs_input_type(local_addr_type)
+    }
+
+    pub(crate) fn needs_dispatcher(&self, framework_item_db: &FrameworkItemDb) -> bool {
+        let dispatcher_type = framework_item_db
+            .get_type(FrameworkItemDb::dispatcher_id())
+            .unwrap();
+        self.needs_input_type(dispatcher_type)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +19,11 @@ pub(crate) struct LeafRouter {
     //     the set of methods that a handler can handle.
     pub(crate) handler_id2methods: BTreeMap<ComponentId, BTreeSet<String>>,
     pub(crate) fallback_id: ComponentId,
+    /// The matching priority of the route.
+    /// Routes with a higher priority are matched first.
+    pub(crate) priority: i32,
+    /// `true` if `fallback_id` points at a handler registered via `Blueprint::method_not_allowed`.
+    pub(crate) method_not_allowed: bool,
 }
 
 impl LeafRouter {
@@ -56,6 +61,8 @@ impl Router {
                     LeafRouter {
                         handler_id2methods,
                         fallback_id,
+                        priority: leaf_router.priority,
+                        method_not_allowed: leaf_router.method_not_allowed,
                     },
                 )
             })
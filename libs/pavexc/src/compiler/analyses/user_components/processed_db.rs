@@ -1,4 +1,4 @@
-use ahash::HashMap;
+use ahash::{HashMap, HashSet};
 use guppy::graph::PackageGraph;
 use indexmap::IndexSet;
 use miette::{miette, NamedSource};
@@ -13,7 +13,10 @@ use crate::compiler::analyses::computations::ComputationDb;
 use crate::compiler::analyses::user_components::raw_db::RawUserComponentDb;
 use crate::compiler::analyses::user_components::resolved_paths::ResolvedPathDb;
 use crate::compiler::analyses::user_components::router::Router;
-use crate::compiler::analyses::user_components::{ScopeGraph, UserComponent, UserComponentId};
+use crate::compiler::analyses::user_components::{
+    ScopeGraph, ScopeId, UserComponent, UserComponentId,
+};
+use crate::compiler::component::ErrorHandler;
 use crate::compiler::interner::Interner;
 use crate::compiler::resolvers::CallableResolutionError;
 use crate::diagnostic;
@@ -52,10 +55,18 @@ pub struct UserComponentDb {
     ///
     /// Invariants: there is an entry for every constructor.
     constructor_id2cloning_strategy: HashMap<UserComponentId, CloningStrategy>,
+    /// The set of constructors that have been marked as optional.
+    optional_constructor_ids: HashSet<UserComponentId>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
     handler_id2middleware_ids: HashMap<UserComponentId, Vec<UserComponentId>>,
+    /// Associate a request handler with the value of the `Cache-Control` header that should be
+    /// set on its responses.
+    ///
+    /// Invariants: there is an entry only for the request handlers whose route has
+    /// been configured via `Route::cache_control`.
+    handler_id2cache_control: HashMap<UserComponentId, String>,
     scope_graph: ScopeGraph,
 }
 
@@ -76,13 +87,13 @@ impl UserComponentDb {
         /// Exit early if there is at least one error.
         macro_rules! exit_on_errors {
             ($var:ident) => {
-                if !$var.is_empty() {
+                if crate::diagnostic::has_errors($var) {
                     return Err(());
                 }
             };
         }
 
-        let (raw_db, scope_graph) = RawUserComponentDb::build(bp, package_graph, diagnostics);
+        let (mut raw_db, scope_graph) = RawUserComponentDb::build(bp, package_graph, diagnostics);
         let resolved_path_db = ResolvedPathDb::build(&raw_db, package_graph, diagnostics);
         let router = Router::new(&raw_db, &scope_graph, package_graph, diagnostics)?;
         exit_on_errors!(diagnostics);
@@ -100,14 +111,27 @@ impl UserComponentDb {
         );
         exit_on_errors!(diagnostics);
 
+        Self::apply_type_wide_error_handlers(
+            &mut raw_db,
+            &scope_graph,
+            computation_db,
+            package_graph,
+            diagnostics,
+        );
+        exit_on_errors!(diagnostics);
+
         let RawUserComponentDb {
             component_interner,
             id2locations,
             constructor_id2cloning_strategy,
+            optional_constructor_ids,
             id2lifecycle,
             identifiers_interner,
             handler_id2middleware_ids,
             fallback_id2path_prefix: _,
+            method_not_allowed_ids: _,
+            handler_id2priority: _,
+            handler_id2cache_control,
         } = raw_db;
 
         Ok((
@@ -117,8 +141,10 @@ impl UserComponentDb {
                 identifiers_interner,
                 id2locations,
                 constructor_id2cloning_strategy,
+                optional_constructor_ids,
                 id2lifecycle,
                 handler_id2middleware_ids,
+                handler_id2cache_control,
                 scope_graph,
             },
         ))
@@ -169,6 +195,22 @@ impl UserComponentDb {
             .filter(|(_, c)| matches!(c, UserComponent::WrappingMiddleware { .. }))
     }
 
+    /// Return the id of the response hook registered against the top-level blueprint, if any.
+    pub fn response_hook(&self) -> Option<UserComponentId> {
+        self.component_interner
+            .iter()
+            .find(|(_, c)| matches!(c, UserComponent::ResponseHook { .. }))
+            .map(|(id, _)| id)
+    }
+
+    /// Return the value of the `Cache-Control` header that should be set on the responses
+    /// returned by the request handler with the given id, if any.
+    pub fn cache_control(&self, handler_id: UserComponentId) -> Option<&str> {
+        self.handler_id2cache_control
+            .get(&handler_id)
+            .map(|s| s.as_str())
+    }
+
     /// Return the lifecycle of the component with the given id.
     pub fn get_lifecycle(&self, id: UserComponentId) -> &Lifecycle {
         &self.id2lifecycle[&id]
@@ -186,6 +228,11 @@ impl UserComponentDb {
         self.constructor_id2cloning_strategy.get(&id)
     }
 
+    /// Return `true` if the component is a constructor that has been marked as optional.
+    pub fn is_optional(&self, id: UserComponentId) -> bool {
+        self.optional_constructor_ids.contains(&id)
+    }
+
     /// Return the scope tree that was built from the application blueprint.
     pub fn scope_graph(&self) -> &ScopeGraph {
         &self.scope_graph
@@ -206,6 +253,25 @@ impl UserComponentDb {
     pub fn get_middleware_ids(&self, id: UserComponentId) -> &[UserComponentId] {
         &self.handler_id2middleware_ids[&id]
     }
+
+    /// Return the id of the constructor that the wrapping middleware with the given id
+    /// enforces, if the middleware was registered via `Blueprint::require_auth`.
+    ///
+    /// The authenticator is registered as a constructor in the same scope as the middleware.
+    pub fn get_authenticator_id(&self, middleware_id: UserComponentId) -> Option<UserComponentId> {
+        let UserComponent::WrappingMiddleware {
+            authenticator_id: Some(raw_callable_identifiers_id),
+            scope_id,
+            ..
+        } = &self.component_interner[middleware_id]
+        else {
+            return None;
+        };
+        self.component_interner.get(&UserComponent::Constructor {
+            raw_callable_identifiers_id: *raw_callable_identifiers_id,
+            scope_id: *scope_id,
+        })
+    }
 }
 
 /// We try to batch together the computation of the JSON documentation for all the crates that,
@@ -253,6 +319,171 @@ impl UserComponentDb {
         }
     }
 
+    /// Pair each fallible component that doesn't have an error handler of its own with
+    /// the closest type-wide error handler that takes a reference to its error type as input,
+    /// if there is one.
+    ///
+    /// Type-wide error handlers registered against the scope of the component are considered
+    /// first, followed by those registered against its parent scope, all the way up to the
+    /// root scope.
+    ///
+    /// Each pairing is registered as a [`UserComponent::ErrorHandler`]: from this point
+    /// onwards, it can't be told apart from an error handler registered via `.error_handler`.
+    fn apply_type_wide_error_handlers(
+        raw_db: &mut RawUserComponentDb,
+        scope_graph: &ScopeGraph,
+        computation_db: &mut ComputationDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let mut scope_id2error_handler_ids: HashMap<ScopeId, Vec<UserComponentId>> =
+            HashMap::default();
+        let mut has_error_handler = HashSet::default();
+        for (id, component) in raw_db.iter() {
+            match component {
+                UserComponent::TypeWideErrorHandler { scope_id, .. } => {
+                    scope_id2error_handler_ids
+                        .entry(*scope_id)
+                        .or_default()
+                        .push(id);
+                }
+                UserComponent::ErrorHandler {
+                    fallible_callable_identifiers_id,
+                    ..
+                } => {
+                    has_error_handler.insert(*fallible_callable_identifiers_id);
+                }
+                UserComponent::RequestHandler { .. }
+                | UserComponent::Fallback { .. }
+                | UserComponent::Constructor { .. }
+                | UserComponent::WrappingMiddleware { .. }
+                | UserComponent::ResponseHook { .. } => {}
+            }
+        }
+        if scope_id2error_handler_ids.is_empty() {
+            return;
+        }
+
+        let fallible_ids: Vec<_> = raw_db
+            .iter()
+            .filter(|(id, component)| {
+                matches!(
+                    component,
+                    UserComponent::RequestHandler { .. }
+                        | UserComponent::Fallback { .. }
+                        | UserComponent::Constructor { .. }
+                        | UserComponent::WrappingMiddleware { .. }
+                )
+                && !has_error_handler.contains(id)
+                // Singletons are built when the application state is assembled,
+                // there is no request to respond to if they fail.
+                && raw_db.id2lifecycle[id] != Lifecycle::Singleton
+                && computation_db[*id].is_fallible()
+            })
+            .map(|(id, _)| id)
+            .collect();
+        for fallible_id in fallible_ids {
+            let scope_id = raw_db[fallible_id].scope_id();
+            for candidate_scope_id in scope_id.self_and_ancestor_ids(scope_graph) {
+                let Some(error_handler_ids) = scope_id2error_handler_ids.get(&candidate_scope_id)
+                else {
+                    continue;
+                };
+                let matching_ids: Vec<_> = error_handler_ids
+                    .iter()
+                    .copied()
+                    .filter(|id| {
+                        ErrorHandler::handles_error_of(
+                            &computation_db[*id],
+                            &computation_db[fallible_id],
+                        )
+                    })
+                    .collect();
+                match matching_ids.as_slice() {
+                    [] => continue,
+                    [error_handler_id] => {
+                        let component = UserComponent::ErrorHandler {
+                            raw_callable_identifiers_id: raw_db[*error_handler_id]
+                                .raw_callable_identifiers_id(),
+                            fallible_callable_identifiers_id: fallible_id,
+                            scope_id,
+                        };
+                        let lifecycle = raw_db.id2lifecycle[&fallible_id];
+                        let location = raw_db.get_location(*error_handler_id).to_owned();
+                        let id = raw_db.intern_component(component, lifecycle, location);
+                        computation_db.alias(id, *error_handler_id);
+                    }
+                    _ => {
+                        Self::ambiguous_type_wide_error_handlers(
+                            fallible_id,
+                            &matching_ids,
+                            raw_db,
+                            package_graph,
+                            diagnostics,
+                        );
+                    }
+                }
+                // The closest scope with at least one match wins.
+                break;
+            }
+        }
+    }
+
+    fn ambiguous_type_wide_error_handlers(
+        fallible_id: UserComponentId,
+        error_handler_ids: &[UserComponentId],
+        raw_db: &RawUserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let fallible_kind = raw_db[fallible_id].callable_type();
+        let location = raw_db.get_location(fallible_id);
+        let source = match location.source_file(package_graph) {
+            Ok(source) => source,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled(format!("The fallible {fallible_kind} was registered here")));
+        let mut snippets = Vec::new();
+        for error_handler_id in error_handler_ids {
+            let location = raw_db.get_location(*error_handler_id);
+            let source = match location.source_file(package_graph) {
+                Ok(source) => source,
+                Err(e) => {
+                    diagnostics.push(e.into());
+                    continue;
+                }
+            };
+            if let Some(label) = diagnostic::get_f_macro_invocation_span(&source, location) {
+                snippets.push(AnnotatedSnippet::new(
+                    source,
+                    label.labeled("A matching type-wide error handler".into()),
+                ));
+            }
+        }
+        let callable_path = raw_db[fallible_id]
+            .raw_callable_identifiers(raw_db)
+            .raw_path();
+        let error = anyhow::anyhow!(
+            "There are {} type-wide error handlers that can handle the errors returned by \
+            `{callable_path}`, and they were all registered against the same blueprint.\n\
+            I don't know which one to pick!",
+            error_handler_ids.len()
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, error)
+            .optional_label(label)
+            .additional_annotated_snippets(snippets.into_iter())
+            .help(format!(
+                "Register an error handler for this {fallible_kind} via `.error_handler`, \
+                or remove all but one of the type-wide error handlers."
+            ))
+            .build();
+        diagnostics.push(diagnostic.into());
+    }
+
     fn cannot_resolve_path(
         e: CallableResolutionError,
         component_id: UserComponentId,
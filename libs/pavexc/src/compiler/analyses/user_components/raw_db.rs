@@ -1,4 +1,4 @@
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use anyhow::anyhow;
 use guppy::graph::PackageGraph;
 
@@ -42,11 +42,26 @@ pub enum UserComponent {
         fallible_callable_identifiers_id: UserComponentId,
         scope_id: ScopeId,
     },
+    /// An error handler registered via `Blueprint::error_handler`.
+    ///
+    /// It isn't tied to a specific fallible component: it is paired with all the
+    /// matching fallible components in its scope once their callables have been resolved.
+    TypeWideErrorHandler {
+        raw_callable_identifiers_id: RawCallableIdentifierId,
+        scope_id: ScopeId,
+    },
     Constructor {
         raw_callable_identifiers_id: RawCallableIdentifierId,
         scope_id: ScopeId,
     },
     WrappingMiddleware {
+        raw_callable_identifiers_id: RawCallableIdentifierId,
+        /// The authenticator enforced by this middleware, if it was registered
+        /// via `Blueprint::require_auth`.
+        authenticator_id: Option<RawCallableIdentifierId>,
+        scope_id: ScopeId,
+    },
+    ResponseHook {
         raw_callable_identifiers_id: RawCallableIdentifierId,
         scope_id: ScopeId,
     },
@@ -59,10 +74,13 @@ impl UserComponent {
     pub fn callable_type(&self) -> CallableType {
         match self {
             UserComponent::RequestHandler { .. } => CallableType::RequestHandler,
-            UserComponent::ErrorHandler { .. } => CallableType::ErrorHandler,
+            UserComponent::ErrorHandler { .. } | UserComponent::TypeWideErrorHandler { .. } => {
+                CallableType::ErrorHandler
+            }
             UserComponent::Constructor { .. } => CallableType::Constructor,
             UserComponent::WrappingMiddleware { .. } => CallableType::WrappingMiddleware,
             UserComponent::Fallback { .. } => CallableType::RequestHandler,
+            UserComponent::ResponseHook { .. } => CallableType::ResponseHook,
         }
     }
 
@@ -86,9 +104,17 @@ impl UserComponent {
                 raw_callable_identifiers_id,
                 ..
             }
+            | UserComponent::TypeWideErrorHandler {
+                raw_callable_identifiers_id,
+                ..
+            }
             | UserComponent::Constructor {
                 raw_callable_identifiers_id,
                 ..
+            }
+            | UserComponent::ResponseHook {
+                raw_callable_identifiers_id,
+                ..
             } => *raw_callable_identifiers_id,
         }
     }
@@ -99,8 +125,10 @@ impl UserComponent {
             UserComponent::RequestHandler { scope_id, .. }
             | UserComponent::Fallback { scope_id, .. }
             | UserComponent::ErrorHandler { scope_id, .. }
+            | UserComponent::TypeWideErrorHandler { scope_id, .. }
             | UserComponent::WrappingMiddleware { scope_id, .. }
-            | UserComponent::Constructor { scope_id, .. } => *scope_id,
+            | UserComponent::Constructor { scope_id, .. }
+            | UserComponent::ResponseHook { scope_id, .. } => *scope_id,
         }
     }
 
@@ -148,6 +176,8 @@ pub(super) struct RawUserComponentDb {
     ///
     /// Invariants: there is an entry for every constructor.
     pub(super) constructor_id2cloning_strategy: HashMap<UserComponentId, CloningStrategy>,
+    /// The set of constructors that have been marked as optional.
+    pub(super) optional_constructor_ids: HashSet<UserComponentId>,
     /// Associate each request handler with the ordered list of middlewares that wrap around it.
     ///
     /// Invariants: there is an entry for every single request handler.
@@ -159,6 +189,21 @@ pub(super) struct RawUserComponentDb {
     ///
     /// Invariants: there is an entry for every single fallback.
     pub(super) fallback_id2path_prefix: HashMap<UserComponentId, Option<String>>,
+    /// The set of fallbacks that have been registered via `Blueprint::method_not_allowed`.
+    ///
+    /// They are only invoked when the path of the incoming request matches a route,
+    /// but its method doesn't.
+    pub(super) method_not_allowed_ids: HashSet<UserComponentId>,
+    /// Associate each user-registered request handler with the matching priority of its route.
+    ///
+    /// Invariants: there is an entry for every single request handler.
+    pub(super) handler_id2priority: HashMap<UserComponentId, i32>,
+    /// Associate each user-registered request handler with the value of the `Cache-Control`
+    /// header that should be set on its responses.
+    ///
+    /// Invariants: there is an entry only for the request handlers whose route has
+    /// been configured via `Route::cache_control`.
+    pub(super) handler_id2cache_control: HashMap<UserComponentId, String>,
 }
 
 // The public `build` method alongside its private supporting routines.
@@ -176,8 +221,12 @@ impl RawUserComponentDb {
             id2locations: HashMap::new(),
             id2lifecycle: HashMap::new(),
             constructor_id2cloning_strategy: HashMap::new(),
+            optional_constructor_ids: HashSet::new(),
             handler_id2middleware_ids: HashMap::new(),
             fallback_id2path_prefix: HashMap::new(),
+            method_not_allowed_ids: HashSet::new(),
+            handler_id2priority: HashMap::new(),
+            handler_id2cache_control: HashMap::new(),
         };
         let mut scope_graph_builder = ScopeGraph::builder(bp.creation_location.clone());
         let root_scope_id = scope_graph_builder.root_scope_id();
@@ -186,12 +235,16 @@ impl RawUserComponentDb {
         // its nested blueprints.
         // By default, the middleware chain is empty.
         let mut current_middleware_chain = Vec::new();
+        // The base path is prepended to the path of every route in the application,
+        // including those registered against nested blueprints.
+        let base_path = self_.validate_base_path(bp, package_graph, diagnostics);
 
         Self::process_blueprint(
             &mut self_,
             bp,
             root_scope_id,
             None,
+            base_path,
             &mut scope_graph_builder,
             &mut current_middleware_chain,
             true,
@@ -241,6 +294,7 @@ impl RawUserComponentDb {
                 &nested_bp.blueprint,
                 nested_scope_id,
                 path_prefix.as_deref(),
+                base_path,
                 &mut scope_graph_builder,
                 &mut current_middleware_chain,
                 false,
@@ -270,11 +324,13 @@ impl RawUserComponentDb {
     ///
     /// If `path_prefix` is `Some`, then it is prepended to the path of each route
     /// in `Blueprint`.
+    /// `base_path` is prepended to the path of each route, before `path_prefix`.
     fn process_blueprint(
         &mut self,
         bp: &Blueprint,
         current_scope_id: ScopeId,
         path_prefix: Option<&str>,
+        base_path: &str,
         scope_graph_builder: &mut ScopeGraphBuilder,
         current_middleware_chain: &mut Vec<UserComponentId>,
         is_root: bool,
@@ -287,6 +343,7 @@ impl RawUserComponentDb {
             current_middleware_chain,
             current_scope_id,
             path_prefix,
+            base_path,
             scope_graph_builder,
             package_graph,
             diagnostics,
@@ -295,6 +352,7 @@ impl RawUserComponentDb {
             self.process_fallback(
                 fallback,
                 path_prefix,
+                base_path,
                 current_middleware_chain,
                 current_scope_id,
                 scope_graph_builder,
@@ -320,12 +378,29 @@ impl RawUserComponentDb {
             self.process_fallback(
                 &registered_fallback,
                 path_prefix,
+                base_path,
                 current_middleware_chain,
                 current_scope_id,
                 scope_graph_builder,
             )
         }
+        if let Some(method_not_allowed) = &bp.method_not_allowed_handler {
+            self.process_method_not_allowed_handler(
+                method_not_allowed,
+                current_middleware_chain,
+                current_scope_id,
+                scope_graph_builder,
+            );
+        }
+        if let Some(response_hook) = &bp.response_hook {
+            if is_root {
+                self.process_response_hook(response_hook, current_scope_id);
+            } else {
+                self.response_hook_in_nested_blueprint(response_hook, package_graph, diagnostics);
+            }
+        }
         self.process_constructors(&bp.constructors, current_scope_id);
+        self.process_type_wide_error_handlers(&bp.error_handlers, current_scope_id);
     }
 
     /// Register with [`RawUserComponentDb`] all the routes that have been
@@ -337,6 +412,7 @@ impl RawUserComponentDb {
         current_middleware_chain: &[UserComponentId],
         current_scope_id: ScopeId,
         path_prefix: Option<&str>,
+        base_path: &str,
         scope_graph_builder: &mut ScopeGraphBuilder,
         package_graph: &PackageGraph,
         diagnostics: &mut Vec<miette::Error>,
@@ -355,10 +431,12 @@ impl RawUserComponentDb {
                         MethodGuard::Some(methods.iter().map(|m| m.to_string()).collect())
                     }
                 };
-                let path = match path_prefix {
-                    Some(prefix) => format!("{}{}", prefix, registered_route.path),
-                    None => registered_route.path.to_owned(),
-                };
+                let path = format!(
+                    "{}{}{}",
+                    base_path,
+                    path_prefix.unwrap_or_default(),
+                    registered_route.path
+                );
                 RouterKey { path, method_guard }
             };
             let component = UserComponent::RequestHandler {
@@ -374,6 +452,12 @@ impl RawUserComponentDb {
 
             self.handler_id2middleware_ids
                 .insert(request_handler_id, current_middleware_chain.to_owned());
+            self.handler_id2priority
+                .insert(request_handler_id, registered_route.priority);
+            if let Some(cache_control) = &registered_route.cache_control {
+                self.handler_id2cache_control
+                    .insert(request_handler_id, cache_control.to_owned());
+            }
 
             self.validate_route(
                 request_handler_id,
@@ -398,6 +482,7 @@ impl RawUserComponentDb {
         &mut self,
         fallback: &RegisteredFallback,
         path_prefix: Option<&str>,
+        base_path: &str,
         current_middleware_chain: &[UserComponentId],
         current_scope_id: ScopeId,
         scope_graph_builder: &mut ScopeGraphBuilder,
@@ -420,8 +505,12 @@ impl RawUserComponentDb {
 
         self.handler_id2middleware_ids
             .insert(fallback_id, current_middleware_chain.to_owned());
-        self.fallback_id2path_prefix
-            .insert(fallback_id, path_prefix.map(|s| s.to_owned()));
+        // The root fallback must keep handling all unmatched requests, including those outside
+        // of the base path: we only prepend the base path to the prefixes of nested blueprints.
+        self.fallback_id2path_prefix.insert(
+            fallback_id,
+            path_prefix.map(|prefix| format!("{base_path}{prefix}")),
+        );
 
         self.process_error_handler(
             &fallback.error_handler,
@@ -431,6 +520,69 @@ impl RawUserComponentDb {
         );
     }
 
+    /// Register with [`RawUserComponentDb`] the `405 Method Not Allowed` handler that has been
+    /// registered against the provided `Blueprint`, including its error handler (if present).
+    ///
+    /// It is processed as a fallback, with a sub-scope of its own, but it is never
+    /// used to handle requests that don't match any route path.
+    fn process_method_not_allowed_handler(
+        &mut self,
+        method_not_allowed: &RegisteredFallback,
+        current_middleware_chain: &[UserComponentId],
+        current_scope_id: ScopeId,
+        scope_graph_builder: &mut ScopeGraphBuilder,
+    ) {
+        const ROUTE_LIFECYCLE: Lifecycle = Lifecycle::RequestScoped;
+
+        let raw_callable_identifiers_id = self
+            .identifiers_interner
+            .get_or_intern(method_not_allowed.request_handler.callable.clone());
+        let route_scope_id = scope_graph_builder.add_scope(current_scope_id, None);
+        let component = UserComponent::Fallback {
+            raw_callable_identifiers_id,
+            scope_id: route_scope_id,
+        };
+        let handler_id = self.intern_component(
+            component,
+            ROUTE_LIFECYCLE,
+            method_not_allowed.request_handler.location.to_owned(),
+        );
+
+        self.handler_id2middleware_ids
+            .insert(handler_id, current_middleware_chain.to_owned());
+        // It never acts as a path-based fallback.
+        self.fallback_id2path_prefix.insert(handler_id, None);
+        self.method_not_allowed_ids.insert(handler_id);
+
+        self.process_error_handler(
+            &method_not_allowed.error_handler,
+            ROUTE_LIFECYCLE,
+            current_scope_id,
+            handler_id,
+        );
+    }
+
+    /// Register with [`RawUserComponentDb`] the response hook that has been registered
+    /// against the top-level `Blueprint`.
+    fn process_response_hook(
+        &mut self,
+        response_hook: &RegisteredCallable,
+        current_scope_id: ScopeId,
+    ) {
+        let raw_callable_identifiers_id = self
+            .identifiers_interner
+            .get_or_intern(response_hook.callable.clone());
+        let component = UserComponent::ResponseHook {
+            raw_callable_identifiers_id,
+            scope_id: current_scope_id,
+        };
+        self.intern_component(
+            component,
+            Lifecycle::RequestScoped,
+            response_hook.location.to_owned(),
+        );
+    }
+
     /// Register with [`RawUserComponentDb`] all the routes that have been
     /// registered against the provided `Blueprint`, including their error handlers
     /// (if present).  
@@ -446,8 +598,13 @@ impl RawUserComponentDb {
             let raw_callable_identifiers_id = self
                 .identifiers_interner
                 .get_or_intern(middleware.middleware.callable.clone());
+            let authenticator_id = middleware
+                .authenticator
+                .as_ref()
+                .map(|a| self.identifiers_interner.get_or_intern(a.clone()));
             let component = UserComponent::WrappingMiddleware {
                 raw_callable_identifiers_id,
+                authenticator_id,
                 scope_id: current_scope_id,
             };
             let component_id = self.intern_component(
@@ -495,6 +652,9 @@ impl RawUserComponentDb {
                     .cloning_strategy
                     .unwrap_or(CloningStrategy::NeverClone),
             );
+            if constructor.optional {
+                self.optional_constructor_ids.insert(constructor_id);
+            }
 
             self.process_error_handler(
                 &constructor.error_handler,
@@ -505,10 +665,38 @@ impl RawUserComponentDb {
         }
     }
 
+    /// Register with [`RawUserComponentDb`] all the type-wide error handlers that have been
+    /// registered against the provided `Blueprint`.
+    ///
+    /// They are paired with the fallible components they apply to in [`UserComponentDb`],
+    /// after all paths have been resolved.
+    ///
+    /// [`UserComponentDb`]: crate::compiler::analyses::user_components::UserComponentDb
+    fn process_type_wide_error_handlers(
+        &mut self,
+        error_handlers: &[RegisteredCallable],
+        current_scope_id: ScopeId,
+    ) {
+        for error_handler in error_handlers {
+            let raw_callable_identifiers_id = self
+                .identifiers_interner
+                .get_or_intern(error_handler.callable.clone());
+            let component = UserComponent::TypeWideErrorHandler {
+                raw_callable_identifiers_id,
+                scope_id: current_scope_id,
+            };
+            self.intern_component(
+                component,
+                Lifecycle::RequestScoped,
+                error_handler.location.to_owned(),
+            );
+        }
+    }
+
     /// A helper function to intern a component without forgetting to do the necessary
     /// bookeeping for the metadata (location and lifecycle) that are common to all
     /// components.
-    fn intern_component(
+    pub(super) fn intern_component(
         &mut self,
         component: UserComponent,
         lifecycle: Lifecycle,
@@ -561,6 +749,74 @@ impl RawUserComponentDb {
         }
     }
 
+    /// Check the base path of the root blueprint, if one was set.
+    /// Emit diagnostics if it is invalid—i.e. empty, missing a leading slash or ending with
+    /// a trailing slash.
+    ///
+    /// It returns the base path to be prepended to all routes—an empty string if there is
+    /// no base path or if it is invalid.
+    fn validate_base_path<'a>(
+        &self,
+        bp: &'a Blueprint,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) -> &'a str {
+        let Some(base_path) = &bp.base_path else {
+            return "";
+        };
+        let path = base_path.path.as_str();
+        let (error, help, label) = if path.is_empty() {
+            (
+                anyhow!("The base path passed to `base_path` cannot be empty."),
+                "If you don't want to add a common prefix to all routes, \
+                remove the `base_path` invocation."
+                    .to_string(),
+                "The empty base path",
+            )
+        } else if !path.starts_with('/') {
+            (
+                anyhow!(
+                    "The base path passed to `base_path` must begin with a forward slash, `/`.\n\
+                    `{path}` doesn't."
+                ),
+                format!(
+                    "Add a '/' at the beginning of the base path to fix this error: \
+                    use `/{path}` instead of `{path}`."
+                ),
+                "The base path missing a leading '/'",
+            )
+        } else if path.ends_with('/') {
+            let correct_path = path.trim_end_matches('/');
+            (
+                anyhow!(
+                    "The base path passed to `base_path` can't end with a trailing slash, `/`. \
+                    `{path}` does."
+                ),
+                format!(
+                    "Remove the '/' at the end of the base path to fix this error: \
+                    use `{correct_path}` instead of `{path}`."
+                ),
+                "The base path ending with a trailing '/'",
+            )
+        } else {
+            return path;
+        };
+
+        let location = &base_path.location;
+        match location.source_file(package_graph) {
+            Ok(source) => {
+                let label = diagnostic::get_base_path_span(&source, location)
+                    .map(|s| s.labeled(label.to_string()));
+                let diagnostic = CompilerDiagnostic::builder(source, error)
+                    .optional_label(label)
+                    .help(help);
+                diagnostics.push(diagnostic.build().into());
+            }
+            Err(e) => diagnostics.push(e.into()),
+        }
+        ""
+    }
+
     /// Check the path prefix of the nested blueprint.
     /// Emit diagnostics if the path prefix is invalid—i.e. empty or missing a leading slash.
     fn validate_nested_bp(
@@ -614,7 +870,10 @@ impl RawUserComponentDb {
                         id
                     );
                 }
-                UserComponent::ErrorHandler { .. } | UserComponent::WrappingMiddleware { .. } => {}
+                UserComponent::ErrorHandler { .. }
+                | UserComponent::TypeWideErrorHandler { .. }
+                | UserComponent::WrappingMiddleware { .. }
+                | UserComponent::ResponseHook { .. } => {}
             }
         }
     }
@@ -766,4 +1025,36 @@ impl RawUserComponentDb {
             .help(format!("Remove the '/' at the end of the path prefix to fix this error: use `{correct_prefix}` instead of `{prefix}`."));
         diagnostics.push(diagnostic.build().into());
     }
+
+    fn response_hook_in_nested_blueprint(
+        &self,
+        response_hook: &RegisteredCallable,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let location = &response_hook.location;
+        let source = match location.source_file(package_graph) {
+            Ok(source) => source,
+            Err(e) => {
+                diagnostics.push(e.into());
+                return;
+            }
+        };
+        let label = diagnostic::get_f_macro_invocation_span(&source, location)
+            .map(|s| s.labeled("The response hook was registered here".to_string()));
+        let err = anyhow!(
+            "Response hooks can only be registered against the top-level blueprint.\n\
+            They are applied to every outgoing response, therefore they can't be scoped \
+            to the routes of a nested blueprint."
+        );
+        let diagnostic = CompilerDiagnostic::builder(source, err)
+            .optional_label(label)
+            .help(
+                "Register the response hook against the blueprint you pass to Pavex, \
+                or use a wrapping middleware if you only want to process the responses \
+                returned by the routes of this nested blueprint."
+                    .into(),
+            );
+        diagnostics.push(diagnostic.build().into());
+    }
 }
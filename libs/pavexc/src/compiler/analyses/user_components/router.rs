@@ -36,13 +36,20 @@ pub(crate) struct LeafRouter {
     /// methods registered for the route.
     /// We always need a fallback, since you might receive requests with "non-standard" methods.
     pub(crate) fallback_id: UserComponentId,
+    /// The matching priority of the route.
+    /// Routes with a higher priority are matched first.
+    pub(crate) priority: i32,
+    /// `true` if `fallback_id` points at a handler registered via `Blueprint::method_not_allowed`.
+    pub(crate) method_not_allowed: bool,
 }
 
 impl LeafRouter {
-    pub fn new(fallback_id: UserComponentId) -> Self {
+    pub fn new(fallback_id: UserComponentId, priority: i32) -> Self {
         Self {
             handler_id2methods: Default::default(),
             fallback_id,
+            priority,
+            method_not_allowed: false,
         }
     }
 }
@@ -58,6 +65,9 @@ impl Router {
             .iter()
             .find_map(|(id, component)| {
                 if let UserComponent::Fallback { scope_id, .. } = component {
+                    if raw_user_component_db.method_not_allowed_ids.contains(&id) {
+                        return None;
+                    }
                     if scope_id
                         .direct_parent_ids(scope_graph)
                         .contains(&scope_graph.root_scope_id())
@@ -69,37 +79,53 @@ impl Router {
             })
             .expect("No fallback registered for the root scope.");
         Self::detect_method_conflicts(raw_user_component_db, package_graph, diagnostics)?;
+        Self::detect_priority_mismatches(raw_user_component_db, package_graph, diagnostics)?;
         let runtime_router =
             Self::detect_path_conflicts(raw_user_component_db, package_graph, diagnostics)?;
-        let (route_id2fallback_id, path_catchall2fallback_id) = Self::assign_fallbacks(
+        let (mut route_id2fallback_id, path_catchall2fallback_id) = Self::assign_fallbacks(
             runtime_router.clone(),
             raw_user_component_db,
             scope_graph,
             package_graph,
             diagnostics,
         )?;
+        Self::assign_method_not_allowed_handlers(
+            &mut route_id2fallback_id,
+            raw_user_component_db,
+            scope_graph,
+        );
         Self::check_method_not_allowed_fallbacks(
             &route_id2fallback_id,
             raw_user_component_db,
             package_graph,
             diagnostics,
         )?;
+        Self::detect_trailing_slash_duplicates(raw_user_component_db, package_graph, diagnostics);
 
         let mut route_path2sub_router = BTreeMap::new();
         for (id, component) in raw_user_component_db.iter() {
             let UserComponent::RequestHandler { router_key, .. } = component else {
                 continue;
             };
+            let priority = raw_user_component_db.handler_id2priority[&id];
             match &router_key.method_guard {
                 MethodGuard::Any => {
                     // We don't need to register a fallback for this route, since it matches
                     // all methods.
-                    route_path2sub_router.insert(router_key.path.clone(), LeafRouter::new(id));
+                    route_path2sub_router
+                        .insert(router_key.path.clone(), LeafRouter::new(id, priority));
                 }
                 MethodGuard::Some(methods) => {
                     let sub_router: &mut LeafRouter = route_path2sub_router
                         .entry(router_key.path.clone())
-                        .or_insert_with(|| LeafRouter::new(route_id2fallback_id[&id]));
+                        .or_insert_with(|| {
+                            let fallback_id = route_id2fallback_id[&id];
+                            let mut sub_router = LeafRouter::new(fallback_id, priority);
+                            sub_router.method_not_allowed = raw_user_component_db
+                                .method_not_allowed_ids
+                                .contains(&fallback_id);
+                            sub_router
+                        });
                     sub_router.handler_id2methods.insert(id, methods.clone());
                 }
             }
@@ -107,7 +133,7 @@ impl Router {
         for (path, fallback_id) in path_catchall2fallback_id {
             route_path2sub_router
                 .entry(path)
-                .or_insert_with(|| LeafRouter::new(fallback_id));
+                .or_insert_with(|| LeafRouter::new(fallback_id, DEFAULT_PRIORITY));
         }
 
         Ok(Self {
@@ -177,6 +203,46 @@ impl Router {
         }
     }
 
+    /// Make sure that all the routes registered for the same path share the same priority.
+    ///
+    /// Priorities are used to determine which path matches an incoming request—there
+    /// would be no way to honor different priorities for the same path.
+    fn detect_priority_mismatches(
+        raw_user_component_db: &RawUserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) -> Result<(), ()> {
+        let n_diagnostics = diagnostics.len();
+
+        let mut path2priority2handler_id = IndexMap::<_, BTreeMap<i32, UserComponentId>>::new();
+        for (id, component) in raw_user_component_db.iter() {
+            if let UserComponent::RequestHandler { router_key, .. } = component {
+                path2priority2handler_id
+                    .entry(&router_key.path)
+                    .or_default()
+                    .entry(raw_user_component_db.handler_id2priority[&id])
+                    .or_insert(id);
+            }
+        }
+        for (path, priority2handler_id) in path2priority2handler_id {
+            if priority2handler_id.len() > 1 {
+                push_priority_mismatch_diagnostic(
+                    path,
+                    &priority2handler_id,
+                    raw_user_component_db,
+                    package_graph,
+                    diagnostics,
+                );
+            }
+        }
+
+        if n_diagnostics == diagnostics.len() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
     /// Make sure that the user-registered paths don't conflict with each other.
     /// In other words: we won't encounter any issue when creating this router.
     ///
@@ -184,17 +250,24 @@ impl Router {
     ///
     /// By trying to create the router in the compiler itself!
     /// If it works now, it'll work at runtime too.
+    ///
+    /// Routes with different priorities are matched using different routers at runtime,
+    /// therefore we only look for conflicts between routes that share the same priority.
+    /// The router for the default priority is returned.
     fn detect_path_conflicts(
         raw_user_component_db: &RawUserComponentDb,
         package_graph: &PackageGraph,
         diagnostics: &mut Vec<miette::Error>,
     ) -> Result<matchit::Router<()>, ()> {
-        let mut path_router = matchit::Router::new();
+        let mut priority2path_router = BTreeMap::new();
         let mut errored = false;
         for (id, component) in raw_user_component_db.iter() {
             let UserComponent::RequestHandler { router_key, .. } = component else {
                 continue;
             };
+            let path_router = priority2path_router
+                .entry(raw_user_component_db.handler_id2priority[&id])
+                .or_insert_with(matchit::Router::new);
             let Err(e) = path_router.insert(router_key.path.clone(), ()) else {
                 continue;
             };
@@ -220,7 +293,48 @@ impl Router {
         if errored {
             Err(())
         } else {
-            Ok(path_router)
+            Ok(priority2path_router
+                .remove(&DEFAULT_PRIORITY)
+                .unwrap_or_else(matchit::Router::new))
+        }
+    }
+
+    /// Warn the user if they registered two routes whose paths only differ by a trailing slash—e.g.
+    /// `/users` and `/users/`.
+    ///
+    /// Paths are matched strictly, therefore they are two distinct routes: it's legitimate,
+    /// but it's usually a mistake.
+    /// This check never fails, it only emits warnings.
+    fn detect_trailing_slash_duplicates(
+        raw_user_component_db: &RawUserComponentDb,
+        package_graph: &PackageGraph,
+        diagnostics: &mut Vec<miette::Error>,
+    ) {
+        let mut path2handler_id = IndexMap::new();
+        for (id, component) in raw_user_component_db.iter() {
+            if let UserComponent::RequestHandler { router_key, .. } = component {
+                path2handler_id
+                    .entry(router_key.path.as_str())
+                    .or_insert(id);
+            }
+        }
+        for (path, handler_id) in &path2handler_id {
+            if path.len() <= 1 {
+                continue;
+            }
+            let Some(without_slash) = path.strip_suffix('/') else {
+                continue;
+            };
+            if let Some(other_handler_id) = path2handler_id.get(without_slash) {
+                push_trailing_slash_duplicate_diagnostic(
+                    without_slash,
+                    *other_handler_id,
+                    *handler_id,
+                    raw_user_component_db,
+                    package_graph,
+                    diagnostics,
+                );
+            }
         }
     }
 
@@ -255,6 +369,9 @@ impl Router {
                 let UserComponent::Fallback { scope_id, .. } = component else {
                     continue;
                 };
+                if raw_user_component_db.method_not_allowed_ids.contains(&id) {
+                    continue;
+                }
                 let parents = scope_id.direct_parent_ids(scope_graph);
                 assert_eq!(parents.len(), 1, "Fallbacks are always encapsulated in their own sub-scope and should only have one parent scope.");
                 let parent_scope_id = parents.into_iter().next().unwrap();
@@ -397,6 +514,57 @@ impl Router {
         }
     }
 
+    /// If a `405 Method Not Allowed` handler is in scope for a request handler, it takes
+    /// precedence over the fallback we assigned to it in [`Self::assign_fallbacks`]
+    /// when the method of the incoming request doesn't match.
+    ///
+    /// If there are multiple `405 Method Not Allowed` handlers in scope, we pick the one
+    /// that was registered closest to the request handler.
+    fn assign_method_not_allowed_handlers(
+        route_id2fallback_id: &mut BTreeMap<UserComponentId, UserComponentId>,
+        raw_user_component_db: &RawUserComponentDb,
+        scope_graph: &ScopeGraph,
+    ) {
+        if raw_user_component_db.method_not_allowed_ids.is_empty() {
+            return;
+        }
+        // Method not allowed handler id <> the scope of the blueprint it was registered against.
+        let handler_id2parent_scope_id: Vec<_> = raw_user_component_db
+            .method_not_allowed_ids
+            .iter()
+            .map(|id| {
+                let parent_scope_id = *raw_user_component_db[*id]
+                    .scope_id()
+                    .direct_parent_ids(scope_graph)
+                    .iter()
+                    .next()
+                    .unwrap();
+                (*id, parent_scope_id)
+            })
+            .collect();
+        for (handler_id, fallback_id) in route_id2fallback_id.iter_mut() {
+            let route_scope_id = raw_user_component_db[*handler_id].scope_id();
+            let mut closest: Option<(UserComponentId, ScopeId)> = None;
+            for (candidate_id, candidate_scope_id) in &handler_id2parent_scope_id {
+                if !route_scope_id.is_descendant_of(*candidate_scope_id, scope_graph) {
+                    continue;
+                }
+                let is_closer = match closest {
+                    None => true,
+                    Some((_, closest_scope_id)) => {
+                        candidate_scope_id.is_descendant_of(closest_scope_id, scope_graph)
+                    }
+                };
+                if is_closer {
+                    closest = Some((*candidate_id, *candidate_scope_id));
+                }
+            }
+            if let Some((method_not_allowed_id, _)) = closest {
+                *fallback_id = method_not_allowed_id;
+            }
+        }
+    }
+
     /// There are two kinds of routing "misses":
     /// 1. there is a registered route that matches the incoming request path, but the method doesn't match
     ///    any of the methods registered for that route.
@@ -412,7 +580,9 @@ impl Router {
     ) -> Result<(), ()> {
         let n_diagnostics = diagnostics.len();
 
-        let mut method_aware_router = matchit::Router::<u32>::new();
+        // Routes with different priorities may have conflicting paths, so we need
+        // a separate router for each priority level.
+        let mut priority2method_aware_router = BTreeMap::<i32, matchit::Router<u32>>::new();
         // Route id <> (fallback_id <> (handler_id <> method guards))
         let mut map: BTreeMap<
             u32,
@@ -433,6 +603,9 @@ impl Router {
                 }
                 MethodGuard::Some(g) => g,
             };
+            let method_aware_router = priority2method_aware_router
+                .entry(raw_user_component_db.handler_id2priority[&handler_id])
+                .or_insert_with(matchit::Router::new);
             let route_id = match method_aware_router.at_mut(router_key.path.as_str()) {
                 Ok(match_) => *match_.value,
                 Err(_) => {
@@ -600,6 +773,9 @@ enum Capture {
     Parameter(String),
 }
 
+/// The priority assigned to routes that don't specify one explicitly.
+const DEFAULT_PRIORITY: i32 = 0;
+
 static METHODS: [&str; 9] = [
     "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "CONNECT", "TRACE",
 ];
@@ -667,6 +843,7 @@ fn push_fallback_ambiguity_diagnostic(
         router_key.path
     );
     let diagnostic = CompilerDiagnostic::builder(route_source, error)
+        .code("pavex::router::fallback_ambiguity")
         .optional_label(label)
         .help(format!(
             "You can fix this by registering `{route_repr}` against the nested blueprint \
@@ -783,6 +960,7 @@ fn push_fallback_method_ambiguity_diagnostic(
     let error = anyhow::anyhow!(err_msg);
     let first_snippet = first_snippet.unwrap();
     let diagnostic = CompilerDiagnostic::builder(first_snippet.source_code, error)
+        .code("pavex::router::fallback_method_ambiguity")
         .labels(first_snippet.labels.into_iter())
         .additional_annotated_snippets(annotated_snippets.into_iter())
         .help(format!(
@@ -801,20 +979,20 @@ fn push_matchit_diagnostic(
 ) {
     // We want to control the error message for style consistency with the rest of the
     // diagnostics we emit.
-    let error = match error {
+    let (code, error) = match error {
         InsertError::Conflict { with } => {
-            anyhow!("This route path, `{}`, conflicts with the path of another route you already registered, `{}`.", path, with)
+            ("pavex::router::path_conflict", anyhow!("This route path, `{}`, conflicts with the path of another route you already registered, `{}`.", path, with))
         }
         InsertError::TooManyParams => {
-            anyhow!("You can only register one path parameter per each path segment.")
+            ("pavex::router::invalid_path", anyhow!("You can only register one path parameter per each path segment."))
         }
         InsertError::UnnamedParam => {
-            anyhow!("All path parameters must be named. You can't use anonymous parameters like `:` or `*`.")
+            ("pavex::router::invalid_path", anyhow!("All path parameters must be named. You can't use anonymous parameters like `:` or `*`."))
         }
         InsertError::InvalidCatchAll => {
-            anyhow!("You can only use catch-all parameters at the end of a route path.")
+            ("pavex::router::invalid_path", anyhow!("You can only use catch-all parameters at the end of a route path."))
         }
-        _ => error.into(),
+        _ => ("pavex::router::invalid_path", error.into()),
     };
 
     let location = raw_user_component_db.get_location(raw_user_component_id);
@@ -827,10 +1005,69 @@ fn push_matchit_diagnostic(
     };
     let label = diagnostic::get_route_path_span(&source, location)
         .labeled("The problematic path".to_string());
-    let diagnostic = CompilerDiagnostic::builder(source, error).optional_label(label);
+    let diagnostic = CompilerDiagnostic::builder(source, error)
+        .code(code)
+        .optional_label(label);
     diagnostics.push(diagnostic.build().into());
 }
 
+fn push_priority_mismatch_diagnostic(
+    path: &str,
+    priority2handler_id: &BTreeMap<i32, UserComponentId>,
+    raw_user_component_db: &RawUserComponentDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let mut annotated_snippets: Vec<AnnotatedSnippet> =
+        Vec::with_capacity(priority2handler_id.len());
+    for (priority, raw_user_component_id) in priority2handler_id {
+        let location = raw_user_component_db.get_location(*raw_user_component_id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                continue;
+            }
+        };
+        if let Some(s) = diagnostic::get_route_path_span(&source, location) {
+            let label = s.labeled(format!("A route with priority {priority}"));
+            annotated_snippets.push(AnnotatedSnippet::new(source, label));
+        }
+    }
+    let mut annotated_snippets = annotated_snippets.into_iter();
+    let Some(first) = annotated_snippets.next() else {
+        return;
+    };
+    let priorities = {
+        let mut buffer = String::new();
+        comma_separated_list(
+            &mut buffer,
+            priority2handler_id.keys(),
+            |p| format!("`{p}`"),
+            "and",
+        )
+        .unwrap();
+        buffer
+    };
+    let overall = CompilerDiagnostic::builder(
+        first.source_code,
+        anyhow!(
+            "All the routes registered for `{path}` must have the same priority, \
+            but you have assigned {} different priorities to them: {priorities}.",
+            priority2handler_id.len()
+        ),
+    )
+    .code("pavex::router::priority_mismatch")
+    .labels(first.labels.into_iter())
+    .additional_annotated_snippets(annotated_snippets)
+    .help(format!(
+        "Use the same priority for all the `{path}` routes. \
+        Priorities determine which route path matches an incoming request, \
+        they can't be used to choose between different HTTP methods for the same path."
+    ));
+    diagnostics.push(overall.build().into());
+}
+
 fn push_router_conflict_diagnostic(
     path: &str,
     method: &str,
@@ -861,6 +1098,7 @@ fn push_router_conflict_diagnostic(
             "I don't know how to route incoming `{method} {path}` requests: you have registered {n_unique_handlers} \
             different request handlers for this path+method combination."
         ))
+        .code("pavex::router::handler_conflict")
         .labels(first.labels.into_iter())
         .additional_annotated_snippets(annotated_snippets)
         .help(
@@ -869,3 +1107,52 @@ fn push_router_conflict_diagnostic(
         );
     diagnostics.push(overall.build().into());
 }
+
+fn push_trailing_slash_duplicate_diagnostic(
+    path: &str,
+    handler_id: UserComponentId,
+    slashed_handler_id: UserComponentId,
+    raw_user_component_db: &RawUserComponentDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let mut annotated_snippets: Vec<AnnotatedSnippet> = Vec::with_capacity(2);
+    for (id, label) in [
+        (handler_id, "Registered here without a trailing slash"),
+        (slashed_handler_id, "Registered here with a trailing slash"),
+    ] {
+        let location = raw_user_component_db.get_location(id);
+        let source = match location.source_file(package_graph) {
+            Ok(s) => s,
+            Err(e) => {
+                diagnostics.push(e.into());
+                continue;
+            }
+        };
+        if let Some(s) = diagnostic::get_route_path_span(&source, location) {
+            annotated_snippets.push(AnnotatedSnippet::new(source, s.labeled(label.into())));
+        }
+    }
+    let mut annotated_snippets = annotated_snippets.into_iter();
+    let Some(first) = annotated_snippets.next() else {
+        return;
+    };
+    let diagnostic = CompilerDiagnostic::builder(
+        first.source_code,
+        anyhow!(
+            "You registered routes for both `{path}` and `{path}/`.\n\
+            Pavex matches paths strictly: they are two distinct routes, and requests for one \
+            of them will never be served by the handlers of the other one."
+        ),
+    )
+    .warning()
+    .code("pavex::router::trailing_slash_duplicate")
+    .labels(first.labels.into_iter())
+    .additional_annotated_snippets(annotated_snippets)
+    .help(format!(
+        "If that's intentional, you can ignore this warning. \
+        Otherwise, remove one of the two routes. If you want to accept both `{path}` and \
+        `{path}/`, keep one of the routes and redirect to it from the other path."
+    ));
+    diagnostics.push(diagnostic.build().into());
+}
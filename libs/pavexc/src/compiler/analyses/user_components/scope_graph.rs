@@ -96,6 +96,24 @@ impl ScopeId {
             .collect()
     }
 
+    /// Return the ID of this scope followed by the IDs of all its ancestors, from the closest
+    /// to the root.
+    ///
+    /// E.g. if this scope is `RH Scope 1` in the example in [`ScopeGraph`], this method will return
+    /// `RH Scope 1`, `Scope 1` and `Root`, in this order.
+    ///
+    /// It must not be invoked on the application state scope, since it has multiple parents.
+    pub fn self_and_ancestor_ids(&self, scope_graph: &ScopeGraph) -> Vec<ScopeId> {
+        debug_assert_ne!(*self, scope_graph.application_state_scope_id());
+        let mut ids = vec![*self];
+        let mut current = *self;
+        while let Some(parent_id) = current.direct_parent_ids(scope_graph).into_iter().next() {
+            ids.push(parent_id);
+            current = parent_id;
+        }
+        ids
+    }
+
     /// Return the IDs of the scopes that are direct children of this scope, if any.
     ///
     /// E.g. if this scope is `Root` in the example in [`ScopeGraph`], this method will return
@@ -1,5 +1,7 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 use std::fmt::Debug;
+use std::fmt::Write as _;
 use std::io::{BufWriter, Write};
 use std::ops::Deref;
 use std::path::Path;
@@ -7,16 +9,20 @@ use std::path::Path;
 use ahash::{HashMap, HashMapExt};
 use bimap::BiHashMap;
 use guppy::graph::PackageGraph;
+use guppy::PackageId;
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 use miette::miette;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use proc_macro2::Ident;
 use quote::format_ident;
 
 use pavex::blueprint::{constructor::Lifecycle, Blueprint};
 
 use crate::compiler::analyses::call_graph::{
-    application_state_call_graph, ApplicationStateCallGraph, RawCallGraphExt,
+    application_state_call_graph, ApplicationStateCallGraph, CallGraphEdgeMetadata, CallGraphNode,
+    InputParameterSource, RawCallGraph, RawCallGraphExt,
 };
 use crate::compiler::analyses::components::{ComponentDb, ComponentId, HydratedComponent};
 use crate::compiler::analyses::computations::ComputationDb;
@@ -25,6 +31,7 @@ use crate::compiler::analyses::framework_items::FrameworkItemDb;
 use crate::compiler::analyses::processing_pipeline::RequestHandlerPipeline;
 use crate::compiler::analyses::router::Router;
 use crate::compiler::analyses::user_components::UserComponentDb;
+use crate::compiler::component::ResponseHook;
 use crate::compiler::computation::Computation;
 use crate::compiler::generated_app::GeneratedApp;
 use crate::compiler::resolvers::CallableResolutionError;
@@ -32,7 +39,7 @@ use crate::compiler::traits::{assert_trait_is_implemented, MissingTraitImplement
 use crate::compiler::utils::process_framework_path;
 use crate::compiler::{codegen, path_parameter_validation};
 use crate::diagnostic;
-use crate::diagnostic::{CompilerDiagnostic, LocationExt, SourceSpanExt};
+use crate::diagnostic::{CompilerDiagnostic, Diagnostic, LocationExt, SourceSpanExt};
 use crate::language::ResolvedType;
 use crate::rustdoc::CrateCollection;
 
@@ -50,6 +57,11 @@ pub struct App {
     codegen_deps: HashMap<String, guppy::PackageId>,
     component_db: ComponentDb,
     computation_db: ComputationDb,
+    log_constructor_failures: bool,
+    /// Diagnostics that didn't prevent us from building the application—e.g. warnings.
+    warnings: Vec<miette::Error>,
+    /// The structured counterpart of `warnings`, one entry for each of them.
+    structured_warnings: Vec<Diagnostic>,
 }
 
 impl App {
@@ -64,7 +76,7 @@ impl App {
         /// Exit early if there is at least one error.
         macro_rules! exit_on_errors {
             ($var:ident) => {
-                if !$var.is_empty() {
+                if crate::diagnostic::has_errors(&$var) {
                     return Err($var);
                 }
             };
@@ -75,6 +87,7 @@ impl App {
         let package_graph = krate_collection.package_graph().to_owned();
         let mut diagnostics = vec![];
         let mut computation_db = ComputationDb::new();
+        let log_constructor_failures = bp.log_constructor_failures;
         let Ok((router, user_component_db)) = UserComponentDb::build(
             &bp,
             &mut computation_db,
@@ -138,6 +151,14 @@ impl App {
             &krate_collection,
             &mut diagnostics,
         );
+        verify_response_hook(
+            &handler_id2pipeline,
+            &framework_item_db,
+            &component_db,
+            &computation_db,
+            &package_graph,
+            &mut diagnostics,
+        );
         exit_on_errors!(diagnostics);
 
         let runtime_singletons: IndexSet<(ResolvedType, ComponentId)> =
@@ -185,9 +206,41 @@ impl App {
             framework_item_db,
             runtime_singleton_bindings,
             codegen_deps,
+            log_constructor_failures,
+            structured_warnings: diagnostics.iter().map(Diagnostic::from).collect(),
+            warnings: diagnostics,
         })
     }
 
+    /// Process the [`Blueprint`] created by user into an [`App`] instance, just like
+    /// [`App::build`].
+    ///
+    /// Errors, if any, are returned as structured [`Diagnostic`]s rather than as
+    /// [`miette::Error`]s: use this method if you want to assert programmatically on
+    /// the outcome of the analysis (e.g. in your tests) rather than render it in a terminal.
+    pub fn analyze(bp: Blueprint, project_fingerprint: String) -> Result<Self, Vec<Diagnostic>> {
+        Self::build(bp, project_fingerprint)
+            .map_err(|errors| errors.iter().map(Diagnostic::from).collect())
+    }
+
+    /// The warnings that were emitted while processing the [`Blueprint`], as structured
+    /// [`Diagnostic`]s.
+    ///
+    /// They didn't prevent Pavex from building the application, but they should be
+    /// reported to the user.
+    /// Check out [`App::warning_reports`] if you want to render them in a terminal.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.structured_warnings
+    }
+
+    /// The warnings that were emitted while processing the [`Blueprint`], as
+    /// [`miette::Error`]s ready to be rendered.
+    ///
+    /// Check out [`App::warnings`] if you want to assert on them programmatically.
+    pub fn warning_reports(&self) -> &[miette::Error] {
+        &self.warnings
+    }
+
     /// Generate the manifest and the Rust code for the analysed application.
     ///
     /// They are generated in-memory, they are not persisted to disk.
@@ -203,7 +256,7 @@ impl App {
             &self.component_db,
             &self.computation_db,
         );
-        let lib_rs = codegen::codegen_app(
+        let (lib_rs, route_modules) = codegen::codegen_app(
             &self.router,
             &self.handler_id2pipeline,
             &self.application_state_call_graph,
@@ -214,9 +267,12 @@ impl App {
             &self.component_db,
             &self.computation_db,
             &self.framework_item_db,
+            self.log_constructor_failures,
         )?;
         Ok(GeneratedApp {
             lib_rs,
+            route_modules,
+            split_modules: false,
             cargo_toml,
             package_graph: self.package_graph.clone(),
         })
@@ -267,6 +323,303 @@ impl App {
             application_state: application_state_graph,
         }
     }
+
+    /// Summarize, for each route, how the framework extractors it relies on (e.g.
+    /// `BufferedBody` or `JsonBody`) are configured: which components provide their inputs
+    /// (e.g. the `BodySizeLimit` in scope for that route) and which error handler
+    /// is invoked if extraction fails.
+    ///
+    /// Every constructor defined in the `pavex` crate counts as a framework extractor,
+    /// regardless of the module it lives in—e.g. `ResponseSender::extract` is included too.
+    pub fn extractor_report(&self) -> ExtractorReport {
+        let (_, package_ids2deps) = codegen::codegen_manifest(
+            &self.package_graph,
+            self.handler_id2pipeline.values(),
+            &self.application_state_call_graph.call_graph.call_graph,
+            &self.framework_item_db.bindings(),
+            &self.codegen_deps,
+            &self.component_db,
+            &self.computation_db,
+        );
+
+        let mut routes = IndexMap::new();
+        for (path, method_router) in &self.router.route_path2sub_router {
+            for (handler_id, methods) in method_router
+                .handler_id2methods
+                .iter()
+                .map(|(k, v)| (*k, Some(v)))
+                .chain(std::iter::once((method_router.fallback_id, None)))
+            {
+                let method = methods
+                    .map(|m| m.iter().join(" | "))
+                    .unwrap_or_else(|| "*".into());
+                let pipeline = &self.handler_id2pipeline[&handler_id];
+                let mut extractors = IndexMap::new();
+                for graph in pipeline.graph_iter() {
+                    self.collect_extractors(&graph.call_graph, &package_ids2deps, &mut extractors);
+                }
+                routes.insert(
+                    (path.to_owned(), method),
+                    extractors.into_values().collect(),
+                );
+            }
+        }
+        ExtractorReport { routes }
+    }
+
+    /// Summarize, for each route, the fallible components it relies on: the type of the
+    /// error they return and the error handler that is invoked if they fail.
+    ///
+    /// It reports the *effective* error handler for each component, whether it was
+    /// registered against the component itself (via `.error_handler`) or it was
+    /// picked among the type-wide error handlers in scope (via `Blueprint::error_handler`).
+    pub fn error_handler_report(&self) -> ErrorHandlerReport {
+        let (_, package_ids2deps) = codegen::codegen_manifest(
+            &self.package_graph,
+            self.handler_id2pipeline.values(),
+            &self.application_state_call_graph.call_graph.call_graph,
+            &self.framework_item_db.bindings(),
+            &self.codegen_deps,
+            &self.component_db,
+            &self.computation_db,
+        );
+
+        let mut routes = IndexMap::new();
+        for (path, method_router) in &self.router.route_path2sub_router {
+            for (handler_id, methods) in method_router
+                .handler_id2methods
+                .iter()
+                .map(|(k, v)| (*k, Some(v)))
+                .chain(std::iter::once((method_router.fallback_id, None)))
+            {
+                let method = methods
+                    .map(|m| m.iter().join(" | "))
+                    .unwrap_or_else(|| "*".into());
+                let pipeline = &self.handler_id2pipeline[&handler_id];
+                let mut fallible_components = IndexMap::new();
+                for graph in pipeline.graph_iter() {
+                    self.collect_fallible_components(
+                        &graph.call_graph,
+                        &package_ids2deps,
+                        &mut fallible_components,
+                    );
+                }
+                routes.insert(
+                    (path.to_owned(), method),
+                    fallible_components.into_values().collect(),
+                );
+            }
+        }
+        ErrorHandlerReport { routes }
+    }
+
+    /// The error handler that is invoked when a component used by the route at `path`
+    /// fails with an error of type `error_type`—e.g. `app::DatabaseError`.
+    ///
+    /// `path` is the full path of the route, including the prefixes of the blueprints
+    /// it was nested into—e.g. `/api/users/:id`.
+    /// The returned value is the path of the error handler—e.g. `app::db_error_handler`.
+    /// Type-wide error handlers are taken into account: if a nested blueprint overrides the
+    /// error handler for a type, its routes report the override while the routes of its
+    /// parent report the original.
+    ///
+    /// It returns `None` if none of the components used by the route can fail with an
+    /// error of type `error_type`.
+    /// Check out [`App::error_handler_report`] if you need the full picture.
+    pub fn effective_error_handler(&self, path: &str, error_type: &str) -> Option<String> {
+        self.error_handler_report()
+            .effective_error_handler(path, error_type)
+            .map(ToOwned::to_owned)
+    }
+
+    /// Summarize what the generated application is made of: the routes it serves,
+    /// the constructors registered by the user and the number of warnings emitted
+    /// while processing the [`Blueprint`].
+    pub fn summary(&self) -> AppSummary {
+        let routes = self
+            .router
+            .route_path2sub_router
+            .iter()
+            .flat_map(|(path, method_router)| {
+                // Routes registered with `ANY` don't have any method-specific handler:
+                // the leaf router always invokes its fallback.
+                if method_router.handler_id2methods.is_empty() {
+                    return vec![(path.to_owned(), "*".to_string())];
+                }
+                method_router
+                    .handler_id2methods
+                    .values()
+                    .map(|methods| (path.to_owned(), methods.iter().join(" | ")))
+                    .collect()
+            })
+            .collect();
+        let constructors = self
+            .component_db
+            .constructors(&self.computation_db)
+            .filter(|(id, _)| self.component_db.user_component_id(*id).is_some())
+            .map(|(id, _)| {
+                let lifecycle = *self.component_db.lifecycle(id).unwrap();
+                (self.component_origin(id), lifecycle)
+            })
+            .collect();
+        AppSummary {
+            routes,
+            constructors,
+            n_warnings: self.warnings.len(),
+        }
+    }
+
+    fn collect_extractors(
+        &self,
+        call_graph: &RawCallGraph,
+        package_ids2names: &BiHashMap<PackageId, String>,
+        extractors: &mut IndexMap<ComponentId, ExtractorConfiguration>,
+    ) {
+        for node_index in call_graph.node_indices() {
+            let CallGraphNode::Compute { component_id, .. } = &call_graph[node_index] else {
+                continue;
+            };
+            if extractors.contains_key(component_id) {
+                continue;
+            }
+            // Framework extractors are the constructors defined by `pavex` itself,
+            // no matter which module they live in.
+            let component = self
+                .component_db
+                .hydrated_component(*component_id, &self.computation_db);
+            let HydratedComponent::Constructor(_) = &component else {
+                continue;
+            };
+            let Computation::Callable(callable) = component.computation() else {
+                continue;
+            };
+            if callable.path.package_id != self.codegen_deps["pavex"] {
+                continue;
+            }
+            let extractor = callable.path.to_string();
+            let mut inputs: Vec<_> = call_graph
+                .edges_directed(node_index, Direction::Incoming)
+                .map(|edge| {
+                    let borrow = match edge.weight() {
+                        CallGraphEdgeMetadata::Move => "",
+                        CallGraphEdgeMetadata::SharedBorrow => "&",
+                    };
+                    let (type_, provider) =
+                        self.describe_input(&call_graph[edge.source()], package_ids2names);
+                    (format!("{borrow}{type_}"), provider)
+                })
+                .collect();
+            inputs.sort();
+            let error_handler = self
+                .component_db
+                .match_ids(*component_id)
+                .and_then(|(_, err_id)| self.component_db.error_handler_id(*err_id))
+                .map(|id| self.component_origin(*id));
+            extractors.insert(
+                *component_id,
+                ExtractorConfiguration {
+                    extractor,
+                    inputs,
+                    error_handler,
+                },
+            );
+        }
+    }
+
+    fn collect_fallible_components(
+        &self,
+        call_graph: &RawCallGraph,
+        package_ids2names: &BiHashMap<PackageId, String>,
+        fallible_components: &mut IndexMap<ComponentId, ErrorHandlerConfiguration>,
+    ) {
+        for node_index in call_graph.node_indices() {
+            let CallGraphNode::Compute { component_id, .. } = &call_graph[node_index] else {
+                continue;
+            };
+            if fallible_components.contains_key(component_id) {
+                continue;
+            }
+            let Some(error_handler_id) = self
+                .component_db
+                .match_ids(*component_id)
+                .and_then(|(_, err_id)| self.component_db.error_handler_id(*err_id))
+            else {
+                continue;
+            };
+            let HydratedComponent::ErrorHandler(error_handler) = self
+                .component_db
+                .hydrated_component(*error_handler_id, &self.computation_db)
+            else {
+                unreachable!()
+            };
+            let error_type = match error_handler.error_type_ref() {
+                ResolvedType::Reference(r) => r.inner.render_type(package_ids2names),
+                t => t.render_type(package_ids2names),
+            };
+            fallible_components.insert(
+                *component_id,
+                ErrorHandlerConfiguration {
+                    fallible: self.component_origin(*component_id),
+                    error_type,
+                    error_handler: self.component_origin(*error_handler_id),
+                },
+            );
+        }
+    }
+
+    /// Return the type of the value produced by a call graph node, alongside a
+    /// description of where it comes from.
+    fn describe_input(
+        &self,
+        node: &CallGraphNode,
+        package_ids2names: &BiHashMap<PackageId, String>,
+    ) -> (String, String) {
+        let component = |id: ComponentId| {
+            self.component_db
+                .hydrated_component(id, &self.computation_db)
+                .output_type()
+                .render_type(package_ids2names)
+        };
+        match node {
+            CallGraphNode::Compute { component_id, .. } => (
+                component(*component_id),
+                self.component_origin(*component_id),
+            ),
+            CallGraphNode::InputParameter {
+                type_,
+                source: InputParameterSource::Component(id),
+            } => {
+                let provider = match self
+                    .component_db
+                    .hydrated_component(*id, &self.computation_db)
+                    .computation()
+                {
+                    Computation::FrameworkItem(_) => "framework".to_string(),
+                    _ => format!("{} (singleton)", self.component_origin(*id)),
+                };
+                (type_.render_type(package_ids2names), provider)
+            }
+            CallGraphNode::InputParameter {
+                type_,
+                source: InputParameterSource::External,
+            } => (type_.render_type(package_ids2names), "external".to_string()),
+            CallGraphNode::MatchBranching => unreachable!(),
+        }
+    }
+
+    /// The path of the user-registered (or framework-provided) callable that a component
+    /// originates from.
+    fn component_origin(&self, id: ComponentId) -> String {
+        match self
+            .component_db
+            .hydrated_component(id, &self.computation_db)
+            .computation()
+        {
+            Computation::Callable(c) => c.path.to_string(),
+            Computation::MatchResult(_) => self.component_origin(self.component_db.fallible_id(id)),
+            Computation::FrameworkItem(_) => "framework".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -342,6 +695,150 @@ impl AppDiagnostics {
     }
 }
 
+/// A summary of the configuration of the framework extractors used by an `App`.
+///
+/// See [`App::extractor_report`] for more details.
+pub struct ExtractorReport {
+    /// For each route, the framework extractors it relies on.
+    ///
+    /// The key is a tuple of `(path, methods)`, see [`AppDiagnostics::handlers`].
+    pub routes: IndexMap<(String, String), Vec<ExtractorConfiguration>>,
+}
+
+/// How a framework extractor is configured for a given route.
+pub struct ExtractorConfiguration {
+    /// The path of the extractor—e.g. `pavex::request::body::BufferedBody::extract`.
+    pub extractor: String,
+    /// The inputs of the extractor, paired with the component that provides them—e.g.
+    /// `pavex::request::body::BodySizeLimit` and `app::upload_size_limit`.
+    pub inputs: Vec<(String, String)>,
+    /// The path of the error handler that will be invoked if extraction fails, if the
+    /// extractor is fallible.
+    pub error_handler: Option<String>,
+}
+
+impl ExtractorReport {
+    /// Render the report in a human-readable format.
+    ///
+    /// Routes that don't use any framework extractor are omitted.
+    pub fn render(&self) -> String {
+        let mut buffer = String::new();
+        for ((path, method), extractors) in &self.routes {
+            if extractors.is_empty() {
+                continue;
+            }
+            writeln!(buffer, "{method} {path}").unwrap();
+            for extractor in extractors {
+                writeln!(buffer, "    {}", extractor.extractor).unwrap();
+                for (type_, provider) in &extractor.inputs {
+                    writeln!(buffer, "        {type_} <- {provider}").unwrap();
+                }
+                if let Some(error_handler) = &extractor.error_handler {
+                    writeln!(buffer, "        error handler: {error_handler}").unwrap();
+                }
+            }
+        }
+        buffer
+    }
+
+    /// Save the report to the specified file.
+    pub fn persist(&self, filepath: &Path) -> Result<(), anyhow::Error> {
+        fs_err::write(filepath, self.render())?;
+        Ok(())
+    }
+}
+
+/// A summary of the error handlers used by an `App`.
+///
+/// See [`App::error_handler_report`] for more details.
+pub struct ErrorHandlerReport {
+    /// For each route, the fallible components it relies on.
+    ///
+    /// The key is a tuple of `(path, methods)`, see [`AppDiagnostics::handlers`].
+    pub routes: IndexMap<(String, String), Vec<ErrorHandlerConfiguration>>,
+}
+
+/// The error handler invoked when a fallible component fails, for a given route.
+pub struct ErrorHandlerConfiguration {
+    /// The path of the fallible component—e.g. `app::get_user`.
+    pub fallible: String,
+    /// The error type returned by the fallible component—e.g. `app::DatabaseError`.
+    pub error_type: String,
+    /// The path of the error handler that will be invoked if the component fails.
+    pub error_handler: String,
+}
+
+impl ErrorHandlerReport {
+    /// The error handler invoked for errors of type `error_type` on the route at `path`.
+    ///
+    /// See [`App::effective_error_handler`] for more details.
+    pub fn effective_error_handler(&self, path: &str, error_type: &str) -> Option<&str> {
+        self.routes
+            .iter()
+            .filter(|((route_path, _), _)| route_path == path)
+            .flat_map(|(_, components)| components)
+            .find(|component| component.error_type == error_type)
+            .map(|component| component.error_handler.as_str())
+    }
+
+    /// Render the report in a human-readable format.
+    ///
+    /// Routes that don't rely on any fallible component are omitted.
+    pub fn render(&self) -> String {
+        let mut buffer = String::new();
+        for ((path, method), fallible_components) in &self.routes {
+            if fallible_components.is_empty() {
+                continue;
+            }
+            writeln!(buffer, "{method} {path}").unwrap();
+            for component in fallible_components {
+                writeln!(buffer, "    {}", component.fallible).unwrap();
+                writeln!(buffer, "        error type: {}", component.error_type).unwrap();
+                writeln!(buffer, "        error handler: {}", component.error_handler).unwrap();
+            }
+        }
+        buffer
+    }
+
+    /// Save the report to the specified file.
+    pub fn persist(&self, filepath: &Path) -> Result<(), anyhow::Error> {
+        fs_err::write(filepath, self.render())?;
+        Ok(())
+    }
+}
+
+/// A summary of what an `App` is made of.
+///
+/// See [`App::summary`] for more details.
+pub struct AppSummary {
+    /// The routes served by the application, as `(path, methods)` tuples.
+    ///
+    /// See [`AppDiagnostics::handlers`] for more details on the format.
+    /// Routes that match all methods (i.e. registered with `ANY`) are listed as `*`.
+    pub routes: Vec<(String, String)>,
+    /// The path of each constructor registered by the user, alongside its lifecycle.
+    pub constructors: Vec<(String, Lifecycle)>,
+    /// The number of warnings emitted while processing the blueprint.
+    pub n_warnings: usize,
+}
+
+impl AppSummary {
+    /// Render the summary in a human-readable format.
+    pub fn render(&self) -> String {
+        let mut buffer = String::new();
+        writeln!(buffer, "Routes ({}):", self.routes.len()).unwrap();
+        for (path, methods) in &self.routes {
+            writeln!(buffer, "    {methods} {path}").unwrap();
+        }
+        writeln!(buffer, "Constructors ({}):", self.constructors.len()).unwrap();
+        for (constructor, lifecycle) in &self.constructors {
+            writeln!(buffer, "    {constructor} ({lifecycle})").unwrap();
+        }
+        writeln!(buffer, "Diagnostics: {} warning(s)", self.n_warnings).unwrap();
+        buffer
+    }
+}
+
 /// Determine the set of singleton types that are required to execute the constructors and handlers
 /// registered by the application.
 /// These singletons will be attached to the overall application state.
@@ -522,3 +1019,112 @@ fn verify_singletons(
         }
     }
 }
+
+/// A response hook that takes `&RequestHead` as input borrows the request head
+/// once the request processing pipeline has returned a response.
+/// The request head must therefore outlive the pipeline: no component in the pipeline
+/// can take it by value.
+fn verify_response_hook(
+    handler_pipelines: &IndexMap<ComponentId, RequestHandlerPipeline>,
+    framework_item_db: &FrameworkItemDb,
+    component_db: &ComponentDb,
+    computation_db: &ComputationDb,
+    package_graph: &PackageGraph,
+    diagnostics: &mut Vec<miette::Error>,
+) {
+    let Some(response_hook_id) = component_db.response_hook_id() else {
+        return;
+    };
+    // Invalid response hooks have already been reported when building the component database.
+    let Ok(response_hook) = ResponseHook::new(Cow::Borrowed(&computation_db[response_hook_id]))
+    else {
+        return;
+    };
+    if !response_hook.needs_request_head() {
+        return;
+    }
+    let request_head = framework_item_db
+        .get_type(FrameworkItemDb::request_head_id())
+        .unwrap();
+    let consumes_request_head = handler_pipelines.values().any(|pipeline| {
+        pipeline
+            .graph_iter()
+            .any(|graph| graph.required_input_types().contains(request_head))
+    });
+    if !consumes_request_head {
+        return;
+    }
+
+    let user_component_db = component_db.user_component_db();
+    let location = user_component_db.get_location(response_hook_id);
+    let source = match location.source_file(package_graph) {
+        Ok(s) => s,
+        Err(e) => {
+            diagnostics.push(e.into());
+            return;
+        }
+    };
+    let label = diagnostic::get_f_macro_invocation_span(&source, location)
+        .map(|s| s.labeled("The response hook was registered here".into()));
+    let err = anyhow::anyhow!(
+        "Your response hook borrows `pavex::request::RequestHead`, but at least one of your \
+        components takes it by value.\n\
+        The response hook is invoked after the request has been processed: the request head \
+        must still be around at that point, it can't be consumed."
+    );
+    let diagnostic = CompilerDiagnostic::builder(source, err)
+        .code("pavex::response_hook::request_head_consumed")
+        .optional_label(label)
+        .help(
+            "Take `&RequestHead` rather than `RequestHead` as input parameter in your components."
+                .into(),
+        )
+        .build();
+    diagnostics.push(diagnostic.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{ErrorHandlerConfiguration, ErrorHandlerReport};
+
+    fn configuration(error_type: &str, error_handler: &str) -> ErrorHandlerConfiguration {
+        ErrorHandlerConfiguration {
+            fallible: "app::get_user".into(),
+            error_type: error_type.into(),
+            error_handler: error_handler.into(),
+        }
+    }
+
+    #[test]
+    fn the_effective_error_handler_depends_on_the_scope_of_the_route() {
+        let mut routes = IndexMap::new();
+        routes.insert(
+            ("/users/:id".to_string(), "GET".to_string()),
+            vec![configuration("app::DbError", "app::db_error")],
+        );
+        routes.insert(
+            ("/admin/users/:id".to_string(), "GET".to_string()),
+            vec![configuration("app::DbError", "app::admin_db_error")],
+        );
+        let report = ErrorHandlerReport { routes };
+
+        assert_eq!(
+            report.effective_error_handler("/users/:id", "app::DbError"),
+            Some("app::db_error")
+        );
+        assert_eq!(
+            report.effective_error_handler("/admin/users/:id", "app::DbError"),
+            Some("app::admin_db_error")
+        );
+        assert_eq!(
+            report.effective_error_handler("/users/:id", "app::OtherError"),
+            None
+        );
+        assert_eq!(
+            report.effective_error_handler("/unknown", "app::DbError"),
+            None
+        );
+    }
+}
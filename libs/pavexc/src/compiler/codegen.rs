@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet};
 
 use ahash::{HashMap, HashSet};
@@ -12,7 +14,7 @@ use quote::{format_ident, quote};
 use syn::{ItemEnum, ItemFn, ItemStruct};
 
 use crate::compiler::analyses::call_graph::{
-    ApplicationStateCallGraph, CallGraphNode, RawCallGraph,
+    ApplicationStateCallGraph, ApplicationStateErrorVariant, CallGraphNode, RawCallGraph,
 };
 use crate::compiler::analyses::components::{ComponentDb, ComponentId};
 use crate::compiler::analyses::computations::ComputationDb;
@@ -22,23 +24,39 @@ use crate::compiler::analyses::processing_pipeline::{
 };
 use crate::compiler::analyses::router::Router;
 use crate::compiler::app::GENERATED_APP_PACKAGE_ID;
+use crate::compiler::codegen_utils::codegen_call;
+use crate::compiler::component::ResponseHook;
 use crate::compiler::computation::Computation;
 use crate::language::{Callable, GenericArgument, ResolvedType};
 use crate::rustdoc::{ALLOC_PACKAGE_ID_REPR, TOOLCHAIN_CRATES};
 
-use super::generated_app::GeneratedManifest;
+use super::generated_app::{GeneratedManifest, RouteModule};
 
 #[derive(Debug, Clone)]
 pub(super) struct CodegenMethodRouter {
-    pub(super) methods_and_pipelines: Vec<(BTreeSet<String>, CodegenedRequestHandlerPipeline)>,
+    /// The methods handled by each pipeline, alongside the value of the `Cache-Control` header
+    /// that must be set on its responses (if any).
+    pub(super) methods_and_pipelines: Vec<(
+        BTreeSet<String>,
+        CodegenedRequestHandlerPipeline,
+        Option<String>,
+    )>,
     pub(super) catch_all_pipeline: CodegenedRequestHandlerPipeline,
+    /// The value of the `Cache-Control` header that must be set on the responses returned
+    /// by `catch_all_pipeline`, if any.
+    /// It can only be set for routes that match all methods—fallbacks don't have
+    /// caching directives.
+    pub(super) catch_all_cache_control: Option<String>,
+    /// If `true`, `catch_all_pipeline` is a `405 Method Not Allowed` handler and
+    /// Pavex must make sure that its responses include an `Allow` header.
+    pub(super) method_not_allowed: bool,
 }
 
 impl CodegenMethodRouter {
     pub fn pipelines(&self) -> impl Iterator<Item = &CodegenedRequestHandlerPipeline> {
         self.methods_and_pipelines
             .iter()
-            .map(|(_, p)| p)
+            .map(|(_, p, _)| p)
             .chain(std::iter::once(&self.catch_all_pipeline))
     }
 
@@ -64,7 +82,8 @@ pub(crate) fn codegen_app(
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
     framework_item_db: &FrameworkItemDb,
-) -> Result<TokenStream, anyhow::Error> {
+    log_constructor_failures: bool,
+) -> Result<(TokenStream, Vec<RouteModule>), anyhow::Error> {
     let get_codegen_dep_import_name = |name: &str| {
         let pkg_id = codegen_deps.get(name).unwrap();
         let import_name = package_id2name.get_by_left(pkg_id).unwrap().clone();
@@ -90,19 +109,32 @@ pub(crate) fn codegen_app(
         computation_db,
     )?;
 
-    let define_server_state = define_server_state(&application_state_def, &matchit_import_name);
-
+    let failure_logger = log_constructor_failures.then_some(&pavex_import_name);
     let handler_id2codegened_pipeline = handler_id2pipeline
         .iter()
         .map(|(id, p)| {
-            p.codegen(package_id2name, component_db, computation_db)
-                .map(|p| (*id, p))
+            p.codegen(
+                package_id2name,
+                component_db,
+                computation_db,
+                failure_logger,
+            )
+            .map(|p| (*id, p))
         })
         .collect::<Result<BTreeMap<_, _>, _>>()?;
-    let handler_modules = handler_id2codegened_pipeline
+    let route_modules = handler_id2codegened_pipeline
         .values()
-        .map(|p| p.as_inline_module())
+        .map(|p| RouteModule {
+            name: format_ident!("{}", p.module_name),
+            code: p.as_module_body(),
+        })
         .collect::<Vec<_>>();
+    let handler_id2cache_control = |handler_id: &ComponentId| {
+        component_db
+            .user_component_id(*handler_id)
+            .and_then(|id| component_db.user_component_db().cache_control(id))
+            .map(ToOwned::to_owned)
+    };
     let path2codegen_router_entry = {
         let mut map: IndexMap<String, CodegenMethodRouter> = IndexMap::new();
         for (path, method_router) in &router.route_path2sub_router {
@@ -110,15 +142,22 @@ pub(crate) fn codegen_app(
                 Vec::with_capacity(method_router.handler_id2methods.len());
             for (handler_id, methods) in &method_router.handler_id2methods {
                 let pipeline = &handler_id2codegened_pipeline[handler_id];
-                methods_and_pipelines.push((methods.clone(), pipeline.clone()));
+                methods_and_pipelines.push((
+                    methods.clone(),
+                    pipeline.clone(),
+                    handler_id2cache_control(handler_id),
+                ));
             }
             let catch_all_pipeline =
                 handler_id2codegened_pipeline[&method_router.fallback_id].clone();
+            let catch_all_cache_control = handler_id2cache_control(&method_router.fallback_id);
             map.insert(
                 path.to_owned(),
                 CodegenMethodRouter {
                     methods_and_pipelines,
                     catch_all_pipeline,
+                    catch_all_cache_control,
+                    method_not_allowed: method_router.method_not_allowed,
                 },
             );
         }
@@ -127,19 +166,48 @@ pub(crate) fn codegen_app(
 
     let mut route_id2path = BiBTreeMap::new();
     let mut route_id2router_entry = BTreeMap::new();
+    // Route ids, grouped by priority—from the highest to the lowest.
+    let mut priority2route_ids: BTreeMap<Reverse<i32>, Vec<u32>> = BTreeMap::new();
     for (route_id, (path, router_entry)) in path2codegen_router_entry.iter().enumerate() {
         route_id2path.insert(route_id as u32, path.to_owned());
         route_id2router_entry.insert(route_id as u32, router_entry.to_owned());
+        priority2route_ids
+            .entry(Reverse(router.route_path2sub_router[path].priority))
+            .or_default()
+            .push(route_id as u32);
     }
+    // We only need multiple routers if the user has assigned different priorities
+    // to their routes.
+    let has_priority_levels = priority2route_ids.len() > 1;
 
-    let router_init = get_router_init(&route_id2path, &matchit_import_name);
+    let define_server_state = define_server_state(
+        &application_state_def,
+        &matchit_import_name,
+        has_priority_levels,
+    );
+    let router_init = get_router_init(&route_id2path, &priority2route_ids, &matchit_import_name);
     let fallback_codegened_pipeline = &handler_id2codegened_pipeline[&router.root_fallback_id];
+    let needs_dispatcher = handler_id2codegened_pipeline
+        .values()
+        .any(|p| p.needs_dispatcher(framework_item_db));
+    let needs_local_addr = handler_id2codegened_pipeline
+        .values()
+        .any(|p| p.needs_local_addr(framework_item_db));
+    let response_hook = component_db.response_hook_id().map(|id| {
+        // The response hook has already been validated when building the component database.
+        ResponseHook::new(Cow::Borrowed(&computation_db[id])).unwrap()
+    });
     let route_request = get_request_dispatcher(
         &route_id2router_entry,
         &route_id2path,
         fallback_codegened_pipeline,
+        needs_dispatcher,
+        needs_local_addr,
+        has_priority_levels,
+        response_hook.as_ref(),
         runtime_singleton_bindings,
         request_scoped_framework_bindings,
+        package_id2name,
         framework_item_db,
         &pavex_import_name,
         &http_import_name,
@@ -160,9 +228,6 @@ pub(crate) fn codegen_app(
         quote! {}
     };
     let code = quote! {
-        //! Do NOT edit this code.
-        //! It was automatically generated by Pavex.
-        //! All manual edits will be lost next time the code is generated.
         #alloc_extern_import
         #define_server_state
         #application_state_def
@@ -171,9 +236,8 @@ pub(crate) fn codegen_app(
         #entrypoint
         #router_init
         #route_request
-        #(#handler_modules)*
     };
-    Ok(code)
+    Ok((code, route_modules))
 }
 
 fn server_startup(pavex: &Ident) -> ItemFn {
@@ -218,19 +282,29 @@ fn define_application_state(
 }
 
 fn define_application_state_error(
-    error_types: &IndexMap<String, ResolvedType>,
+    error_variants: &IndexMap<String, ApplicationStateErrorVariant>,
     package_id2name: &BiHashMap<PackageId, String>,
     thiserror_import_name: &Ident,
 ) -> Option<ItemEnum> {
-    if error_types.is_empty() {
+    if error_variants.is_empty() {
         return None;
     }
-    let singleton_fields = error_types.iter().map(|(variant_name, type_)| {
-        let variant_type = type_.syn_type(package_id2name);
+    let singleton_fields = error_variants.iter().map(|(variant_name, variant)| {
+        let variant_type = variant.error_type.syn_type(package_id2name);
         let variant_name = format_ident!("{}", variant_name);
+        // The error message names the constructor that failed, to make it
+        // easy to pinpoint the source of the problem when the application fails to boot.
+        // The message is used as a format string by `thiserror`: braces in the path
+        // (e.g. a const generic argument) must be escaped.
+        let constructor_path = variant
+            .constructor_path
+            .replace('{', "{{")
+            .replace('}', "}}");
+        let error_message =
+            format!("Failed to build a singleton: `{constructor_path}` returned an error");
         quote! {
-            #[error(transparent)]
-            #variant_name(#variant_type)
+            #[error(#error_message)]
+            #variant_name(#[source] #variant_type)
         }
     });
     Some(
@@ -247,6 +321,7 @@ fn define_application_state_error(
 fn define_server_state(
     application_state_def: &ItemStruct,
     matchit_import_name: &Ident,
+    has_priority_levels: bool,
 ) -> ItemStruct {
     let attribute = if application_state_def.fields.is_empty() {
         quote! {
@@ -255,9 +330,15 @@ fn define_server_state(
     } else {
         quote! {}
     };
+    let router_type = if has_priority_levels {
+        // One router for each priority level, from the highest to the lowest.
+        quote! { Vec<#matchit_import_name::Router<u32>> }
+    } else {
+        quote! { #matchit_import_name::Router<u32> }
+    };
     syn::parse2(quote! {
         struct ServerState {
-            router: #matchit_import_name::Router<u32>,
+            router: #router_type,
             #attribute
             application_state: ApplicationState
         }
@@ -271,10 +352,13 @@ fn get_application_state_init(
     component_db: &ComponentDb,
     computation_db: &ComputationDb,
 ) -> Result<ItemFn, anyhow::Error> {
+    // Failures in singleton constructors are surfaced via `ApplicationStateError`,
+    // there is no need to log them.
     let mut function = application_state_call_graph.call_graph.codegen(
         package_id2name,
         component_db,
         computation_db,
+        None,
     )?;
     function.sig.ident = format_ident!("build_application_state");
     if !application_state_call_graph.error_variants.is_empty() {
@@ -288,7 +372,14 @@ fn get_application_state_init(
     Ok(function)
 }
 
-fn get_router_init(route_id2path: &BiBTreeMap<u32, String>, matchit_import_name: &Ident) -> ItemFn {
+fn get_router_init(
+    route_id2path: &BiBTreeMap<u32, String>,
+    priority2route_ids: &BTreeMap<Reverse<i32>, Vec<u32>>,
+    matchit_import_name: &Ident,
+) -> ItemFn {
+    if priority2route_ids.len() > 1 {
+        return get_prioritized_router_init(route_id2path, priority2route_ids, matchit_import_name);
+    }
     let mut router_init = quote! {
         let mut router = #matchit_import_name::Router::new();
     };
@@ -310,17 +401,59 @@ fn get_router_init(route_id2path: &BiBTreeMap<u32, String>, matchit_import_name:
     .unwrap()
 }
 
+/// Build one router for each priority level.
+/// At runtime, they'll be tried in order, until one of them matches the incoming request path.
+fn get_prioritized_router_init(
+    route_id2path: &BiBTreeMap<u32, String>,
+    priority2route_ids: &BTreeMap<Reverse<i32>, Vec<u32>>,
+    matchit_import_name: &Ident,
+) -> ItemFn {
+    let n_routers = priority2route_ids.len();
+    let mut routers_init = quote! {
+        let mut routers = Vec::with_capacity(#n_routers);
+    };
+    for route_ids in priority2route_ids.values() {
+        let inserts = route_ids.iter().map(|route_id| {
+            let path = route_id2path.get_by_left(route_id).unwrap();
+            quote! {
+                router.insert(#path, #route_id).unwrap();
+            }
+        });
+        routers_init = quote! {
+            #routers_init
+            let mut router = #matchit_import_name::Router::new();
+            #(#inserts)*
+            routers.push(router);
+        };
+    }
+    syn::parse2(quote! {
+        fn build_router() -> Vec<#matchit_import_name::Router<u32>> {
+            // Pavex has validated at compile-time that all route paths are valid
+            // and that there are no conflicts between routes with the same priority,
+            // therefore we can safely unwrap every `insert`.
+            #routers_init
+            routers
+        }
+    })
+    .unwrap()
+}
+
 fn get_request_dispatcher(
     route_id2router_entry: &BTreeMap<u32, CodegenMethodRouter>,
     route_id2path: &BiBTreeMap<u32, String>,
     fallback_codegened_pipeline: &CodegenedRequestHandlerPipeline,
+    needs_dispatcher: bool,
+    needs_local_addr: bool,
+    has_priority_levels: bool,
+    response_hook: Option<&ResponseHook>,
     singleton_bindings: &BiHashMap<Ident, ResolvedType>,
     request_scoped_bindings: &BiHashMap<Ident, ResolvedType>,
+    package_id2name: &BiHashMap<PackageId, String>,
     framework_items_db: &FrameworkItemDb,
     pavex: &Ident,
     http: &Ident,
     hyper: &Ident,
-) -> ItemFn {
+) -> TokenStream {
     static WELL_KNOWN_METHODS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
         HashSet::from_iter(
             [
@@ -336,10 +469,15 @@ fn get_request_dispatcher(
     for (route_id, sub_router) in route_id2router_entry {
         let match_arm = if sub_router.methods_and_pipelines.is_empty() {
             // We just have the catch-all handler, we can skip the `match`.
-            sub_router.catch_all_pipeline.entrypoint_invocation(
+            let invocation = sub_router.catch_all_pipeline.entrypoint_invocation(
                 singleton_bindings,
                 request_scoped_bindings,
                 &server_state_ident,
+            );
+            with_cache_control(
+                invocation,
+                sub_router.catch_all_cache_control.as_deref(),
+                pavex,
             )
         } else {
             let mut sub_router_dispatch_table = quote! {};
@@ -347,7 +485,7 @@ fn get_request_dispatcher(
                 let allowed_methods = sub_router
                     .methods_and_pipelines
                     .iter()
-                    .flat_map(|(methods, _)| methods)
+                    .flat_map(|(methods, _, _)| methods)
                     .map(|m| {
                         if WELL_KNOWN_METHODS.contains(m.as_str()) {
                             let i = format_ident!("{}", m);
@@ -368,12 +506,13 @@ fn get_request_dispatcher(
                 }
             };
 
-            for (methods, request_pipeline) in &sub_router.methods_and_pipelines {
+            for (methods, request_pipeline, cache_control) in &sub_router.methods_and_pipelines {
                 let invocation = request_pipeline.entrypoint_invocation(
                     singleton_bindings,
                     request_scoped_bindings,
                     &server_state_ident,
                 );
+                let invocation = with_cache_control(invocation, cache_control.as_deref(), pavex);
                 let invocation = if request_pipeline.needs_allowed_methods(framework_items_db) {
                     quote! {
                         {
@@ -429,7 +568,16 @@ fn get_request_dispatcher(
                 request_scoped_bindings,
                 &server_state_ident,
             );
-            if sub_router
+            if sub_router.method_not_allowed {
+                // The `Allow` header is set by the framework if the handler doesn't set it.
+                fallback_invocation = quote! {
+                    {
+                        #allowed_methods_init
+                        let response = #fallback_invocation;
+                        #pavex::router::ensure_allow_header(response, &allowed_methods)
+                    }
+                };
+            } else if sub_router
                 .catch_all_pipeline
                 .needs_allowed_methods(framework_items_db)
             {
@@ -475,35 +623,156 @@ fn get_request_dispatcher(
     } else {
         quote! {}
     };
-    syn::parse2(quote! {
+    let path_match = if has_priority_levels {
+        // Routers are sorted by priority: the first match wins.
+        quote! {
+            server_state
+                .router
+                .iter()
+                .find_map(|router| router.at(&request_head.target.path()).ok())
+                .ok_or(())
+        }
+    } else {
+        quote! { server_state.router.at(&request_head.target.path()) }
+    };
+    let routing = quote! {
+        let matched_route = match #path_match {
+            Ok(m) => m,
+            Err(_) => {
+                #allowed_methods
+                #unmatched_route
+                return #root_fallback_invocation;
+            }
+        };
+        let route_id = matched_route.value;
+        #[allow(unused)]
+        let url_params: #pavex::request::path::RawPathParams<'_, '_> = matched_route
+            .params
+            .into();
+        match route_id {
+            #route_dispatch_table
+            i => unreachable!("Unknown route id: {}", i),
+        }
+    };
+    // The response hook, if registered, is invoked on the response returned by the routing
+    // logic—i.e. after all middlewares, request handlers and error handlers have been executed.
+    // Internal requests issued via a `Dispatcher` don't go through the hook: it would otherwise
+    // be applied multiple times to the same outgoing response.
+    let response_hook_invocation = response_hook.map(|hook| {
+        let callable = hook.callable.as_ref();
+        let mut bindings: HashMap<ResolvedType, Box<dyn quote::ToTokens>> = HashMap::default();
+        bindings.insert(callable.inputs[0].clone(), Box::new(quote! { response }));
+        // If the hook needs the request head, the request processing pipeline is guaranteed
+        // to only borrow it (see `verify_response_hook`): it's still around when the response
+        // is ready.
+        if hook.needs_request_head() {
+            bindings.insert(
+                callable.inputs[1].clone(),
+                Box::new(quote! { &request_head }),
+            );
+        }
+        codegen_call(callable, &bindings, package_id2name)
+    });
+    // The local address is attached to the incoming request by Pavex's server, as an
+    // extension. We only extract it if at least one component needs it.
+    // It should never be missing, but we don't want to panic on the request path if that
+    // invariant is ever broken: we return a 500 instead.
+    let local_addr_init = if needs_local_addr {
+        quote! {
+            let Some(&local_addr) = request_head
+                .extensions
+                .get::<#pavex::connection::LocalAddr>() else {
+                return #pavex::response::Response::internal_server_error();
+            };
+        }
+    } else {
+        quote! {}
+    };
+    if !needs_dispatcher {
+        let routing = match response_hook_invocation {
+            None => routing,
+            Some(hook_invocation) => quote! {
+                let response = async { #routing }.await;
+                #hook_invocation
+            },
+        };
+        return quote! {
+            async fn route_request(
+                request: #http::Request<#hyper::body::Incoming>,
+                #server_state_ident: std::sync::Arc<ServerState>
+            ) -> #pavex::response::Response {
+                let (request_head, request_body) = request.into_parts();
+                #local_addr_init
+                #[allow(unused)]
+                let request_body = #pavex::request::body::RawIncomingBody::from(request_body);
+                let request_head: #pavex::request::RequestHead = request_head.into();
+                #routing
+            }
+        };
+    }
+    // Internal requests inherit the local address of the request that spawned them.
+    let (local_addr_arg, local_addr_param) = if needs_local_addr {
+        (
+            quote! { local_addr, },
+            quote! { local_addr: #pavex::connection::LocalAddr, },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+    let dispatch = quote! {
+        dispatch_request(request_head, request_body, #server_state_ident, #local_addr_arg 0).await
+    };
+    // The hook is invoked within `dispatch_request`, where the request head lives, but only for
+    // the request received from the network.
+    let routing = match response_hook_invocation {
+        None => routing,
+        Some(hook_invocation) => quote! {
+            let response = async { #routing }.await;
+            if dispatch_depth == 0 {
+                #hook_invocation
+            } else {
+                response
+            }
+        },
+    };
+    // At least one component needs a `Dispatcher`: we split routing into its own function,
+    // so that it can be invoked recursively to process internal requests.
+    // The returned future must be boxed to break the recursive type.
+    quote! {
         async fn route_request(
             request: #http::Request<#hyper::body::Incoming>,
             #server_state_ident: std::sync::Arc<ServerState>
         ) -> #pavex::response::Response {
             let (request_head, request_body) = request.into_parts();
-            #[allow(unused)]
+            #local_addr_init
             let request_body = #pavex::request::body::RawIncomingBody::from(request_body);
             let request_head: #pavex::request::RequestHead = request_head.into();
-            let matched_route = match server_state.router.at(&request_head.target.path()) {
-                Ok(m) => m,
-                Err(_) => {
-                    #allowed_methods
-                    #unmatched_route
-                    return #root_fallback_invocation;
-                }
-            };
-            let route_id = matched_route.value;
+            #dispatch
+        }
+
+        fn dispatch_request(
+            request_head: #pavex::request::RequestHead,
             #[allow(unused)]
-            let url_params: #pavex::request::path::RawPathParams<'_, '_> = matched_route
-                .params
-                .into();
-            match route_id {
-                #route_dispatch_table
-                i => unreachable!("Unknown route id: {}", i),
-            }
+            request_body: #pavex::request::body::RawIncomingBody,
+            #server_state_ident: std::sync::Arc<ServerState>,
+            #local_addr_param
+            dispatch_depth: usize,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = #pavex::response::Response>>> {
+            Box::pin(async move {
+                #[allow(unused)]
+                let dispatcher = {
+                    let #server_state_ident = #server_state_ident.clone();
+                    #pavex::router::Dispatcher::new(
+                        dispatch_depth,
+                        move |request_head, request_body, depth| {
+                            dispatch_request(request_head, request_body, #server_state_ident.clone(), #local_addr_arg depth)
+                        },
+                    )
+                };
+                #routing
+            })
         }
-    })
-    .unwrap()
+    }
 }
 
 pub(crate) fn codegen_manifest<'a, I>(
@@ -664,6 +933,29 @@ where
     (dependencies, package_ids2dependency_name)
 }
 
+/// Set the `Cache-Control` header on the response returned by `invocation`,
+/// unless the response already includes one.
+///
+/// `invocation` is returned unchanged if the route doesn't have caching directives.
+fn with_cache_control(
+    invocation: TokenStream,
+    cache_control: Option<&str>,
+    pavex: &Ident,
+) -> TokenStream {
+    let Some(cache_control) = cache_control else {
+        return invocation;
+    };
+    quote! {
+        {
+            let response = #invocation;
+            #pavex::response::ensure_cache_control(
+                response,
+                #pavex::http::HeaderValue::from_static(#cache_control)
+            )
+        }
+    }
+}
+
 fn collect_package_ids<'a, I>(
     handler_pipelines: I,
     application_state_call_graph: &'a RawCallGraph,
@@ -688,6 +980,9 @@ where
         computation_db,
         application_state_call_graph,
     );
+    if let Some(response_hook_id) = component_db.response_hook_id() {
+        collect_callable_package_ids(&mut package_ids, &computation_db[response_hook_id]);
+    }
     for handler_pipeline in handler_pipelines {
         for graph in handler_pipeline.graph_iter() {
             collect_call_graph_package_ids(
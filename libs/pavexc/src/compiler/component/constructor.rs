@@ -3,6 +3,7 @@ use std::borrow::Cow;
 use indexmap::IndexSet;
 
 use crate::compiler::computation::{Computation, MatchResult};
+use crate::compiler::utils::{get_err_variant, get_ok_variant};
 use crate::language::ResolvedType;
 
 /// Build a new instance of a type by performing a computation.
@@ -106,6 +107,35 @@ impl<'a> Constructor<'a> {
         self.output_type().is_result()
     }
 
+    /// Returns `true` if the constructor is fallible and its error type is
+    /// `pavex::response::Response`—e.g. `Result<Session, Response>`.
+    ///
+    /// The error of these constructors doesn't need an error handler: it is returned
+    /// to the caller as it is, short-circuiting the rest of the request processing pipeline.
+    pub fn short_circuits_with_a_response(&self) -> bool {
+        if !self.is_fallible() {
+            return false;
+        }
+        let ResolvedType::ResolvedPath(error_type) = get_err_variant(self.output_type()) else {
+            return false;
+        };
+        error_type.base_type == ["pavex", "response", "Response"]
+    }
+
+    /// Returns `true` if the type built by the constructor (i.e. the `Ok` variant, if the
+    /// constructor is fallible) has non-`'static` lifetime parameters—e.g. `&DatabaseSettings`
+    /// or `Cow<'a, str>`.
+    ///
+    /// The output of these constructors borrows from their inputs: it can't outlive them.
+    pub fn output_borrows_from_inputs(&self) -> bool {
+        let output = if self.is_fallible() {
+            get_ok_variant(self.output_type())
+        } else {
+            self.output_type()
+        };
+        output.has_implicit_lifetime_parameters() || !output.named_lifetime_parameters().is_empty()
+    }
+
     pub fn into_owned(self) -> Constructor<'static> {
         Constructor(self.0.into_owned())
     }
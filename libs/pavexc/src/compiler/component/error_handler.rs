@@ -26,28 +26,7 @@ impl ErrorHandler {
                 error_handler.path,
             ));
         }
-        let result_type = fallible_callable
-            .output
-            .as_ref()
-            .expect("Fallible callable must have an output type")
-            .clone();
-        assert!(
-            result_type.is_result(),
-            "Fallible callable must return a Result"
-        );
-        let error_type_ref = {
-            let ResolvedType::ResolvedPath(result_type) = result_type else {
-                unreachable!()
-            };
-            let GenericArgument::TypeParameter(e) = result_type.generic_arguments[1].clone() else {
-                unreachable!()
-            };
-            ResolvedType::Reference(TypeReference {
-                is_mutable: false,
-                lifetime: Lifetime::Elided,
-                inner: Box::new(e),
-            })
-        };
+        let error_type_ref = fallible_error_type_ref(fallible_callable);
         // TODO: verify that the error handler does NOT return a `Result`
         // TODO: return a more specific error if the error handler takes the error as an input
         //  parameter by value instead of taking it by reference.
@@ -99,6 +78,15 @@ impl ErrorHandler {
         })
     }
 
+    /// Return `true` if `error_handler` takes a reference to the error type of
+    /// `fallible_callable` as one of its input parameters.
+    ///
+    /// It doesn't perform any of the other checks carried out by [`ErrorHandler::new`].
+    pub(crate) fn handles_error_of(error_handler: &Callable, fallible_callable: &Callable) -> bool {
+        let error_type_ref = fallible_error_type_ref(fallible_callable);
+        error_handler.inputs.iter().any(|i| i == &error_type_ref)
+    }
+
     /// Return the error type that this error handler takes as input.
     ///
     /// This is a **reference** to the error type returned by the fallible callable
@@ -127,6 +115,31 @@ impl ErrorHandler {
     }
 }
 
+/// Return a reference to the error type of a fallible callable—i.e. `&E` if the
+/// callable returns `Result<T, E>`.
+fn fallible_error_type_ref(fallible_callable: &Callable) -> ResolvedType {
+    let result_type = fallible_callable
+        .output
+        .as_ref()
+        .expect("Fallible callable must have an output type")
+        .clone();
+    assert!(
+        result_type.is_result(),
+        "Fallible callable must return a Result"
+    );
+    let ResolvedType::ResolvedPath(result_type) = result_type else {
+        unreachable!()
+    };
+    let GenericArgument::TypeParameter(e) = result_type.generic_arguments[1].clone() else {
+        unreachable!()
+    };
+    ResolvedType::Reference(TypeReference {
+        is_mutable: false,
+        lifetime: Lifetime::Elided,
+        inner: Box::new(e),
+    })
+}
+
 impl From<ErrorHandler> for Callable {
     fn from(e: ErrorHandler) -> Self {
         e.callable
@@ -1,9 +1,11 @@
 mod constructor;
 mod error_handler;
 mod request_handler;
+mod response_hook;
 mod wrapping_middleware;
 
 pub(crate) use constructor::{Constructor, ConstructorValidationError};
 pub(crate) use error_handler::{ErrorHandler, ErrorHandlerValidationError};
 pub(crate) use request_handler::{RequestHandler, RequestHandlerValidationError};
+pub(crate) use response_hook::{ResponseHook, ResponseHookValidationError};
 pub(crate) use wrapping_middleware::{WrappingMiddleware, WrappingMiddlewareValidationError};
@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+
+use crate::language::{Callable, ResolvedType};
+
+/// A callable that gets invoked on every outgoing response, after all middlewares, request
+/// handlers and error handlers have been executed.
+///
+/// # Input parameters
+///
+/// Response hooks must take a `pavex::response::Response` as their first input parameter.
+/// They can, optionally, take a `&pavex::request::RequestHead` as second input parameter.
+///
+/// # Output type
+///
+/// Response hooks must return a `pavex::response::Response`—they can't fail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ResponseHook<'a> {
+    pub(crate) callable: Cow<'a, Callable>,
+}
+
+impl<'a> ResponseHook<'a> {
+    /// Creates a new response hook from a callable, either owned or borrowed.
+    ///
+    /// This function validates that the callable satisfies all the constraints of
+    /// a response hook. An error is returned if it doesn't.
+    pub fn new(c: Cow<'a, Callable>) -> Result<Self, ResponseHookValidationError> {
+        use ResponseHookValidationError::*;
+
+        match c.output.as_ref() {
+            Some(output) if is_response(output) => {}
+            _ => return Err(MustReturnAResponse),
+        }
+        match c.inputs.as_slice() {
+            [response] if is_response(response) => {}
+            [response, request_head]
+                if is_response(response) && is_request_head_ref(request_head) => {}
+            _ => return Err(InvalidInputParameters),
+        }
+
+        Ok(Self { callable: c })
+    }
+
+    /// Returns `true` if the hook takes `&RequestHead` as input parameter.
+    pub fn needs_request_head(&self) -> bool {
+        self.callable.inputs.len() == 2
+    }
+}
+
+/// Returns `true` if the given type is an owned `pavex::response::Response`.
+fn is_response(t: &ResolvedType) -> bool {
+    let ResolvedType::ResolvedPath(t) = t else {
+        return false;
+    };
+    t.base_type == ["pavex", "response", "Response"]
+}
+
+/// Returns `true` if the given type is a shared reference to `pavex::request::RequestHead`.
+fn is_request_head_ref(t: &ResolvedType) -> bool {
+    let ResolvedType::Reference(r) = t else {
+        return false;
+    };
+    if r.is_mutable {
+        return false;
+    }
+    let ResolvedType::ResolvedPath(t) = r.inner.as_ref() else {
+        return false;
+    };
+    t.base_type == ["pavex", "request", "RequestHead"]
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum ResponseHookValidationError {
+    #[error(
+        "Response hooks must return a `pavex::response::Response`.\n\
+        This response hook doesn't."
+    )]
+    MustReturnAResponse,
+    #[error(
+        "Response hooks must take a `pavex::response::Response` as their first input parameter \
+        and, optionally, a `&pavex::request::RequestHead` as their second input parameter.\n\
+        This response hook doesn't follow this signature."
+    )]
+    InvalidInputParameters,
+}
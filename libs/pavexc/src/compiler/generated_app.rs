@@ -4,7 +4,8 @@ use std::path::{Path, PathBuf};
 
 use cargo_manifest::{Dependency, Edition};
 use guppy::graph::PackageGraph;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
 use serde::Serialize;
 use toml_edit::ser::ValueSerializer;
 
@@ -15,11 +16,27 @@ use persist_if_changed::persist_if_changed;
 ///
 /// Built by [`App::codegen`](crate::compiler::App::codegen).
 pub struct GeneratedApp {
+    /// The items that live at the root of the generated crate—application state,
+    /// router, request dispatcher, etc.
     pub(crate) lib_rs: TokenStream,
+    /// The request processing pipeline of each route, one module per route.
+    pub(crate) route_modules: Vec<RouteModule>,
+    /// If `true`, each route module is persisted in its own file rather than
+    /// inlined in `lib.rs`.
+    pub(crate) split_modules: bool,
     pub(crate) cargo_toml: GeneratedManifest,
     pub(crate) package_graph: PackageGraph,
 }
 
+#[derive(Clone)]
+/// The code generated for the request processing pipeline of a single route.
+pub(crate) struct RouteModule {
+    /// The name of the module—e.g. `route_0`.
+    pub(crate) name: Ident,
+    /// The items in the module, without the `mod` declaration itself.
+    pub(crate) code: TokenStream,
+}
+
 #[derive(Clone, Debug)]
 /// The fields that we *must* control in the manifest for the generated application.  
 pub struct GeneratedManifest {
@@ -55,23 +72,42 @@ impl GeneratedManifest {
     }
 }
 
+/// The header prepended to every source file that Pavex generates.
+///
+/// It's also used to tell apart the files that Pavex generated from the ones
+/// written by the user—see [`GeneratedApp::remove_stale_route_files`].
+const GENERATED_FILE_HEADER: &str = "\
+//! Do NOT edit this code.
+//! It was automatically generated by Pavex.
+//! All manual edits will be lost next time the code is generated.
+";
+
 impl GeneratedApp {
+    /// Persist the code generated for each route in its own file (`src/route_<n>.rs`),
+    /// rather than inlining all of it in `lib.rs`.
+    ///
+    /// The module hierarchy is the same in both layouts: `lib.rs` declares a `route_<n>`
+    /// module for each route and the generated crate exposes the same items either way.
+    /// It keeps the size of each generated file in check for applications with many
+    /// routes, making the generated code easier to navigate, review and diff.
+    /// Files are only rewritten if their content changed.
+    pub fn split_modules(mut self) -> Self {
+        self.split_modules = true;
+        self
+    }
+
     /// Save the code and the manifest for the generated application to disk.
     /// The newly created library crate is also injected as a member into the current workspace.
     #[tracing::instrument(skip_all, level = tracing::Level::INFO)]
     pub fn persist(self, directory: &Path) -> Result<(), anyhow::Error> {
+        let source_files = self.render_source_files()?;
         let Self {
-            lib_rs,
             mut cargo_toml,
             package_graph,
+            ..
         } = self;
         let workspace = package_graph.workspace();
-
-        let pkg_directory = if directory.is_relative() {
-            workspace.root().as_std_path().join(directory)
-        } else {
-            directory.to_path_buf()
-        };
+        let pkg_directory = Self::package_directory(&workspace, directory);
 
         Self::normalize_path_dependencies(&mut cargo_toml, &pkg_directory)?;
         Self::inject_app_into_workspace_members(&workspace, &pkg_directory)?;
@@ -80,9 +116,100 @@ impl GeneratedApp {
         fs_err::create_dir_all(&source_directory)?;
         Self::persist_manifest(&cargo_toml, &pkg_directory)?;
 
-        let lib_rs = prettyplease::unparse(&syn::parse2(lib_rs)?);
-        persist_if_changed(&source_directory.join("lib.rs"), lib_rs.as_bytes())?;
+        Self::remove_stale_route_files(&source_directory, &source_files)?;
+        for (filename, code) in source_files {
+            persist_if_changed(&source_directory.join(filename), code.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Go through the same steps as [`persist`](Self::persist), without touching the filesystem.
+    ///
+    /// It returns the paths of the files that [`persist`](Self::persist) would write
+    /// for the generated crate, relative to the root of the current workspace.
+    /// The generated code is rendered in full: an error is returned if it's not valid Rust.
+    pub fn dry_run(&self, directory: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let source_files = self.render_source_files()?;
+        let workspace = self.package_graph.workspace();
+        let pkg_directory = Self::package_directory(&workspace, directory);
+        let pkg_directory = pathdiff::diff_paths(&pkg_directory, workspace.root().as_std_path())
+            .unwrap_or(pkg_directory);
+
+        let mut files = vec![pkg_directory.join("Cargo.toml")];
+        files.extend(
+            source_files
+                .into_iter()
+                .map(|(filename, _)| pkg_directory.join("src").join(filename)),
+        );
+        Ok(files)
+    }
+
+    /// Relative paths are interpreted as relative to the root of the workspace.
+    fn package_directory(workspace: &guppy::graph::Workspace, directory: &Path) -> PathBuf {
+        if directory.is_relative() {
+            workspace.root().as_std_path().join(directory)
+        } else {
+            directory.to_path_buf()
+        }
+    }
 
+    /// Render the source files of the generated crate, in memory.
+    ///
+    /// Each file is returned alongside its name, relative to the `src` directory.
+    /// `lib.rs` always comes first.
+    fn render_source_files(&self) -> Result<Vec<(String, String)>, anyhow::Error> {
+        let mut module_declarations = Vec::with_capacity(self.route_modules.len());
+        let mut route_files = Vec::new();
+        for RouteModule { name, code } in &self.route_modules {
+            if self.split_modules {
+                module_declarations.push(quote! { pub mod #name; });
+                route_files.push((format!("{name}.rs"), code));
+            } else {
+                module_declarations.push(quote! { pub mod #name { #code } });
+            }
+        }
+        let lib_rs = &self.lib_rs;
+        let lib_rs = quote! {
+            #lib_rs
+            #(#module_declarations)*
+        };
+        let mut files = vec![("lib.rs".to_string(), Self::render_file(lib_rs)?)];
+        for (filename, code) in route_files {
+            files.push((filename, Self::render_file(code.clone())?));
+        }
+        Ok(files)
+    }
+
+    /// Pretty-print `code` and prepend [`GENERATED_FILE_HEADER`] to it.
+    fn render_file(code: TokenStream) -> Result<String, anyhow::Error> {
+        let code = prettyplease::unparse(&syn::parse2(code)?);
+        Ok(format!("{GENERATED_FILE_HEADER}{code}"))
+    }
+
+    /// Remove the route files left behind by a previous code generation run—e.g.
+    /// if a route has been removed or if the application is no longer generated with
+    /// [`split_modules`](Self::split_modules).
+    ///
+    /// Only files that were generated by Pavex are removed.
+    fn remove_stale_route_files(
+        source_directory: &Path,
+        source_files: &[(String, String)],
+    ) -> Result<(), anyhow::Error> {
+        for entry in fs_err::read_dir(source_directory)? {
+            let path = entry?.path();
+            let Some(filename) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let is_route_file = filename.starts_with("route_") && filename.ends_with(".rs");
+            if !is_route_file || source_files.iter().any(|(f, _)| f == filename) {
+                continue;
+            }
+            let contents = fs_err::read_to_string(&path)?;
+            if contents.starts_with(GENERATED_FILE_HEADER) {
+                fs_err::remove_file(&path)?;
+            }
+        }
         Ok(())
     }
 
@@ -5,7 +5,7 @@ use itertools::Itertools;
 use miette::Report;
 use petgraph::graph::NodeIndex;
 use petgraph::Direction;
-use rustdoc_types::{ItemEnum, StructKind};
+use rustdoc_types::{ItemEnum, StructKind, Type};
 
 use crate::compiler::analyses::call_graph::{CallGraphNode, RawCallGraph};
 use crate::compiler::analyses::components::{ComponentDb, ComponentId, HydratedComponent};
@@ -144,6 +144,14 @@ pub(crate) fn verify_path_parameters(
                         rustdoc_item_id: field_id.clone(),
                         package_id: extracted_path_type.package_id.clone(),
                     });
+                    // Fields of type `Option<T>` don't have to appear in the path pattern:
+                    // they'll be set to `None` when the route doesn't capture them.
+                    if let ItemEnum::StructField(Type::ResolvedPath(field_type)) = &field_item.inner
+                    {
+                        if is_option(&field_type.name) {
+                            continue;
+                        }
+                    }
                     struct_field_names.insert(field_item.name.clone().unwrap());
                 }
                 struct_field_names
@@ -171,6 +179,14 @@ pub(crate) fn verify_path_parameters(
     }
 }
 
+/// Returns `true` if the path (as spelled in rustdoc's JSON) points at `Option`.
+fn is_option(path: &str) -> bool {
+    matches!(
+        path,
+        "Option" | "std::option::Option" | "core::option::Option" | "option::Option"
+    )
+}
+
 /// Report an error on each compute node that consumes the `PathParams` extractor
 /// while trying to extract one or more path parameters that are not present in
 /// the respective path pattern.
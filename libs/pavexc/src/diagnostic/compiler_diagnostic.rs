@@ -5,6 +5,7 @@ use miette::{Diagnostic, LabeledSpan, NamedSource, Severity, SourceCode};
 /// A builder for a [`CompilerDiagnostic`].
 pub struct CompilerDiagnosticBuilder {
     severity: Severity,
+    code: Option<&'static str>,
     source_code: NamedSource,
     labels: Option<Vec<LabeledSpan>>,
     help: Option<String>,
@@ -17,6 +18,7 @@ impl CompilerDiagnosticBuilder {
     fn new(source_code: impl Into<NamedSource>, error: impl Into<anyhow::Error>) -> Self {
         Self {
             severity: Severity::Error,
+            code: None,
             source_code: source_code.into(),
             labels: None,
             help: None,
@@ -120,10 +122,30 @@ impl CompilerDiagnosticBuilder {
         self
     }
 
+    /// Report this diagnostic as a warning rather than an error.
+    ///
+    /// Warnings are shown to the user, but they don't prevent Pavex from generating
+    /// the application code.
+    pub fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    /// Attach a unique code to this diagnostic.
+    ///
+    /// Codes are part of Pavex's public interface: users rely on them to assert,
+    /// programmatically, which diagnostics were emitted for a blueprint.
+    /// Don't change them once they've been released!
+    pub fn code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     /// Finalize the builder and return a [`CompilerDiagnostic`].
     pub fn build(self) -> CompilerDiagnostic {
         let Self {
             severity,
+            code,
             source_code,
             labels,
             help,
@@ -165,6 +187,7 @@ impl CompilerDiagnosticBuilder {
         CompilerDiagnostic {
             source_code,
             severity,
+            code,
             labels,
             help,
             error_source,
@@ -195,6 +218,14 @@ impl CompilerDiagnostic {
     ) -> CompilerDiagnosticBuilder {
         CompilerDiagnosticBuilder::new(source_code, error)
     }
+
+    /// The unique code attached to this diagnostic, if any.
+    ///
+    /// It isn't exposed via [`miette::Diagnostic::code`] since it's not meant to be
+    /// rendered in the terminal: it's surfaced via [`Diagnostic`](super::Diagnostic).
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
 }
 
 /// An help message supported by an annotated code snippet.
@@ -256,6 +287,7 @@ impl AnnotatedSnippet {
 pub struct CompilerDiagnostic {
     source_code: NamedSource,
     severity: Severity,
+    code: Option<&'static str>,
     labels: Option<Vec<LabeledSpan>>,
     help: Option<String>,
     #[source]
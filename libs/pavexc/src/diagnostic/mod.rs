@@ -7,8 +7,10 @@ pub(crate) use compiler_diagnostic::{
 pub(crate) use ordinals::ZeroBasedOrdinal;
 pub(crate) use proc_macro_utils::ProcMacroSpanExt;
 pub(crate) use registration_locations::{
-    get_bp_new_span, get_f_macro_invocation_span, get_nest_at_prefix_span, get_route_path_span,
+    get_base_path_span, get_bp_new_span, get_f_macro_invocation_span, get_nest_at_prefix_span,
+    get_route_path_span,
 };
+pub use report::{Diagnostic, DiagnosticLabel, Severity};
 pub(crate) use source_file::{read_source_file, LocationExt, ParsedSourceFile};
 
 pub(crate) use self::miette::{
@@ -20,6 +22,7 @@ mod miette;
 mod ordinals;
 mod proc_macro_utils;
 mod registration_locations;
+mod report;
 mod source_file;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -28,6 +31,7 @@ pub enum CallableType {
     Constructor,
     ErrorHandler,
     WrappingMiddleware,
+    ResponseHook,
 }
 
 impl Display for CallableType {
@@ -37,7 +41,16 @@ impl Display for CallableType {
             CallableType::Constructor => "constructor",
             CallableType::ErrorHandler => "error handler",
             CallableType::WrappingMiddleware => "wrapping middleware",
+            CallableType::ResponseHook => "response hook",
         };
         write!(f, "{s}")
     }
 }
+
+/// Returns `true` if at least one of the diagnostics is an error—i.e. it's not
+/// a warning or an advice.
+pub(crate) fn has_errors(diagnostics: &[::miette::Error]) -> bool {
+    diagnostics
+        .iter()
+        .any(|d| matches!(d.severity(), None | Some(::miette::Severity::Error)))
+}
@@ -38,7 +38,9 @@ pub(crate) fn get_f_macro_invocation_span(
     match node {
         Call::MethodCall(node) => {
             let argument = match node.method.to_string().as_str() {
-                "error_handler" | "constructor" | "wrap" | "fallback" => node.args.first(),
+                "error_handler" | "constructor" | "wrap" | "fallback" | "require_auth" => {
+                    node.args.first()
+                }
                 "route" => node.args.iter().nth(2),
                 s => {
                     tracing::trace!(
@@ -180,6 +182,47 @@ pub(crate) fn get_nest_at_prefix_span(
     Some(convert_proc_macro_span(raw_source, span))
 }
 
+/// Location, obtained via `#[track_caller]` and `std::panic::Location::caller`, points at the
+/// `.` in the method invocation for `base_path`.
+/// E.g.
+///
+/// ```rust,ignore
+/// bp.base_path("/service-a")
+/// //^ `location` points here!
+/// ```
+///
+/// We build a `SourceSpan` that matches the base path argument.
+/// E.g.
+///
+/// ```rust,ignore
+/// bp.base_path("/service-a")
+/// //           ^^^^^^^^^^^^
+/// //           We want a SourceSpan that points at this
+/// ```
+pub(crate) fn get_base_path_span(
+    source: &ParsedSourceFile,
+    location: &Location,
+) -> Option<SourceSpan> {
+    let raw_source = &source.contents;
+    let node = find_method_call(location, &source.parsed)?;
+    let argument = match node {
+        Call::MethodCall(node) => {
+            if node.method != "base_path" {
+                tracing::trace!(
+                    "Unknown method name when looking for a `base_path` invocation: {}",
+                    node.method
+                );
+                return None;
+            }
+            // bp.base_path(path)
+            node.args.first()
+        }
+        // Blueprint::base_path(bp, path)
+        Call::FunctionCall(node) => node.args.iter().nth(1),
+    }?;
+    Some(convert_proc_macro_span(raw_source, argument.span()))
+}
+
 /// Location, obtained via `#[track_caller]` and `std::panic::Location::caller`, points at the
 /// `(` in the method invocation for `Blueprint::new`.
 /// E.g.
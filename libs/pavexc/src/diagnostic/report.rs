@@ -0,0 +1,209 @@
+//! A structured representation of the diagnostics emitted by Pavex, meant to be
+//! inspected programmatically (e.g. in tests) rather than rendered in a terminal.
+use miette::{LabeledSpan, SourceCode};
+
+use super::CompilerDiagnostic;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+/// A single error or warning emitted by Pavex while analyzing a [`Blueprint`].
+///
+/// You'll usually get your hands on a [`Diagnostic`] via [`App::analyze`]:
+///
+/// ```rust,ignore
+/// use pavexc::App;
+///
+/// let diagnostics = App::analyze(blueprint(), fingerprint).unwrap_err();
+/// assert!(diagnostics
+///     .iter()
+///     .any(|d| d.code.as_deref() == Some("pavex::router::handler_conflict")));
+/// ```
+///
+/// Warnings don't cause the analysis to fail: you can find them via [`App::warnings`].
+///
+/// [`Blueprint`]: pavex::blueprint::Blueprint
+/// [`App::analyze`]: crate::App::analyze
+/// [`App::warnings`]: crate::App::warnings
+pub struct Diagnostic {
+    /// How serious the issue is.
+    pub severity: Severity,
+    /// A unique identifier for the kind of issue that was detected—e.g.
+    /// `pavex::router::handler_conflict`.
+    ///
+    /// Codes are stable: you can rely on them in your assertions.
+    /// Not all diagnostics have a code (yet).
+    pub code: Option<String>,
+    /// The main error message.
+    pub message: String,
+    /// A suggestion on how to fix the issue, if there is one.
+    pub help: Option<String>,
+    /// The source code locations that the diagnostic points at.
+    pub labels: Vec<DiagnosticLabel>,
+    /// Additional diagnostics attached to this one—e.g. help messages that point
+    /// at a different source location.
+    pub related: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// See [`Diagnostic::severity`].
+pub enum Severity {
+    /// A critical issue: code generation can't proceed.
+    Error,
+    /// Something looks off, but code generation can proceed.
+    Warning,
+    /// A suggestion or an additional piece of information.
+    Advice,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+/// A source code location highlighted by a [`Diagnostic`].
+pub struct DiagnosticLabel {
+    /// The text attached to the highlighted location, if any.
+    pub text: Option<String>,
+    /// The name of the source file, if known.
+    pub file: Option<String>,
+    /// The 1-based line number where the highlighted location starts, if known.
+    pub line: Option<usize>,
+    /// The 1-based column number where the highlighted location starts, if known.
+    pub column: Option<usize>,
+    /// The byte offset where the highlighted location starts.
+    pub offset: usize,
+    /// The length of the highlighted location, in bytes.
+    pub len: usize,
+}
+
+impl From<&miette::Error> for Diagnostic {
+    fn from(error: &miette::Error) -> Self {
+        let code = error
+            .downcast_ref::<CompilerDiagnostic>()
+            .and_then(|d| d.code())
+            .map(ToOwned::to_owned);
+        let mut diagnostic = Diagnostic::new(&**error, None);
+        diagnostic.code = code;
+        diagnostic
+    }
+}
+
+impl Diagnostic {
+    fn new(d: &dyn miette::Diagnostic, parent_source_code: Option<&dyn SourceCode>) -> Diagnostic {
+        let source_code = d.source_code().or(parent_source_code);
+        let labels_of = |d: &dyn miette::Diagnostic| -> Vec<DiagnosticLabel> {
+            let source_code = d.source_code().or(source_code);
+            d.labels()
+                .into_iter()
+                .flatten()
+                .map(|l| DiagnosticLabel::new(&l, source_code))
+                .collect()
+        };
+
+        let mut labels = labels_of(d);
+        let mut related = vec![];
+        for r in d.related().into_iter().flatten() {
+            // Related diagnostics with an empty message are just additional code snippets
+            // for the parent diagnostic—see `CompilerDiagnosticBuilder::build`.
+            if r.to_string().is_empty() {
+                labels.extend(labels_of(r));
+            } else {
+                related.push(Diagnostic::new(r, source_code));
+            }
+        }
+
+        Diagnostic {
+            severity: match d.severity() {
+                Some(miette::Severity::Warning) => Severity::Warning,
+                Some(miette::Severity::Advice) => Severity::Advice,
+                Some(miette::Severity::Error) | None => Severity::Error,
+            },
+            code: d.code().map(|c| c.to_string()),
+            message: d.to_string(),
+            help: d.help().map(|h| h.to_string()),
+            labels,
+            related,
+        }
+    }
+}
+
+impl DiagnosticLabel {
+    fn new(label: &LabeledSpan, source_code: Option<&dyn SourceCode>) -> Self {
+        let contents = source_code.and_then(|s| s.read_span(label.inner(), 0, 0).ok());
+        Self {
+            text: label.label().map(ToOwned::to_owned),
+            file: contents
+                .as_ref()
+                .and_then(|c| c.name())
+                .map(ToOwned::to_owned),
+            line: contents.as_ref().map(|c| c.line() + 1),
+            column: contents.as_ref().map(|c| c.column() + 1),
+            offset: label.offset(),
+            len: label.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pavex::blueprint::{router::GET, Blueprint};
+    use pavex::f;
+
+    use crate::App;
+
+    use super::Severity;
+
+    pub fn handler_1() -> pavex::response::Response {
+        todo!()
+    }
+
+    pub fn handler_2() -> pavex::response::Response {
+        todo!()
+    }
+
+    #[test]
+    fn router_conflicts_can_be_asserted_on() {
+        let mut bp = Blueprint::new();
+        // The labels point at the `f!` invocations.
+        let first_line = line!() + 4;
+        bp.route(
+            GET,
+            "/home",
+            f!(crate::diagnostic::report::tests::handler_1),
+        );
+        let second_line = line!() + 4;
+        bp.route(
+            GET,
+            "/home",
+            f!(crate::diagnostic::report::tests::handler_2),
+        );
+
+        let diagnostics = match App::analyze(bp, "router_conflicts_can_be_asserted_on".into()) {
+            Ok(_) => panic!("Expected the analysis to fail"),
+            Err(diagnostics) => diagnostics,
+        };
+        let conflict = diagnostics
+            .iter()
+            .find(|d| d.code.as_deref() == Some("pavex::router::handler_conflict"))
+            .expect("Expected a router conflict to be reported");
+        assert_eq!(conflict.severity, Severity::Error);
+        assert!(conflict
+            .message
+            .starts_with("I don't know how to route incoming `GET /home` requests"));
+
+        let labels: Vec<_> = conflict
+            .labels
+            .iter()
+            .map(|l| (l.text.as_deref().unwrap(), l.line.unwrap()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("The first conflicting handler", first_line as usize),
+                ("The second conflicting handler", second_line as usize),
+            ]
+        );
+        assert!(conflict
+            .labels
+            .iter()
+            .all(|l| l.file.as_deref().unwrap().ends_with("report.rs")));
+    }
+}
@@ -1,6 +1,7 @@
 extern crate core;
 
 pub use compiler::App;
+pub use diagnostic::{Diagnostic, DiagnosticLabel, Severity};
 
 mod compiler;
 mod diagnostic;
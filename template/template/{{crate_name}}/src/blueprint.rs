@@ -1,7 +1,7 @@
-use pavex::blueprint::constructor::CloningStrategy;
-use pavex::blueprint::{constructor::Lifecycle, router::GET, Blueprint};
+use pavex::blueprint::{router::GET, Blueprint};
 use pavex::request::{query::QueryParams, path::PathParams};
 use pavex::request::body::{BodySizeLimit, BufferedBody, JsonBody};
+use pavex::telemetry::{RootSpan, TraceContext, TraceContextPropagation};
 use pavex::f;
 
 /// The main blueprint, containing all the routes, constructors and error handlers
@@ -27,11 +27,9 @@ fn register_common_constructors(bp: &mut Blueprint) {
 
 /// Add the telemetry middleware, as well as the constructors of its dependencies.
 fn add_telemetry_middleware(bp: &mut Blueprint) {
-    bp.constructor(
-        f!(crate::telemetry::RootSpan::new),
-        Lifecycle::RequestScoped,
-    )
-    .cloning(CloningStrategy::CloneIfNecessary);
+    TraceContext::register(bp);
+    TraceContextPropagation::register(bp);
+    RootSpan::register(bp);
 
-    bp.wrap(f!(crate::telemetry::logger));
+    bp.wrap(f!(pavex::telemetry::logger));
 }
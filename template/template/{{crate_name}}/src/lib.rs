@@ -1,6 +1,5 @@
 mod blueprint;
 pub mod configuration;
 pub mod routes;
-pub mod telemetry;
 
 pub use blueprint::blueprint;